@@ -1,4 +0,0 @@
-pub mod addition_assignment;
-pub mod decrement_assignment;
-pub mod multiplication_assignment;
-pub mod division_assignment;
\ No newline at end of file
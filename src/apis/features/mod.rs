@@ -1,2 +1 @@
-pub mod assignment;
 pub mod object_features;
@@ -1,2 +1,3 @@
 pub mod type_variants;
-pub mod features;
\ No newline at end of file
+pub mod features;
+pub mod stdlib;
\ No newline at end of file
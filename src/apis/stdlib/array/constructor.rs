@@ -0,0 +1,110 @@
+use crate::apis::stdlib::{argument, callable, define_method};
+use crate::apis::type_variants::js_array::JSArray;
+use crate::apis::type_variants::js_object::JSObject;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+pub fn define_methods(interpretter: &Interpretter, array: &JSObject) {
+    define_method(interpretter, array, "isArray", 1, is_array);
+    define_method(interpretter, array, "of", 0, of);
+    define_method(interpretter, array, "from", 1, from);
+}
+
+/// `Array(length)` makes an empty array of that length, while any other
+/// arguments become the elements.
+pub fn array(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    if let [JSValueVariant::JSNumber(js_number)] = arguments {
+        let number = js_number.number_value();
+        let length = operations::to_uint32(number);
+
+        if length as f64 != number {
+            return Err(EndiumError::invalid_array_length());
+        }
+
+        let js_array = JSArray::new(Some(interpretter.array_prototype().clone()));
+        js_array.set_length(length)?;
+
+        return Ok(JSValueVariant::JSArray(js_array));
+    }
+
+    Ok(JSValueVariant::JSArray(
+        interpretter.create_array(arguments.to_vec()),
+    ))
+}
+
+fn is_array(
+    _interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    Ok(JSValueVariant::from(matches!(
+        argument(arguments, 0),
+        JSValueVariant::JSArray(_)
+    )))
+}
+
+fn of(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    Ok(JSValueVariant::JSArray(
+        interpretter.create_array(arguments.to_vec()),
+    ))
+}
+
+/// `Array.from(items, mapFn, thisArg)` accepts iterables as well as
+/// array-like objects with a `length`.
+fn from(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let items = argument(arguments, 0);
+    let map_function = match argument(arguments, 1) {
+        JSValueVariant::Undefined => None,
+        value => Some(callable(&value)?),
+    };
+    let this_arg = argument(arguments, 2);
+
+    let values = match &items {
+        JSValueVariant::JSObject(_) | JSValueVariant::JSFunction(_) => {
+            let length = operations::to_integer_or_infinity(operations::to_number(
+                &interpretter.handle_member(&items, "length")?,
+            ))
+            .clamp(0.0, u32::MAX as f64) as u32;
+
+            (0..length)
+                .map(|index| interpretter.handle_member(&items, &index.to_string()))
+                .collect::<Result<_, _>>()?
+        }
+        JSValueVariant::JSArray(_)
+        | JSValueVariant::JSString(_)
+        | JSValueVariant::Null
+        | JSValueVariant::Undefined => interpretter.iterate(&items)?,
+        _ => vec![],
+    };
+
+    let values = match map_function {
+        Some(map_function) => {
+            let mut mapped = vec![];
+            for (index, value) in values.into_iter().enumerate() {
+                mapped.push(interpretter.call_function(
+                    &map_function,
+                    this_arg.clone(),
+                    vec![value, JSValueVariant::from(index as f64)],
+                )?);
+            }
+            mapped
+        }
+        None => values,
+    };
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
@@ -0,0 +1,41 @@
+pub mod constructor;
+pub mod prototype;
+
+use crate::apis::type_variants::js_function::JSFunction;
+use crate::apis::type_variants::js_object::PropertyDescriptor;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::state::State;
+use crate::engine::value_variant::JSValueVariant;
+
+/// Fills in `Array.prototype` and binds the global `Array` constructor.
+pub fn install(interpretter: &mut Interpretter) {
+    let array_prototype = interpretter.array_prototype().clone();
+    prototype::define_methods(interpretter, &array_prototype);
+
+    let array = JSFunction::native(
+        "Array",
+        1,
+        constructor::array,
+        interpretter.function_prototype().clone(),
+    );
+    constructor::define_methods(interpretter, array.object());
+
+    array.object().define_property(
+        "prototype",
+        PropertyDescriptor::data(
+            JSValueVariant::JSObject(array_prototype.clone()),
+            false,
+            false,
+            false,
+        ),
+    );
+    array_prototype.define_property(
+        "constructor",
+        PropertyDescriptor::data(JSValueVariant::JSFunction(array.clone()), true, false, true),
+    );
+
+    interpretter.scope().borrow_mut().insert_state(
+        String::from("Array"),
+        State::new(JSValueVariant::JSFunction(array), true),
+    );
+}
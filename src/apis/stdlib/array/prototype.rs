@@ -0,0 +1,1037 @@
+use crate::apis::stdlib::{argument, callable, define_method};
+use crate::apis::type_variants::js_array::JSArray;
+use crate::apis::type_variants::js_function::JSFunction;
+use crate::apis::type_variants::js_object::{JSObject, array_index};
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+use std::cmp::Ordering;
+
+/// How deeply `flat` follows nested arrays, as deep as calls may nest.
+const MAX_FLATTEN_DEPTH: usize = 10_000;
+
+pub fn define_methods(interpretter: &Interpretter, prototype: &JSObject) {
+    define_method(interpretter, prototype, "push", 1, push);
+    define_method(interpretter, prototype, "pop", 0, pop);
+    define_method(interpretter, prototype, "shift", 0, shift);
+    define_method(interpretter, prototype, "unshift", 1, unshift);
+    define_method(interpretter, prototype, "slice", 2, slice);
+    define_method(interpretter, prototype, "splice", 2, splice);
+    define_method(interpretter, prototype, "concat", 1, concat);
+    define_method(interpretter, prototype, "join", 1, join);
+    define_method(interpretter, prototype, "indexOf", 1, index_of);
+    define_method(interpretter, prototype, "includes", 1, includes);
+    define_method(interpretter, prototype, "find", 1, find);
+    define_method(interpretter, prototype, "findIndex", 1, find_index);
+    define_method(interpretter, prototype, "findLast", 1, find_last);
+    define_method(interpretter, prototype, "filter", 1, filter);
+    define_method(interpretter, prototype, "map", 1, map);
+    define_method(interpretter, prototype, "forEach", 1, for_each);
+    define_method(interpretter, prototype, "reduce", 1, reduce);
+    define_method(interpretter, prototype, "reduceRight", 1, reduce_right);
+    define_method(interpretter, prototype, "some", 1, some);
+    define_method(interpretter, prototype, "every", 1, every);
+    define_method(interpretter, prototype, "sort", 1, sort);
+    define_method(interpretter, prototype, "reverse", 0, reverse);
+    define_method(interpretter, prototype, "flat", 0, flat);
+    define_method(interpretter, prototype, "flatMap", 1, flat_map);
+    define_method(interpretter, prototype, "fill", 1, fill);
+    define_method(interpretter, prototype, "at", 1, at);
+    define_method(interpretter, prototype, "toSorted", 1, to_sorted);
+    define_method(interpretter, prototype, "toReversed", 0, to_reversed);
+    define_method(interpretter, prototype, "with", 2, with);
+}
+
+/// The `this` of an array method as an array. Anything else is read as an
+/// array-like, such as `arguments`: its length and indexed properties are
+/// copied into a new array, which `write_back` copies back after a change.
+/// So is an array whose elements are not all plain, for its accessors and
+/// read-only properties to be respected.
+fn this_array(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    method: &str,
+) -> Result<JSArray, EndiumError> {
+    let (length, indices) = match this {
+        JSValueVariant::JSArray(js_array) if js_array.is_plain() => return Ok(js_array.clone()),
+        JSValueVariant::Null | JSValueVariant::Undefined => {
+            return Err(EndiumError::type_error(format!(
+                "Array.prototype.{} called on null or undefined",
+                method
+            )));
+        }
+        _ => (array_like_length(interpretter, this)?, index_keys(this)),
+    };
+
+    let js_array = JSArray::new(Some(interpretter.array_prototype().clone()));
+    js_array.set_length(length)?;
+
+    for index in indices.into_iter().filter(|index| *index < length) {
+        js_array.set(index, interpretter.handle_member(this, &index.to_string())?)?;
+    }
+
+    Ok(js_array)
+}
+
+/// The `length` of an array-like, as far as an array can hold it.
+fn array_like_length(
+    interpretter: &mut Interpretter,
+    value: &JSValueVariant,
+) -> Result<u32, EndiumError> {
+    let length = interpretter.handle_member(value, "length")?;
+
+    Ok(operations::to_length(operations::to_number(&length)).min(u32::MAX as u64) as u32)
+}
+
+/// The indices an array-like holds, in ascending order. Only own
+/// properties are looked at.
+fn index_keys(value: &JSValueVariant) -> Vec<u32> {
+    match value {
+        JSValueVariant::JSString(js_string) => (0..js_string.code_units().len())
+            .map(|index| index as u32)
+            .collect(),
+        JSValueVariant::JSArray(js_array) => js_array.own_indices(),
+        _ => Interpretter::object_of(value).map_or(vec![], |object| {
+            object
+                .keys()
+                .iter()
+                .filter_map(|key| array_index(key))
+                .collect()
+        }),
+    }
+}
+
+/// Copies the elements and length of `js_array` back onto the array-like
+/// `this` it was made from. Indices that are now holes are deleted. An array
+/// the method changed in place is left as it is.
+fn write_back(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    js_array: &JSArray,
+) -> Result<(), EndiumError> {
+    if let JSValueVariant::JSArray(shared) = this
+        && shared.ptr_eq(js_array)
+    {
+        return Ok(());
+    }
+
+    let length = array_like_length(interpretter, this)?.max(js_array.length());
+    for index in index_keys(this) {
+        if index < length && !js_array.has_index(index) {
+            interpretter.delete_member(this, &index.to_string())?;
+        }
+    }
+
+    for (index, element) in js_array.entries() {
+        interpretter.set_member(this, &index.to_string(), element)?;
+    }
+
+    interpretter.set_member(
+        this,
+        "length",
+        JSValueVariant::from(js_array.length() as f64),
+    )
+}
+
+fn new_array(interpretter: &Interpretter, elements: Vec<Option<JSValueVariant>>) -> JSValueVariant {
+    JSValueVariant::JSArray(JSArray::from(
+        elements,
+        Some(interpretter.array_prototype().clone()),
+    ))
+}
+
+/// The element at `index`, reading holes as undefined.
+fn element(js_array: &JSArray, index: u32) -> JSValueVariant {
+    js_array.get(index).unwrap_or(JSValueVariant::Undefined)
+}
+
+/// Resolves a start or end argument: negative values count back from the
+/// end, and the result is clamped to the array bounds.
+fn relative_index(value: &JSValueVariant, length: u32, default: u32) -> u32 {
+    if matches!(value, JSValueVariant::Undefined) {
+        return default;
+    }
+
+    let relative = operations::to_integer_or_infinity(operations::to_number(value));
+    let length = length as f64;
+
+    if relative < 0.0 {
+        (length + relative).max(0.0) as u32
+    } else {
+        relative.min(length) as u32
+    }
+}
+
+/// Fails, before anything is changed, when adding `count` elements would
+/// take the array past the largest length.
+fn check_growth(js_array: &JSArray, count: usize) -> Result<(), EndiumError> {
+    if js_array.length() as u64 + count as u64 > u32::MAX as u64 {
+        return Err(EndiumError::invalid_array_length());
+    }
+
+    Ok(())
+}
+
+/// Calls `callback(value, index, object)` the way the iteration methods do.
+fn call_back(
+    interpretter: &mut Interpretter,
+    callback: &JSFunction,
+    this_arg: &JSValueVariant,
+    object: &JSValueVariant,
+    index: u32,
+    value: JSValueVariant,
+) -> Result<JSValueVariant, EndiumError> {
+    interpretter.call_function(
+        callback,
+        this_arg.clone(),
+        vec![value, JSValueVariant::from(index as f64), object.clone()],
+    )
+}
+
+fn push(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "push")?;
+    check_growth(&js_array, arguments.len())?;
+
+    for value in arguments {
+        js_array.push(value.clone())?;
+    }
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(JSValueVariant::from(js_array.length() as f64))
+}
+
+fn pop(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "pop")?;
+    let length = js_array.length();
+
+    if length == 0 {
+        return Ok(JSValueVariant::Undefined);
+    }
+
+    let value = element(&js_array, length - 1);
+    js_array.set_length(length - 1)?;
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(value)
+}
+
+fn shift(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "shift")?;
+    let length = js_array.length();
+
+    if length == 0 {
+        return Ok(JSValueVariant::Undefined);
+    }
+
+    let value = element(&js_array, 0);
+    let entries = js_array
+        .entries()
+        .into_iter()
+        .filter(|(index, _)| *index > 0)
+        .map(|(index, element)| (index - 1, element))
+        .collect();
+    js_array.set_entries(entries, length - 1);
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(value)
+}
+
+fn unshift(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "unshift")?;
+    check_growth(&js_array, arguments.len())?;
+    let count = arguments.len() as u32;
+
+    let entries = (0..count)
+        .zip(arguments.iter().cloned())
+        .chain(
+            js_array
+                .entries()
+                .into_iter()
+                .map(|(index, element)| (index + count, element)),
+        )
+        .collect();
+    js_array.set_entries(entries, js_array.length() + count);
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(JSValueVariant::from(js_array.length() as f64))
+}
+
+fn slice(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "slice")?;
+    let length = js_array.length();
+
+    let start = relative_index(&argument(arguments, 0), length, 0);
+    let end = relative_index(&argument(arguments, 1), length, length);
+
+    let elements = (start..end.max(start))
+        .map(|index| js_array.get(index))
+        .collect();
+
+    Ok(new_array(interpretter, elements))
+}
+
+fn splice(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "splice")?;
+    let length = js_array.length();
+
+    let start = relative_index(&argument(arguments, 0), length, 0);
+    let delete_count = match arguments.len() {
+        0 => 0,
+        1 => length - start,
+        _ => operations::to_integer_or_infinity(operations::to_number(&arguments[1]))
+            .clamp(0.0, (length - start) as f64) as u32,
+    };
+
+    let items: Vec<JSValueVariant> = arguments.iter().skip(2).cloned().collect();
+    check_growth(&js_array, items.len().saturating_sub(delete_count as usize))?;
+    let end = start + delete_count;
+    let inserted = items.len() as u32;
+
+    let mut entries = vec![];
+    let mut removed = vec![];
+    for (index, element) in js_array.entries() {
+        if index < start {
+            entries.push((index, element));
+        } else if index < end {
+            removed.push((index - start, element));
+        } else {
+            entries.push((index - delete_count + inserted, element));
+        }
+    }
+
+    // The items go between the elements before `start` and those moved up.
+    let split = entries.partition_point(|(index, _)| *index < start);
+    entries.splice(split..split, (start..).zip(items));
+    js_array.set_entries(entries, length - delete_count + inserted);
+    write_back(interpretter, this, &js_array)?;
+
+    let removed_array = JSArray::new(Some(interpretter.array_prototype().clone()));
+    removed_array.set_entries(removed, delete_count);
+
+    Ok(JSValueVariant::JSArray(removed_array))
+}
+
+fn concat(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    if matches!(this, JSValueVariant::Null | JSValueVariant::Undefined) {
+        return Err(EndiumError::type_error(String::from(
+            "Array.prototype.concat called on null or undefined",
+        )));
+    }
+
+    let mut entries = vec![];
+    let mut length = 0;
+
+    // Like the arguments, a `this` that is not an array is added as it is.
+    for value in std::iter::once(this).chain(arguments) {
+        match value {
+            JSValueVariant::JSArray(other) => {
+                let offset = length as u32;
+                length += other.length() as u64;
+                if length > u32::MAX as u64 {
+                    return Err(EndiumError::invalid_array_length());
+                }
+
+                entries.extend(
+                    interpretter
+                        .array_entries(other)?
+                        .into_iter()
+                        .map(|(index, element)| (index + offset, element)),
+                );
+            }
+            _ => {
+                if length == u32::MAX as u64 {
+                    return Err(EndiumError::invalid_array_length());
+                }
+
+                entries.push((length as u32, value.clone()));
+                length += 1;
+            }
+        }
+    }
+
+    let concatenated = JSArray::new(Some(interpretter.array_prototype().clone()));
+    concatenated.set_entries(entries, length as u32);
+
+    Ok(JSValueVariant::JSArray(concatenated))
+}
+
+fn join(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "join")?;
+    let separator = match argument(arguments, 0) {
+        JSValueVariant::Undefined => String::from(","),
+        separator => interpretter.string_of(&separator)?,
+    };
+
+    Ok(JSValueVariant::from(
+        interpretter.join(&js_array, &separator)?,
+    ))
+}
+
+/// Where `indexOf` and `includes` start searching.
+fn search_start(from_index: &JSValueVariant, length: u32) -> u32 {
+    let relative = operations::to_integer_or_infinity(operations::to_number(from_index));
+
+    if relative < 0.0 {
+        (length as f64 + relative).max(0.0) as u32
+    } else {
+        relative.min(length as f64) as u32
+    }
+}
+
+fn index_of(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "indexOf")?;
+    let search = argument(arguments, 0);
+    let start = search_start(&argument(arguments, 1), js_array.length());
+
+    // Holes are skipped, so only the present elements need checking.
+    let index = js_array.indices().into_iter().find(|index| {
+        *index >= start && operations::strict_equals(&element(&js_array, *index), &search)
+    });
+
+    Ok(JSValueVariant::from(
+        index.map_or(-1.0, |index| index as f64),
+    ))
+}
+
+fn includes(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "includes")?;
+    let search = argument(arguments, 0);
+    let length = js_array.length();
+    let start = search_start(&argument(arguments, 1), length);
+
+    let present: Vec<u32> = js_array
+        .indices()
+        .into_iter()
+        .filter(|index| *index >= start)
+        .collect();
+
+    // Holes read as undefined, so any hole in range matches undefined.
+    if matches!(search, JSValueVariant::Undefined) && (present.len() as u32) < length - start {
+        return Ok(JSValueVariant::from(true));
+    }
+
+    Ok(JSValueVariant::from(present.into_iter().any(|index| {
+        operations::same_value_zero(&element(&js_array, index), &search)
+    })))
+}
+
+/// Returns the first index, in the given order, whose element satisfies the
+/// predicate. Holes are visited and read as undefined.
+fn find_position(
+    interpretter: &mut Interpretter,
+    js_array: &JSArray,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+    indices: impl Iterator<Item = u32>,
+) -> Result<Option<u32>, EndiumError> {
+    let predicate = callable(&argument(arguments, 0))?;
+    let this_arg = argument(arguments, 1);
+
+    for index in indices {
+        let value = element(js_array, index);
+        let result = call_back(interpretter, &predicate, &this_arg, this, index, value)?;
+        if operations::to_boolean(&result) {
+            return Ok(Some(index));
+        }
+    }
+
+    Ok(None)
+}
+
+fn find(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "find")?;
+    let index = find_position(
+        interpretter,
+        &js_array,
+        this,
+        arguments,
+        0..js_array.length(),
+    )?;
+
+    Ok(index.map_or(JSValueVariant::Undefined, |index| element(&js_array, index)))
+}
+
+fn find_index(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "findIndex")?;
+    let index = find_position(
+        interpretter,
+        &js_array,
+        this,
+        arguments,
+        0..js_array.length(),
+    )?;
+
+    Ok(JSValueVariant::from(
+        index.map_or(-1.0, |index| index as f64),
+    ))
+}
+
+fn find_last(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "findLast")?;
+    let index = find_position(
+        interpretter,
+        &js_array,
+        this,
+        arguments,
+        (0..js_array.length()).rev(),
+    )?;
+
+    Ok(index.map_or(JSValueVariant::Undefined, |index| element(&js_array, index)))
+}
+
+/// Runs `callback` over the present elements, skipping holes, and hands each
+/// index, element and result to `visit`. Iteration stops as soon as `visit`
+/// returns false.
+fn for_each_present(
+    interpretter: &mut Interpretter,
+    js_array: &JSArray,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+    mut visit: impl FnMut(u32, JSValueVariant, JSValueVariant) -> bool,
+) -> Result<(), EndiumError> {
+    let callback = callable(&argument(arguments, 0))?;
+    let this_arg = argument(arguments, 1);
+
+    for index in js_array.indices() {
+        // The callback may have deleted later elements.
+        if !js_array.has_index(index) {
+            continue;
+        }
+
+        let value = element(js_array, index);
+        let result = call_back(
+            interpretter,
+            &callback,
+            &this_arg,
+            this,
+            index,
+            value.clone(),
+        )?;
+        if !visit(index, value, result) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn filter(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "filter")?;
+    let mut kept = vec![];
+
+    for_each_present(
+        interpretter,
+        &js_array,
+        this,
+        arguments,
+        |_, value, result| {
+            if operations::to_boolean(&result) {
+                kept.push(value);
+            }
+            true
+        },
+    )?;
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(kept)))
+}
+
+fn map(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "map")?;
+    let mapped = JSArray::new(Some(interpretter.array_prototype().clone()));
+    mapped.set_length(js_array.length())?;
+    let mut results = vec![];
+
+    for_each_present(
+        interpretter,
+        &js_array,
+        this,
+        arguments,
+        |index, _, result| {
+            results.push((index, result));
+            true
+        },
+    )?;
+
+    for (index, result) in results {
+        mapped.set(index, result)?;
+    }
+
+    Ok(JSValueVariant::JSArray(mapped))
+}
+
+fn for_each(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "forEach")?;
+    for_each_present(interpretter, &js_array, this, arguments, |_, _, _| true)?;
+
+    Ok(JSValueVariant::Undefined)
+}
+
+fn some(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "some")?;
+    let mut found = false;
+
+    for_each_present(interpretter, &js_array, this, arguments, |_, _, result| {
+        found = operations::to_boolean(&result);
+        !found
+    })?;
+
+    Ok(JSValueVariant::from(found))
+}
+
+fn every(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "every")?;
+    let mut all = true;
+
+    for_each_present(interpretter, &js_array, this, arguments, |_, _, result| {
+        all = operations::to_boolean(&result);
+        all
+    })?;
+
+    Ok(JSValueVariant::from(all))
+}
+
+/// Folds the present elements visited in `indices` order.
+fn fold(
+    interpretter: &mut Interpretter,
+    js_array: &JSArray,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+    indices: Vec<u32>,
+) -> Result<JSValueVariant, EndiumError> {
+    let callback = callable(&argument(arguments, 0))?;
+    let mut indices = indices.into_iter();
+
+    let mut accumulator = match arguments.get(1) {
+        Some(initial_value) => initial_value.clone(),
+        None => match indices.next() {
+            Some(index) => element(js_array, index),
+            None => {
+                return Err(EndiumError::type_error(String::from(
+                    "Reduce of empty array with no initial value",
+                )));
+            }
+        },
+    };
+
+    for index in indices {
+        if !js_array.has_index(index) {
+            continue;
+        }
+
+        accumulator = interpretter.call_function(
+            &callback,
+            JSValueVariant::Undefined,
+            vec![
+                accumulator,
+                element(js_array, index),
+                JSValueVariant::from(index as f64),
+                this.clone(),
+            ],
+        )?;
+    }
+
+    Ok(accumulator)
+}
+
+fn reduce(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "reduce")?;
+    let indices = js_array.indices();
+
+    fold(interpretter, &js_array, this, arguments, indices)
+}
+
+fn reduce_right(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "reduceRight")?;
+    let mut indices = js_array.indices();
+    indices.reverse();
+
+    fold(interpretter, &js_array, this, arguments, indices)
+}
+
+fn comparator(arguments: &[JSValueVariant]) -> Result<Option<JSFunction>, EndiumError> {
+    match argument(arguments, 0) {
+        JSValueVariant::Undefined => Ok(None),
+        JSValueVariant::JSFunction(js_function) => Ok(Some(js_function)),
+        _ => Err(EndiumError::type_error(String::from(
+            "The comparison function must be either a function or undefined",
+        ))),
+    }
+}
+
+fn compare(
+    interpretter: &mut Interpretter,
+    comparator: Option<&JSFunction>,
+    left: &JSValueVariant,
+    right: &JSValueVariant,
+) -> Result<Ordering, EndiumError> {
+    let Some(comparator) = comparator else {
+        // Without a comparator elements are ordered as strings, by UTF-16 code units.
+        let left = interpretter.string_of(left)?;
+        let right = interpretter.string_of(right)?;
+        return Ok(left.encode_utf16().cmp(right.encode_utf16()));
+    };
+
+    let result = interpretter.call_function(
+        comparator,
+        JSValueVariant::Undefined,
+        vec![left.clone(), right.clone()],
+    )?;
+    let order = interpretter.number_of(&result)?;
+
+    Ok(if order < 0.0 {
+        Ordering::Less
+    } else if order > 0.0 {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    })
+}
+
+/// A stable merge sort. Undefined values always go last and are never
+/// passed to the comparator.
+fn sort_values(
+    interpretter: &mut Interpretter,
+    comparator: Option<&JSFunction>,
+    values: Vec<JSValueVariant>,
+) -> Result<Vec<JSValueVariant>, EndiumError> {
+    let (mut defined, undefined): (Vec<_>, Vec<_>) = values
+        .into_iter()
+        .partition(|value| !matches!(value, JSValueVariant::Undefined));
+
+    defined = merge_sort(interpretter, comparator, defined)?;
+    defined.extend(undefined);
+
+    Ok(defined)
+}
+
+fn merge_sort(
+    interpretter: &mut Interpretter,
+    comparator: Option<&JSFunction>,
+    mut values: Vec<JSValueVariant>,
+) -> Result<Vec<JSValueVariant>, EndiumError> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+
+    let right = values.split_off(values.len() / 2);
+    let left = merge_sort(interpretter, comparator, values)?;
+    let right = merge_sort(interpretter, comparator, right)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(left_value), Some(right_value)) = (left.peek(), right.peek()) {
+        // Taking from the left on ties keeps the sort stable.
+        if compare(interpretter, comparator, right_value, left_value)? == Ordering::Less {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+
+    merged.extend(left);
+    merged.extend(right);
+
+    Ok(merged)
+}
+
+fn sort(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let comparator = comparator(arguments)?;
+    let js_array = this_array(interpretter, this, "sort")?;
+
+    let values = js_array
+        .entries()
+        .into_iter()
+        .map(|(_, element)| element)
+        .collect();
+
+    // Holes are moved to the end.
+    let sorted = (0..)
+        .zip(sort_values(interpretter, comparator.as_ref(), values)?)
+        .collect();
+    js_array.set_entries(sorted, js_array.length());
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(this.clone())
+}
+
+fn to_sorted(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let comparator = comparator(arguments)?;
+    let js_array = this_array(interpretter, this, "toSorted")?;
+
+    let values = (0..js_array.length())
+        .map(|index| element(&js_array, index))
+        .collect();
+    let sorted = sort_values(interpretter, comparator.as_ref(), values)?;
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(sorted)))
+}
+
+fn reverse(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "reverse")?;
+    let length = js_array.length();
+
+    let entries = js_array
+        .entries()
+        .into_iter()
+        .rev()
+        .map(|(index, element)| (length - 1 - index, element))
+        .collect();
+    js_array.set_entries(entries, length);
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(this.clone())
+}
+
+fn to_reversed(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "toReversed")?;
+
+    let values = (0..js_array.length())
+        .rev()
+        .map(|index| element(&js_array, index))
+        .collect();
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
+
+/// Appends the present elements of `js_array` to `target`, flattening nested
+/// arrays up to `depth` levels. `path` holds the arrays being flattened
+/// further up, as an array nested in itself would never end.
+fn flatten_into(
+    target: &mut Vec<JSValueVariant>,
+    js_array: &JSArray,
+    depth: f64,
+    path: &mut Vec<usize>,
+) -> Result<(), EndiumError> {
+    if path.contains(&js_array.id()) || path.len() >= MAX_FLATTEN_DEPTH {
+        return Err(EndiumError::range_error(String::from(
+            "Maximum call stack size exceeded",
+        )));
+    }
+    path.push(js_array.id());
+
+    for index in js_array.indices() {
+        match element(js_array, index) {
+            JSValueVariant::JSArray(inner) if depth > 0.0 => {
+                flatten_into(target, &inner, depth - 1.0, path)?
+            }
+            value => target.push(value),
+        }
+    }
+
+    path.pop();
+    Ok(())
+}
+
+fn flat(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "flat")?;
+    let depth = match argument(arguments, 0) {
+        JSValueVariant::Undefined => 1.0,
+        depth => operations::to_integer_or_infinity(interpretter.number_of(&depth)?),
+    };
+
+    let mut values = vec![];
+    flatten_into(&mut values, &js_array, depth, &mut vec![])?;
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
+
+fn flat_map(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "flatMap")?;
+    let mut results = vec![];
+
+    for_each_present(interpretter, &js_array, this, arguments, |_, _, result| {
+        results.push(result);
+        true
+    })?;
+
+    // Only the arrays the callback returns are flattened, one level deep.
+    let mut values = vec![];
+    for result in results {
+        match result {
+            JSValueVariant::JSArray(inner) => flatten_into(&mut values, &inner, 0.0, &mut vec![])?,
+            value => values.push(value),
+        }
+    }
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
+
+fn fill(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "fill")?;
+    let length = js_array.length();
+
+    let value = argument(arguments, 0);
+    let start = relative_index(&argument(arguments, 1), length, 0);
+    let end = relative_index(&argument(arguments, 2), length, length);
+
+    for index in start..end {
+        js_array.set(index, value.clone())?;
+    }
+    write_back(interpretter, this, &js_array)?;
+
+    Ok(this.clone())
+}
+
+/// Resolves an `at`/`with` index, counting negative values from the end.
+/// Returns `None` when it falls outside the array.
+fn element_index(value: &JSValueVariant, length: u32) -> Option<u32> {
+    let relative = operations::to_integer_or_infinity(operations::to_number(value));
+    let index = if relative < 0.0 {
+        length as f64 + relative
+    } else {
+        relative
+    };
+
+    (0.0..length as f64)
+        .contains(&index)
+        .then_some(index as u32)
+}
+
+fn at(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "at")?;
+
+    Ok(
+        match element_index(&argument(arguments, 0), js_array.length()) {
+            Some(index) => element(&js_array, index),
+            None => JSValueVariant::Undefined,
+        },
+    )
+}
+
+fn with(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let js_array = this_array(interpretter, this, "with")?;
+    let index = argument(arguments, 0);
+
+    let Some(index) = element_index(&index, js_array.length()) else {
+        return Err(EndiumError::range_error(format!(
+            "Invalid index : {}",
+            operations::to_string(&index)
+        )));
+    };
+
+    let mut values: Vec<JSValueVariant> = (0..js_array.length())
+        .map(|index| element(&js_array, index))
+        .collect();
+    values[index as usize] = argument(arguments, 1);
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
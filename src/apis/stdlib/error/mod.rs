@@ -0,0 +1,254 @@
+use crate::apis::stdlib::{argument, define_method};
+use crate::apis::type_variants::js_function::{JSFunction, NativeFunction};
+use crate::apis::type_variants::js_object::{JSObject, PropertyDescriptor};
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::state::State;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+/// The built-in errors that derive from `Error`.
+pub const NATIVE_ERRORS: [&str; 4] = ["TypeError", "RangeError", "ReferenceError", "SyntaxError"];
+
+/// Fills in `Error.prototype` and the native error prototypes, and binds
+/// their constructors globally.
+pub fn install(interpretter: &mut Interpretter) {
+    let error = define_constructor(
+        interpretter,
+        "Error",
+        error,
+        interpretter.function_prototype().clone(),
+    );
+
+    if let Some(error_prototype) = interpretter.error_prototype("Error").cloned() {
+        define_method(interpretter, &error_prototype, "toString", 0, to_string);
+    }
+
+    let native_errors: [NativeFunction; 4] =
+        [type_error, range_error, reference_error, syntax_error];
+    for (name, function) in NATIVE_ERRORS.into_iter().zip(native_errors) {
+        // Native error constructors inherit from `Error` itself.
+        define_constructor(interpretter, name, function, error.object().clone());
+    }
+}
+
+fn define_constructor(
+    interpretter: &mut Interpretter,
+    name: &str,
+    function: NativeFunction,
+    prototype: JSObject,
+) -> JSFunction {
+    let constructor = JSFunction::native(name, 1, function, prototype);
+
+    if let Some(error_prototype) = interpretter.error_prototype(name) {
+        constructor.object().define_property(
+            "prototype",
+            PropertyDescriptor::data(
+                JSValueVariant::JSObject(error_prototype.clone()),
+                false,
+                false,
+                false,
+            ),
+        );
+        error_prototype.define_property(
+            "constructor",
+            PropertyDescriptor::data(
+                JSValueVariant::JSFunction(constructor.clone()),
+                true,
+                false,
+                true,
+            ),
+        );
+        error_prototype.define_property(
+            "name",
+            PropertyDescriptor::data(JSValueVariant::from(name), true, false, true),
+        );
+        error_prototype.define_property(
+            "message",
+            PropertyDescriptor::data(JSValueVariant::from(""), true, false, true),
+        );
+    }
+
+    interpretter.scope().borrow_mut().insert_state(
+        name.to_string(),
+        State::new(JSValueVariant::JSFunction(constructor.clone()), true),
+    );
+
+    constructor
+}
+
+/// Creates an error object of the built-in kind `name`, recording the
+/// current call stack.
+pub fn create(interpretter: &Interpretter, name: &str, message: Option<String>) -> JSObject {
+    let error = JSObject::from(interpretter.error_prototype(name).cloned());
+
+    let header = match &message {
+        Some(message) if !message.is_empty() => format!("{}: {}", name, message),
+        _ => name.to_string(),
+    };
+
+    if let Some(message) = message {
+        error.define_property(
+            "message",
+            PropertyDescriptor::data(JSValueVariant::from(message), true, false, true),
+        );
+    }
+
+    error.define_property(
+        "stack",
+        PropertyDescriptor::data(
+            JSValueVariant::from(format!("{}\n{}", header, interpretter.stack_trace())),
+            true,
+            false,
+            true,
+        ),
+    );
+
+    error
+}
+
+/// How an uncaught exception is described: an error by its name and
+/// message, anything else as the value itself.
+pub fn describe(value: &JSValueVariant) -> String {
+    let JSValueVariant::JSObject(js_object) = value else {
+        return operations::to_string(value);
+    };
+    if stack(value).is_none() {
+        return operations::to_string(value);
+    }
+
+    let property = |key: &str| {
+        js_object
+            .get_property(key)
+            .map(|value| operations::to_string(&value))
+    };
+    let name = property("name").unwrap_or_else(|| String::from("Error"));
+    let message = property("message").unwrap_or_default();
+
+    if name.is_empty() {
+        message
+    } else if message.is_empty() {
+        name
+    } else {
+        format!("{}: {}", name, message)
+    }
+}
+
+/// The stack an error recorded when it was created.
+pub fn stack(value: &JSValueVariant) -> Option<String> {
+    if let JSValueVariant::JSObject(js_object) = value
+        && let Some(JSValueVariant::JSString(stack)) = js_object.get_property("stack")
+    {
+        return Some(stack.str_value().to_string());
+    }
+
+    None
+}
+
+/// `Error(message, options)` and the native errors create a new error
+/// whether or not they are called with `new`. An `options.cause` is kept.
+fn construct(
+    interpretter: &mut Interpretter,
+    name: &str,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let message = match argument(arguments, 0) {
+        JSValueVariant::Undefined => None,
+        message => Some(operations::to_string(&message)),
+    };
+
+    let error = create(interpretter, name, message);
+
+    let options = argument(arguments, 1);
+    if let JSValueVariant::JSObject(js_object) = &options
+        && js_object.has_property("cause")
+    {
+        error.define_property(
+            "cause",
+            PropertyDescriptor::data(
+                interpretter.handle_member(&options, "cause")?,
+                true,
+                false,
+                true,
+            ),
+        );
+    }
+
+    Ok(JSValueVariant::JSObject(error))
+}
+
+fn error(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    construct(interpretter, "Error", arguments)
+}
+
+fn type_error(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    construct(interpretter, "TypeError", arguments)
+}
+
+fn range_error(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    construct(interpretter, "RangeError", arguments)
+}
+
+fn reference_error(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    construct(interpretter, "ReferenceError", arguments)
+}
+
+fn syntax_error(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    construct(interpretter, "SyntaxError", arguments)
+}
+
+/// `Error.prototype.toString()` joins the name and message with ": ",
+/// leaving out whichever is empty.
+fn to_string(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    if !matches!(
+        this,
+        JSValueVariant::JSObject(_) | JSValueVariant::JSArray(_) | JSValueVariant::JSFunction(_)
+    ) {
+        return Err(EndiumError::type_error(String::from(
+            "Error.prototype.toString requires that 'this' be an Object",
+        )));
+    }
+
+    let name = match interpretter.handle_member(this, "name")? {
+        JSValueVariant::Undefined => String::from("Error"),
+        name => operations::to_string(&name),
+    };
+    let message = match interpretter.handle_member(this, "message")? {
+        JSValueVariant::Undefined => String::new(),
+        message => operations::to_string(&message),
+    };
+
+    let string = if name.is_empty() {
+        message
+    } else if message.is_empty() {
+        name
+    } else {
+        format!("{}: {}", name, message)
+    };
+
+    Ok(JSValueVariant::from(string))
+}
@@ -0,0 +1,111 @@
+use crate::apis::stdlib::{argument, callable, define_method};
+use crate::apis::type_variants::js_function::JSFunction;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+/// Fills in `Function.prototype`.
+pub fn install(interpretter: &mut Interpretter) {
+    let function_prototype = interpretter.function_prototype().clone();
+
+    define_method(interpretter, &function_prototype, "call", 1, call);
+    define_method(interpretter, &function_prototype, "apply", 2, apply);
+    define_method(interpretter, &function_prototype, "bind", 1, bind);
+}
+
+/// `f.call(thisArg, ...args)` calls `f` with the given `this`.
+fn call(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let function = callable(this)?;
+
+    interpretter.call_function(
+        &function,
+        argument(arguments, 0),
+        arguments.get(1..).unwrap_or_default().to_vec(),
+    )
+}
+
+/// `f.apply(thisArg, args)` calls `f` with the given `this` and the elements
+/// of an array-like `args`.
+fn apply(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let function = callable(this)?;
+    let arguments_list = list_from_array_like(interpretter, &argument(arguments, 1))?;
+
+    interpretter.call_function(&function, argument(arguments, 0), arguments_list)
+}
+
+/// The elements of an array-like object, as read through its `length` and
+/// indices. Null and undefined stand for no elements.
+fn list_from_array_like(
+    interpretter: &mut Interpretter,
+    array_like: &JSValueVariant,
+) -> Result<Vec<JSValueVariant>, EndiumError> {
+    match array_like {
+        JSValueVariant::Null | JSValueVariant::Undefined => return Ok(vec![]),
+        JSValueVariant::JSArray(_)
+        | JSValueVariant::JSObject(_)
+        | JSValueVariant::JSFunction(_) => {}
+        _ => {
+            return Err(EndiumError::type_error(String::from(
+                "CreateListFromArrayLike called on non-object",
+            )));
+        }
+    }
+
+    let length = operations::to_integer_or_infinity(operations::to_number(
+        &interpretter.handle_member(array_like, "length")?,
+    ))
+    .clamp(0.0, u32::MAX as f64) as u32;
+
+    (0..length)
+        .map(|index| interpretter.handle_member(array_like, &index.to_string()))
+        .collect()
+}
+
+/// `f.bind(thisArg, ...args)` makes a function that calls `f` with the given
+/// `this` and `args` before its own arguments. Its length is what remains of
+/// the length of `f`.
+fn bind(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let target = callable(this)?;
+    let bound_arguments = arguments.get(1..).unwrap_or_default().to_vec();
+
+    let length = match interpretter.handle_member(this, "length")? {
+        JSValueVariant::JSNumber(length) => {
+            let length = operations::to_integer_or_infinity(length.number_value());
+            (length - bound_arguments.len() as f64).max(0.0) as usize
+        }
+        _ => 0,
+    };
+
+    let name = match interpretter.handle_member(this, "name")? {
+        JSValueVariant::JSString(name) => name.str_value().to_string(),
+        _ => String::new(),
+    };
+
+    // A bound function inherits from whatever its target inherits from.
+    let prototype = target
+        .object()
+        .prototype()
+        .unwrap_or_else(|| interpretter.function_prototype().clone());
+
+    Ok(JSValueVariant::JSFunction(JSFunction::bound(
+        target,
+        argument(arguments, 0),
+        bound_arguments,
+        &name,
+        length,
+        prototype,
+    )))
+}
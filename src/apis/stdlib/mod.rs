@@ -0,0 +1,52 @@
+pub mod array;
+pub mod error;
+pub mod function;
+pub mod object;
+pub mod string;
+
+use crate::apis::type_variants::js_function::{JSFunction, NativeFunction};
+use crate::apis::type_variants::js_object::{JSObject, PropertyDescriptor};
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+/// Defines a native method on `object`. Like every built-in method it is
+/// writable and configurable, but not enumerable.
+pub fn define_method(
+    interpretter: &Interpretter,
+    object: &JSObject,
+    name: &str,
+    length: usize,
+    function: NativeFunction,
+) {
+    let method = JSFunction::native(
+        name,
+        length,
+        function,
+        interpretter.function_prototype().clone(),
+    );
+
+    object.define_property(
+        name,
+        PropertyDescriptor::data(JSValueVariant::JSFunction(method), true, false, true),
+    );
+}
+
+/// The argument at `index`, or undefined when the caller passed fewer.
+pub fn argument(arguments: &[JSValueVariant], index: usize) -> JSValueVariant {
+    arguments
+        .get(index)
+        .cloned()
+        .unwrap_or(JSValueVariant::Undefined)
+}
+
+pub fn callable(value: &JSValueVariant) -> Result<JSFunction, EndiumError> {
+    match value {
+        JSValueVariant::JSFunction(js_function) => Ok(js_function.clone()),
+        _ => Err(EndiumError::type_error(format!(
+            "{} is not a function",
+            operations::to_string(value)
+        ))),
+    }
+}
@@ -0,0 +1,474 @@
+use crate::apis::stdlib::{argument, define_method};
+use crate::apis::type_variants::js_array::JSArray;
+use crate::apis::type_variants::js_function::JSFunction;
+use crate::apis::type_variants::js_object::{
+    JSObject, PartialDescriptor, PropertyDescriptor, PropertyKind, array_index,
+};
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::state::State;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+/// Binds the global `Object` constructor, `Object.is` and the property
+/// descriptor functions.
+pub fn install(interpretter: &mut Interpretter) {
+    let object_prototype = interpretter.object_prototype().clone();
+
+    let object = JSFunction::native(
+        "Object",
+        1,
+        object,
+        interpretter.function_prototype().clone(),
+    );
+
+    define_method(interpretter, object.object(), "is", 2, is);
+    define_method(
+        interpretter,
+        object.object(),
+        "defineProperty",
+        3,
+        define_property,
+    );
+    define_method(
+        interpretter,
+        object.object(),
+        "defineProperties",
+        2,
+        define_properties,
+    );
+    define_method(
+        interpretter,
+        object.object(),
+        "getOwnPropertyDescriptor",
+        2,
+        get_own_property_descriptor,
+    );
+    define_method(
+        interpretter,
+        object.object(),
+        "getOwnPropertyDescriptors",
+        1,
+        get_own_property_descriptors,
+    );
+
+    object.object().define_property(
+        "prototype",
+        PropertyDescriptor::data(
+            JSValueVariant::JSObject(object_prototype.clone()),
+            false,
+            false,
+            false,
+        ),
+    );
+    object_prototype.define_property(
+        "constructor",
+        PropertyDescriptor::data(
+            JSValueVariant::JSFunction(object.clone()),
+            true,
+            false,
+            true,
+        ),
+    );
+
+    interpretter.scope().borrow_mut().insert_state(
+        String::from("Object"),
+        State::new(JSValueVariant::JSFunction(object), true),
+    );
+}
+
+/// `Object(value)` returns objects as they are, and a new empty object for
+/// null and undefined. Primitives are returned unchanged, as there are no
+/// wrapper objects.
+fn object(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    match argument(arguments, 0) {
+        JSValueVariant::Null | JSValueVariant::Undefined => Ok(JSValueVariant::JSObject(
+            JSObject::from(Some(interpretter.object_prototype().clone())),
+        )),
+        value => Ok(value),
+    }
+}
+
+/// `Object.is(a, b)` compares with SameValue.
+fn is(
+    _interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    Ok(JSValueVariant::from(operations::same_value(
+        &argument(arguments, 0),
+        &argument(arguments, 1),
+    )))
+}
+
+fn define_property(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let target = argument(arguments, 0);
+    if Interpretter::object_of(&target).is_none() {
+        return Err(EndiumError::type_error(String::from(
+            "Object.defineProperty called on non-object",
+        )));
+    }
+
+    let property_key = operations::to_string(&argument(arguments, 1));
+    let descriptor = to_property_descriptor(interpretter, &argument(arguments, 2))?;
+
+    define_own_property(&target, &property_key, descriptor)?;
+
+    Ok(target)
+}
+
+/// `Object.defineProperties(target, properties)` reads every descriptor in
+/// `properties` before defining any of them.
+fn define_properties(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let target = argument(arguments, 0);
+    if Interpretter::object_of(&target).is_none() {
+        return Err(EndiumError::type_error(String::from(
+            "Object.defineProperties called on non-object",
+        )));
+    }
+
+    let properties = argument(arguments, 1);
+    if matches!(properties, JSValueVariant::Null | JSValueVariant::Undefined) {
+        return Err(to_object_error());
+    }
+
+    let mut descriptors: Vec<(String, PartialDescriptor)> = vec![];
+    for property_key in own_keys(&properties) {
+        let is_enumerable = own_property(&properties, &property_key)
+            .is_some_and(|property| property.is_enumerable());
+
+        if is_enumerable {
+            let descriptor = interpretter.handle_member(&properties, &property_key)?;
+            descriptors.push((
+                property_key,
+                to_property_descriptor(interpretter, &descriptor)?,
+            ));
+        }
+    }
+
+    for (property_key, descriptor) in descriptors {
+        define_own_property(&target, &property_key, descriptor)?;
+    }
+
+    Ok(target)
+}
+
+fn get_own_property_descriptor(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let target = argument(arguments, 0);
+    if matches!(target, JSValueVariant::Null | JSValueVariant::Undefined) {
+        return Err(to_object_error());
+    }
+
+    let property_key = operations::to_string(&argument(arguments, 1));
+
+    Ok(match own_property(&target, &property_key) {
+        Some(property) => from_property_descriptor(interpretter, &property),
+        None => JSValueVariant::Undefined,
+    })
+}
+
+fn get_own_property_descriptors(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let target = argument(arguments, 0);
+    if matches!(target, JSValueVariant::Null | JSValueVariant::Undefined) {
+        return Err(to_object_error());
+    }
+
+    let descriptors = JSObject::from(Some(interpretter.object_prototype().clone()));
+
+    for property_key in own_keys(&target) {
+        if let Some(property) = own_property(&target, &property_key) {
+            let descriptor = from_property_descriptor(interpretter, &property);
+            descriptors.define_property(&property_key, PropertyDescriptor::from(descriptor));
+        }
+    }
+
+    Ok(JSValueVariant::JSObject(descriptors))
+}
+
+fn to_object_error() -> EndiumError {
+    EndiumError::type_error(String::from("Cannot convert undefined or null to object"))
+}
+
+/// Reads the fields of a descriptor object, including inherited ones.
+fn to_property_descriptor(
+    interpretter: &mut Interpretter,
+    descriptor: &JSValueVariant,
+) -> Result<PartialDescriptor, EndiumError> {
+    let Some(object) = Interpretter::object_of(descriptor).cloned() else {
+        return Err(EndiumError::type_error(format!(
+            "Property description must be an object: {}",
+            operations::to_string(descriptor)
+        )));
+    };
+
+    let mut field = |name: &str| -> Result<Option<JSValueVariant>, EndiumError> {
+        if object.has_property(name) {
+            Ok(Some(interpretter.handle_member(descriptor, name)?))
+        } else {
+            Ok(None)
+        }
+    };
+
+    let enumerable = field("enumerable")?.map(|value| operations::to_boolean(&value));
+    let configurable = field("configurable")?.map(|value| operations::to_boolean(&value));
+    let value = field("value")?;
+    let writable = field("writable")?.map(|value| operations::to_boolean(&value));
+    let get = field("get")?;
+    let set = field("set")?;
+
+    for (kind, function) in [("Getter", &get), ("Setter", &set)] {
+        if let Some(function) = function
+            && !matches!(
+                function,
+                JSValueVariant::JSFunction(_) | JSValueVariant::Undefined
+            )
+        {
+            return Err(EndiumError::type_error(format!(
+                "{} must be a function: {}",
+                kind,
+                operations::to_string(function)
+            )));
+        }
+    }
+
+    let descriptor = PartialDescriptor {
+        value,
+        writable,
+        get,
+        set,
+        enumerable,
+        configurable,
+    };
+
+    if descriptor.is_accessor() && descriptor.is_data() {
+        return Err(EndiumError::type_error(String::from(
+            "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute",
+        )));
+    }
+
+    Ok(descriptor)
+}
+
+/// Creates the object `Object.getOwnPropertyDescriptor` returns for a
+/// property.
+fn from_property_descriptor(
+    interpretter: &Interpretter,
+    property: &PropertyDescriptor,
+) -> JSValueVariant {
+    let descriptor = JSObject::from(Some(interpretter.object_prototype().clone()));
+    let field = |name: &str, value: JSValueVariant| {
+        descriptor.define_property(name, PropertyDescriptor::from(value));
+    };
+
+    match property.kind() {
+        PropertyKind::Data { value, writable } => {
+            field("value", value.clone());
+            field("writable", JSValueVariant::from(*writable));
+        }
+        PropertyKind::Accessor { get, set } => {
+            field("get", get.clone().unwrap_or(JSValueVariant::Undefined));
+            field("set", set.clone().unwrap_or(JSValueVariant::Undefined));
+        }
+    }
+    field("enumerable", JSValueVariant::from(property.is_enumerable()));
+    field(
+        "configurable",
+        JSValueVariant::from(property.is_configurable()),
+    );
+
+    JSValueVariant::JSObject(descriptor)
+}
+
+/// Plain array elements, array lengths and string characters are kept
+/// outside of any object, so their descriptors are made up here.
+fn own_property(target: &JSValueVariant, property_key: &str) -> Option<PropertyDescriptor> {
+    match target {
+        JSValueVariant::JSArray(js_array) => {
+            if property_key == "length" {
+                let length = JSValueVariant::from(js_array.length() as f64);
+                let writable = js_array.is_length_writable();
+                return Some(PropertyDescriptor::data(length, writable, false, false));
+            }
+
+            match array_index(property_key).and_then(|index| js_array.get(index)) {
+                Some(element) => Some(PropertyDescriptor::from(element)),
+                None => js_array.object().get_own_property(property_key),
+            }
+        }
+        JSValueVariant::JSString(js_string) => {
+            if property_key == "length" {
+                let length = js_string.code_units().len();
+                let length = JSValueVariant::from(length as f64);
+                return Some(PropertyDescriptor::data(length, false, false, false));
+            }
+
+            let code_unit =
+                array_index(property_key).and_then(|index| js_string.code_unit(index as usize))?;
+            let character = JSValueVariant::from(String::from_utf16_lossy(&[code_unit]));
+
+            Some(PropertyDescriptor::data(character, false, true, false))
+        }
+        _ => Interpretter::object_of(target)?.get_own_property(property_key),
+    }
+}
+
+fn own_keys(target: &JSValueVariant) -> Vec<String> {
+    match target {
+        JSValueVariant::JSArray(js_array) => js_array
+            .own_indices()
+            .into_iter()
+            .map(|index| index.to_string())
+            .chain([String::from("length")])
+            .chain(
+                js_array
+                    .object()
+                    .keys()
+                    .into_iter()
+                    .filter(|key| array_index(key).is_none()),
+            )
+            .collect(),
+        JSValueVariant::JSString(js_string) => (0..js_string.code_units().len())
+            .map(|index| index.to_string())
+            .chain([String::from("length")])
+            .collect(),
+        _ => match Interpretter::object_of(target) {
+            Some(object) => object.keys(),
+            None => vec![],
+        },
+    }
+}
+
+/// Applies a descriptor to a property of `target`, which is an object. An
+/// array element is kept as plain data while it stays writable, enumerable
+/// and configurable, and is held by the array's object once it does not.
+fn define_own_property(
+    target: &JSValueVariant,
+    property_key: &str,
+    descriptor: PartialDescriptor,
+) -> Result<(), EndiumError> {
+    if let JSValueVariant::JSArray(js_array) = target {
+        if property_key == "length" {
+            return define_length(js_array, descriptor);
+        }
+
+        if let Some(index) = array_index(property_key) {
+            return define_index(js_array, index, property_key, descriptor);
+        }
+    }
+
+    let defined = match Interpretter::object_of(target) {
+        Some(object) => object.define_own_property(property_key, descriptor),
+        None => true,
+    };
+
+    if !defined {
+        return Err(EndiumError::type_error(format!(
+            "Cannot redefine property: {}",
+            property_key
+        )));
+    }
+
+    Ok(())
+}
+
+/// The length stays a non-enumerable and non-configurable data property,
+/// which can be made read-only but not writable again.
+fn define_length(js_array: &JSArray, descriptor: PartialDescriptor) -> Result<(), EndiumError> {
+    if descriptor.is_accessor()
+        || descriptor.enumerable == Some(true)
+        || descriptor.configurable == Some(true)
+        || (descriptor.writable == Some(true) && !js_array.is_length_writable())
+    {
+        return Err(EndiumError::type_error(String::from(
+            "Cannot redefine property: length",
+        )));
+    }
+
+    if let Some(value) = &descriptor.value {
+        let number = operations::to_number(value);
+        let length = operations::to_uint32(number);
+
+        if length as f64 != number {
+            return Err(EndiumError::invalid_array_length());
+        }
+        if length != js_array.length() && !js_array.is_length_writable() {
+            return Err(EndiumError::type_error(String::from(
+                "Cannot redefine property: length",
+            )));
+        }
+
+        js_array.set_length(length)?;
+    }
+
+    if descriptor.writable == Some(false) {
+        js_array.freeze_length();
+    }
+
+    Ok(())
+}
+
+/// Attributes left out keep those of an existing element, and are false for
+/// a new one, which then has to be held by the array's object.
+fn define_index(
+    js_array: &JSArray,
+    index: u32,
+    property_key: &str,
+    descriptor: PartialDescriptor,
+) -> Result<(), EndiumError> {
+    let existing = js_array.get(index);
+    let held = js_array.object().get_own_property(property_key).is_some();
+    let default = existing.is_some();
+
+    if !held
+        && !descriptor.is_accessor()
+        && descriptor.writable.unwrap_or(default)
+        && descriptor.enumerable.unwrap_or(default)
+        && descriptor.configurable.unwrap_or(default)
+    {
+        let value = descriptor.value.or(existing);
+        return js_array.set(index, value.unwrap_or(JSValueVariant::Undefined));
+    }
+
+    if index >= js_array.length() && !js_array.is_length_writable() {
+        return Err(EndiumError::type_error(format!(
+            "Cannot define property {}, object is not extensible",
+            property_key
+        )));
+    }
+
+    js_array.detach(index);
+    let object = js_array.object();
+
+    if !object.define_own_property(property_key, descriptor) {
+        return Err(EndiumError::type_error(format!(
+            "Cannot redefine property: {}",
+            property_key
+        )));
+    }
+
+    if index >= js_array.length() {
+        js_array.set_length(index + 1)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+pub mod normalize;
+pub mod prototype;
+pub mod unicode_data;
+
+use crate::apis::stdlib::argument;
+use crate::apis::type_variants::js_function::JSFunction;
+use crate::apis::type_variants::js_object::PropertyDescriptor;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::state::State;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+
+/// Fills in `String.prototype` and binds the global `String` function.
+pub fn install(interpretter: &mut Interpretter) {
+    let string_prototype = interpretter.string_prototype().clone();
+    prototype::define_methods(interpretter, &string_prototype);
+
+    let string = JSFunction::native(
+        "String",
+        1,
+        string,
+        interpretter.function_prototype().clone(),
+    );
+
+    string.object().define_property(
+        "prototype",
+        PropertyDescriptor::data(
+            JSValueVariant::JSObject(string_prototype.clone()),
+            false,
+            false,
+            false,
+        ),
+    );
+    string_prototype.define_property(
+        "constructor",
+        PropertyDescriptor::data(
+            JSValueVariant::JSFunction(string.clone()),
+            true,
+            false,
+            true,
+        ),
+    );
+
+    interpretter.scope().borrow_mut().insert_state(
+        String::from("String"),
+        State::new(JSValueVariant::JSFunction(string), true),
+    );
+}
+
+/// `String(value)` converts its argument, and `String()` is the empty string.
+fn string(
+    interpretter: &mut Interpretter,
+    _this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    if arguments.is_empty() {
+        return Ok(JSValueVariant::from(""));
+    }
+
+    Ok(JSValueVariant::from(
+        interpretter.string_of(&argument(arguments, 0))?,
+    ))
+}
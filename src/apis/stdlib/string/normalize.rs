@@ -0,0 +1,167 @@
+use crate::apis::stdlib::string::unicode_data::{COMBINING_CLASSES, COMPOSITIONS, DECOMPOSITIONS};
+
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
+impl NormalizationForm {
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "NFC" => Some(Self::Nfc),
+            "NFD" => Some(Self::Nfd),
+            "NFKC" => Some(Self::Nfkc),
+            "NFKD" => Some(Self::Nfkd),
+            _ => None,
+        }
+    }
+
+    fn is_compatibility(&self) -> bool {
+        matches!(self, Self::Nfkc | Self::Nfkd)
+    }
+
+    fn is_composed(&self) -> bool {
+        matches!(self, Self::Nfc | Self::Nfkc)
+    }
+}
+
+pub fn normalize(string: &str, form: NormalizationForm) -> String {
+    let mut code_points = vec![];
+    for ch in string.chars() {
+        decompose(ch as u32, form.is_compatibility(), &mut code_points);
+    }
+
+    reorder(&mut code_points);
+
+    if form.is_composed() {
+        code_points = compose(&code_points);
+    }
+
+    code_points.into_iter().filter_map(char::from_u32).collect()
+}
+
+fn combining_class(code_point: u32) -> u8 {
+    COMBINING_CLASSES
+        .binary_search_by(|(first, last, _)| {
+            if *last < code_point {
+                std::cmp::Ordering::Less
+            } else if *first > code_point {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .map_or(0, |index| COMBINING_CLASSES[index].2)
+}
+
+/// Appends the full decomposition of `code_point` to `output`.
+fn decompose(code_point: u32, compatibility: bool, output: &mut Vec<u32>) {
+    if (HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT).contains(&code_point) {
+        let index = code_point - HANGUL_S_BASE;
+        output.push(HANGUL_L_BASE + index / HANGUL_N_COUNT);
+        output.push(HANGUL_V_BASE + (index % HANGUL_N_COUNT) / HANGUL_T_COUNT);
+        if !index.is_multiple_of(HANGUL_T_COUNT) {
+            output.push(HANGUL_T_BASE + index % HANGUL_T_COUNT);
+        }
+        return;
+    }
+
+    match DECOMPOSITIONS.binary_search_by_key(&code_point, |(code_point, _, _)| *code_point) {
+        Ok(index) if compatibility || !DECOMPOSITIONS[index].1 => {
+            for part in DECOMPOSITIONS[index].2 {
+                decompose(*part, compatibility, output);
+            }
+        }
+        _ => output.push(code_point),
+    }
+}
+
+/// Puts every run of combining marks into canonical order. The sort is
+/// stable, so marks of equal class keep their relative order.
+fn reorder(code_points: &mut [u32]) {
+    let mut start = 0;
+
+    while start < code_points.len() {
+        if combining_class(code_points[start]) == 0 {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        while end < code_points.len() && combining_class(code_points[end]) != 0 {
+            end += 1;
+        }
+
+        code_points[start..end].sort_by_key(|code_point| combining_class(*code_point));
+        start = end;
+    }
+}
+
+fn compose_pair(first: u32, second: u32) -> Option<u32> {
+    let l_range = HANGUL_L_BASE..HANGUL_L_BASE + HANGUL_L_COUNT;
+    let v_range = HANGUL_V_BASE..HANGUL_V_BASE + HANGUL_V_COUNT;
+    let s_range = HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT;
+
+    if l_range.contains(&first) && v_range.contains(&second) {
+        let l_index = first - HANGUL_L_BASE;
+        let v_index = second - HANGUL_V_BASE;
+        return Some(HANGUL_S_BASE + (l_index * HANGUL_V_COUNT + v_index) * HANGUL_T_COUNT);
+    }
+
+    if s_range.contains(&first)
+        && (first - HANGUL_S_BASE).is_multiple_of(HANGUL_T_COUNT)
+        && (HANGUL_T_BASE + 1..HANGUL_T_BASE + HANGUL_T_COUNT).contains(&second)
+    {
+        return Some(first + second - HANGUL_T_BASE);
+    }
+
+    COMPOSITIONS
+        .binary_search_by_key(&(first, second), |(first, second, _)| (*first, *second))
+        .ok()
+        .map(|index| COMPOSITIONS[index].2)
+}
+
+/// The canonical composition algorithm: each mark is combined with the last
+/// starter unless another mark of the same or higher class sits between them.
+fn compose(code_points: &[u32]) -> Vec<u32> {
+    let mut output: Vec<u32> = Vec::with_capacity(code_points.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class: Option<u8> = None;
+
+    for &code_point in code_points {
+        let class = combining_class(code_point);
+
+        if let Some(starter_index) = starter {
+            let blocked = last_class.is_some_and(|last_class| last_class >= class);
+
+            if !blocked && let Some(composite) = compose_pair(output[starter_index], code_point) {
+                output[starter_index] = composite;
+                continue;
+            }
+        }
+
+        if class == 0 {
+            starter = Some(output.len());
+            last_class = None;
+        } else {
+            last_class = Some(class);
+        }
+
+        output.push(code_point);
+    }
+
+    output
+}
@@ -0,0 +1,669 @@
+use crate::apis::stdlib::string::normalize::{NormalizationForm, normalize};
+use crate::apis::stdlib::{argument, define_method};
+use crate::apis::type_variants::js_object::JSObject;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+use std::rc::Rc;
+
+pub fn define_methods(interpretter: &Interpretter, prototype: &JSObject) {
+    define_method(interpretter, prototype, "charAt", 1, char_at);
+    define_method(interpretter, prototype, "charCodeAt", 1, char_code_at);
+    define_method(interpretter, prototype, "codePointAt", 1, code_point_at);
+    define_method(interpretter, prototype, "slice", 2, slice);
+    define_method(interpretter, prototype, "substring", 2, substring);
+    define_method(interpretter, prototype, "indexOf", 1, index_of);
+    define_method(interpretter, prototype, "lastIndexOf", 1, last_index_of);
+    define_method(interpretter, prototype, "includes", 1, includes);
+    define_method(interpretter, prototype, "startsWith", 1, starts_with);
+    define_method(interpretter, prototype, "endsWith", 1, ends_with);
+    define_method(interpretter, prototype, "split", 2, split);
+    define_method(interpretter, prototype, "trim", 0, trim);
+    define_method(interpretter, prototype, "trimStart", 0, trim_start);
+    define_method(interpretter, prototype, "trimEnd", 0, trim_end);
+    define_method(interpretter, prototype, "padStart", 2, pad_start);
+    define_method(interpretter, prototype, "padEnd", 2, pad_end);
+    define_method(interpretter, prototype, "repeat", 1, repeat);
+    define_method(interpretter, prototype, "replace", 2, replace);
+    define_method(interpretter, prototype, "replaceAll", 2, replace_all);
+    define_method(interpretter, prototype, "toUpperCase", 0, to_upper_case);
+    define_method(interpretter, prototype, "toLowerCase", 0, to_lower_case);
+    define_method(interpretter, prototype, "at", 1, at);
+    define_method(interpretter, prototype, "normalize", 0, normalize_string);
+}
+
+/// Converts `this` to a string, as every `String.prototype` method does.
+fn this_string(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    method: &str,
+) -> Result<String, EndiumError> {
+    match this {
+        JSValueVariant::Null | JSValueVariant::Undefined => Err(EndiumError::type_error(format!(
+            "String.prototype.{} called on null or undefined",
+            method
+        ))),
+        _ => interpretter.string_of(this),
+    }
+}
+
+/// The code units of `this` converted to a string. A string shares the ones
+/// it already holds.
+fn this_code_units(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    method: &str,
+) -> Result<Rc<[u16]>, EndiumError> {
+    match this {
+        JSValueVariant::JSString(js_string) => Ok(Rc::clone(js_string.code_units())),
+        _ => Ok(this_string(interpretter, this, method)?
+            .encode_utf16()
+            .collect()),
+    }
+}
+
+fn string_argument(
+    interpretter: &mut Interpretter,
+    arguments: &[JSValueVariant],
+    index: usize,
+) -> Result<Vec<u16>, EndiumError> {
+    Ok(interpretter
+        .string_of(&argument(arguments, index))?
+        .encode_utf16()
+        .collect())
+}
+
+fn from_code_units(code_units: &[u16]) -> JSValueVariant {
+    JSValueVariant::from(String::from_utf16_lossy(code_units))
+}
+
+fn integer_argument(
+    interpretter: &mut Interpretter,
+    arguments: &[JSValueVariant],
+    index: usize,
+) -> Result<f64, EndiumError> {
+    let number = interpretter.number_of(&argument(arguments, index))?;
+
+    Ok(operations::to_integer_or_infinity(number))
+}
+
+/// Clamps a position argument to `0..=length`.
+fn clamped_position(
+    interpretter: &mut Interpretter,
+    arguments: &[JSValueVariant],
+    index: usize,
+    length: usize,
+) -> Result<usize, EndiumError> {
+    Ok(integer_argument(interpretter, arguments, index)?.clamp(0.0, length as f64) as usize)
+}
+
+/// Resolves a `slice` bound: negative values count back from the end.
+fn relative_position(
+    interpretter: &mut Interpretter,
+    value: &JSValueVariant,
+    length: usize,
+    default: usize,
+) -> Result<usize, EndiumError> {
+    if matches!(value, JSValueVariant::Undefined) {
+        return Ok(default);
+    }
+
+    let relative = operations::to_integer_or_infinity(interpretter.number_of(value)?);
+    let length = length as f64;
+
+    Ok(if relative < 0.0 {
+        (length + relative).max(0.0) as usize
+    } else {
+        relative.min(length) as usize
+    })
+}
+
+/// The first index at or after `from` where `search` occurs.
+fn find(code_units: &[u16], search: &[u16], from: usize) -> Option<usize> {
+    if search.len() > code_units.len() {
+        return None;
+    }
+
+    (from..=code_units.len() - search.len()).find(|index| code_units[*index..].starts_with(search))
+}
+
+fn char_at(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "charAt")?;
+    let position = integer_argument(interpretter, arguments, 0)?;
+
+    if position < 0.0 || position >= code_units.len() as f64 {
+        return Ok(JSValueVariant::from(""));
+    }
+
+    Ok(from_code_units(&[code_units[position as usize]]))
+}
+
+fn char_code_at(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "charCodeAt")?;
+    let position = integer_argument(interpretter, arguments, 0)?;
+
+    if position < 0.0 || position >= code_units.len() as f64 {
+        return Ok(JSValueVariant::from(f64::NAN));
+    }
+
+    Ok(JSValueVariant::from(code_units[position as usize] as f64))
+}
+
+fn code_point_at(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "codePointAt")?;
+    let position = integer_argument(interpretter, arguments, 0)?;
+
+    if position < 0.0 || position >= code_units.len() as f64 {
+        return Ok(JSValueVariant::Undefined);
+    }
+
+    let position = position as usize;
+    let first = code_units[position];
+
+    // A surrogate pair is read as the single code point it encodes.
+    if let Some(&second) = code_units.get(position + 1)
+        && (0xD800..0xDC00).contains(&first)
+        && (0xDC00..0xE000).contains(&second)
+    {
+        let code_point = 0x10000 + ((first as u32 - 0xD800) << 10) + (second as u32 - 0xDC00);
+        return Ok(JSValueVariant::from(code_point as f64));
+    }
+
+    Ok(JSValueVariant::from(first as f64))
+}
+
+fn slice(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "slice")?;
+    let length = code_units.len();
+
+    let start = relative_position(interpretter, &argument(arguments, 0), length, 0)?;
+    let end = relative_position(interpretter, &argument(arguments, 1), length, length)?;
+
+    Ok(from_code_units(&code_units[start..end.max(start)]))
+}
+
+fn substring(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "substring")?;
+    let length = code_units.len();
+
+    let start = clamped_position(interpretter, arguments, 0, length)?;
+    let end = match argument(arguments, 1) {
+        JSValueVariant::Undefined => length,
+        _ => clamped_position(interpretter, arguments, 1, length)?,
+    };
+
+    // The bounds may be given in either order.
+    Ok(from_code_units(&code_units[start.min(end)..start.max(end)]))
+}
+
+fn index_of(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "indexOf")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+    let start = clamped_position(interpretter, arguments, 1, code_units.len())?;
+
+    Ok(JSValueVariant::from(
+        find(&code_units, &search, start).map_or(-1.0, |index| index as f64),
+    ))
+}
+
+fn last_index_of(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "lastIndexOf")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+
+    // A missing or NaN position searches from the end.
+    let position = interpretter.number_of(&argument(arguments, 1))?;
+    let start = if position.is_nan() {
+        code_units.len()
+    } else {
+        operations::to_integer_or_infinity(position).clamp(0.0, code_units.len() as f64) as usize
+    };
+
+    if search.len() > code_units.len() {
+        return Ok(JSValueVariant::from(-1.0));
+    }
+
+    let index = (0..=start.min(code_units.len() - search.len()))
+        .rev()
+        .find(|index| code_units[*index..].starts_with(&search));
+
+    Ok(JSValueVariant::from(
+        index.map_or(-1.0, |index| index as f64),
+    ))
+}
+
+fn includes(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "includes")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+    let start = clamped_position(interpretter, arguments, 1, code_units.len())?;
+
+    Ok(JSValueVariant::from(
+        find(&code_units, &search, start).is_some(),
+    ))
+}
+
+fn starts_with(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "startsWith")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+    let start = clamped_position(interpretter, arguments, 1, code_units.len())?;
+
+    Ok(JSValueVariant::from(
+        code_units[start..].starts_with(&search),
+    ))
+}
+
+fn ends_with(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "endsWith")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+    let end = match argument(arguments, 1) {
+        JSValueVariant::Undefined => code_units.len(),
+        _ => clamped_position(interpretter, arguments, 1, code_units.len())?,
+    };
+
+    Ok(JSValueVariant::from(code_units[..end].ends_with(&search)))
+}
+
+fn split(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "split")?;
+    let limit = match argument(arguments, 1) {
+        JSValueVariant::Undefined => u32::MAX,
+        limit => operations::to_uint32(interpretter.number_of(&limit)?),
+    } as usize;
+
+    let parts: Vec<Vec<u16>> = match argument(arguments, 0) {
+        _ if limit == 0 => vec![],
+        JSValueVariant::Undefined => vec![code_units.to_vec()],
+        separator => {
+            let separator: Vec<u16> = interpretter.string_of(&separator)?.encode_utf16().collect();
+
+            if separator.is_empty() {
+                code_units
+                    .iter()
+                    .map(|code_unit| vec![*code_unit])
+                    .collect()
+            } else {
+                let mut parts = vec![];
+                let mut start = 0;
+
+                while let Some(index) = find(&code_units, &separator, start) {
+                    parts.push(code_units[start..index].to_vec());
+                    start = index + separator.len();
+                }
+                parts.push(code_units[start..].to_vec());
+
+                parts
+            }
+        }
+    };
+
+    let values = parts
+        .iter()
+        .take(limit)
+        .map(|part| from_code_units(part))
+        .collect();
+
+    Ok(JSValueVariant::JSArray(interpretter.create_array(values)))
+}
+
+fn trim(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let string = this_string(interpretter, this, "trim")?;
+
+    Ok(JSValueVariant::from(
+        string.trim_matches(operations::is_js_whitespace),
+    ))
+}
+
+fn trim_start(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let string = this_string(interpretter, this, "trimStart")?;
+
+    Ok(JSValueVariant::from(
+        string.trim_start_matches(operations::is_js_whitespace),
+    ))
+}
+
+fn trim_end(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let string = this_string(interpretter, this, "trimEnd")?;
+
+    Ok(JSValueVariant::from(
+        string.trim_end_matches(operations::is_js_whitespace),
+    ))
+}
+
+/// The filler `padStart` and `padEnd` add to reach the requested length, or
+/// `None` when the string is left as it is.
+fn padding(
+    interpretter: &mut Interpretter,
+    code_units: &[u16],
+    arguments: &[JSValueVariant],
+) -> Result<Option<Vec<u16>>, EndiumError> {
+    let max_length = integer_argument(interpretter, arguments, 0)?;
+    if max_length <= code_units.len() as f64 {
+        return Ok(None);
+    }
+
+    if max_length > operations::MAX_STRING_LENGTH as f64 {
+        return Err(EndiumError::invalid_string_length());
+    }
+
+    let filler: Vec<u16> = match argument(arguments, 1) {
+        JSValueVariant::Undefined => vec![b' ' as u16],
+        filler => interpretter.string_of(&filler)?.encode_utf16().collect(),
+    };
+    if filler.is_empty() {
+        return Ok(None);
+    }
+
+    let fill_length = max_length as usize - code_units.len();
+
+    Ok(Some(
+        filler.iter().copied().cycle().take(fill_length).collect(),
+    ))
+}
+
+fn pad_start(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "padStart")?;
+
+    Ok(match padding(interpretter, &code_units, arguments)? {
+        Some(mut padded) => {
+            padded.extend_from_slice(&code_units);
+            from_code_units(&padded)
+        }
+        None => from_code_units(&code_units),
+    })
+}
+
+fn pad_end(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let mut code_units = this_code_units(interpretter, this, "padEnd")?.to_vec();
+
+    if let Some(padding) = padding(interpretter, &code_units, arguments)? {
+        code_units.extend(padding);
+    }
+
+    Ok(from_code_units(&code_units))
+}
+
+fn repeat(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let string = this_string(interpretter, this, "repeat")?;
+    let count = integer_argument(interpretter, arguments, 0)?;
+
+    if count < 0.0 || count.is_infinite() {
+        return Err(EndiumError::range_error(format!(
+            "Invalid count value: {}",
+            operations::number_to_string(count)
+        )));
+    }
+
+    if string.is_empty() {
+        return Ok(JSValueVariant::from(""));
+    }
+
+    if string.encode_utf16().count() as f64 * count > operations::MAX_STRING_LENGTH as f64 {
+        return Err(EndiumError::invalid_string_length());
+    }
+
+    Ok(JSValueVariant::from(string.repeat(count as usize)))
+}
+
+/// Expands the `$` patterns of a replacement string for the match of
+/// `matched` at `position` in `code_units`.
+fn substitution(
+    code_units: &[u16],
+    matched: &[u16],
+    position: usize,
+    replacement: &[u16],
+) -> Vec<u16> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < replacement.len() {
+        let code_unit = replacement[index];
+        let next = replacement.get(index + 1).copied();
+
+        if code_unit == b'$' as u16 {
+            match next.map(|next| next as u8) {
+                Some(b'$') => result.push(b'$' as u16),
+                Some(b'&') => result.extend_from_slice(matched),
+                Some(b'`') => result.extend_from_slice(&code_units[..position]),
+                Some(b'\'') => result.extend_from_slice(&code_units[position + matched.len()..]),
+                _ => {
+                    result.push(code_unit);
+                    index += 1;
+                    continue;
+                }
+            }
+            index += 2;
+            continue;
+        }
+
+        result.push(code_unit);
+        index += 1;
+    }
+
+    result
+}
+
+/// Replaces the matches of the search string found at `positions`. The
+/// replacement is either a string with `$` patterns or a function called
+/// with the match, its position and the whole string.
+fn replace_at(
+    interpretter: &mut Interpretter,
+    code_units: &[u16],
+    search: &[u16],
+    positions: &[usize],
+    replacement: &JSValueVariant,
+) -> Result<JSValueVariant, EndiumError> {
+    let mut result = vec![];
+    let mut end_of_last_match = 0;
+
+    // A replacement string is converted once, before anything is replaced.
+    let template: Vec<u16> = match replacement {
+        JSValueVariant::JSFunction(_) => vec![],
+        _ => interpretter
+            .string_of(replacement)?
+            .encode_utf16()
+            .collect(),
+    };
+
+    for &position in positions {
+        result.extend_from_slice(&code_units[end_of_last_match..position]);
+
+        let replaced: Vec<u16> = match replacement {
+            JSValueVariant::JSFunction(replacer) => {
+                let value = interpretter.call_function(
+                    replacer,
+                    JSValueVariant::Undefined,
+                    vec![
+                        from_code_units(search),
+                        JSValueVariant::from(position as f64),
+                        from_code_units(code_units),
+                    ],
+                )?;
+                interpretter.string_of(&value)?.encode_utf16().collect()
+            }
+            _ => substitution(code_units, search, position, &template),
+        };
+
+        result.extend(replaced);
+        end_of_last_match = position + search.len();
+    }
+
+    result.extend_from_slice(&code_units[end_of_last_match..]);
+
+    Ok(from_code_units(&result))
+}
+
+fn replace(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "replace")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+    let positions: Vec<usize> = find(&code_units, &search, 0).into_iter().collect();
+
+    replace_at(
+        interpretter,
+        &code_units,
+        &search,
+        &positions,
+        &argument(arguments, 1),
+    )
+}
+
+fn replace_all(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "replaceAll")?;
+    let search = string_argument(interpretter, arguments, 0)?;
+
+    // An empty search string matches between every pair of code units.
+    let advance = search.len().max(1);
+    let mut positions = vec![];
+    let mut position = find(&code_units, &search, 0);
+
+    while let Some(index) = position {
+        positions.push(index);
+        position = if index + advance <= code_units.len() {
+            find(&code_units, &search, index + advance)
+        } else {
+            None
+        };
+    }
+
+    replace_at(
+        interpretter,
+        &code_units,
+        &search,
+        &positions,
+        &argument(arguments, 1),
+    )
+}
+
+fn to_upper_case(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    Ok(JSValueVariant::from(
+        this_string(interpretter, this, "toUpperCase")?.to_uppercase(),
+    ))
+}
+
+fn to_lower_case(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    _arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    Ok(JSValueVariant::from(
+        this_string(interpretter, this, "toLowerCase")?.to_lowercase(),
+    ))
+}
+
+fn at(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let code_units = this_code_units(interpretter, this, "at")?;
+    let relative = integer_argument(interpretter, arguments, 0)?;
+    let index = if relative < 0.0 {
+        code_units.len() as f64 + relative
+    } else {
+        relative
+    };
+
+    if index < 0.0 || index >= code_units.len() as f64 {
+        return Ok(JSValueVariant::Undefined);
+    }
+
+    Ok(from_code_units(&[code_units[index as usize]]))
+}
+
+fn normalize_string(
+    interpretter: &mut Interpretter,
+    this: &JSValueVariant,
+    arguments: &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError> {
+    let string = this_string(interpretter, this, "normalize")?;
+    let form = match argument(arguments, 0) {
+        JSValueVariant::Undefined => Some(NormalizationForm::Nfc),
+        form => NormalizationForm::from(&interpretter.string_of(&form)?),
+    };
+
+    match form {
+        Some(form) => Ok(JSValueVariant::from(normalize(&string, form))),
+        None => Err(EndiumError::range_error(String::from(
+            "The normalization form should be one of NFC, NFD, NFKC, NFKD.",
+        ))),
+    }
+}
@@ -0,0 +1,413 @@
+use crate::apis::type_variants::js_object::{JSObject, PropertyDescriptor, array_index};
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// How far past the end an element may be written before the array stops
+/// storing its elements contiguously.
+const MAX_DENSE_GAP: u32 = 1024;
+
+/// Elements are kept in a vector, with `None` for holes, until a write leaves
+/// a large gap; from then on only the present elements are stored.
+#[derive(Debug)]
+enum ArrayStorage {
+    Dense(Vec<Option<JSValueVariant>>),
+    Sparse(BTreeMap<u32, JSValueVariant>),
+}
+
+#[derive(Debug)]
+struct ArrayData {
+    elements: ArrayStorage,
+    length: u32,
+    length_writable: bool,
+    /// Whether some index is held by the object rather than the elements.
+    index_properties: bool,
+}
+
+/// An array. Elements live in their own storage while named properties and
+/// the prototype are kept in an ordinary object. An index given attributes
+/// or accessors of its own is moved into the object as well.
+#[derive(Clone)]
+pub struct JSArray {
+    data: Rc<RefCell<ArrayData>>,
+    object: JSObject,
+}
+
+impl JSArray {
+    pub fn length(&self) -> u32 {
+        self.data.borrow().length
+    }
+
+    pub fn is_length_writable(&self) -> bool {
+        self.data.borrow().length_writable
+    }
+
+    /// Makes the length read-only, so the array can neither grow nor shrink.
+    pub fn freeze_length(&self) {
+        self.data.borrow_mut().length_writable = false;
+    }
+
+    /// Whether every element is plain data in the element storage and the
+    /// length is writable, so elements can be read and written directly.
+    pub fn is_plain(&self) -> bool {
+        let data = self.data.borrow();
+
+        data.length_writable && !data.index_properties
+    }
+
+    /// Grows or truncates the array. Truncating drops every element at or
+    /// past `length`, and stops past the last index the object holds that
+    /// cannot be deleted.
+    pub fn set_length(&self, length: u32) -> Result<(), EndiumError> {
+        if length != self.length() && !self.is_length_writable() {
+            return Err(EndiumError::type_error(String::from(
+                "Cannot assign to read only property 'length' of object",
+            )));
+        }
+
+        if self.data.borrow().index_properties {
+            for key in self.object.keys().into_iter().rev() {
+                if array_index(&key).is_some_and(|index| index >= length)
+                    && !self.object.delete_property(&key)
+                {
+                    self.resize(array_index(&key).unwrap_or(length) + 1);
+                    return Err(EndiumError::type_error(format!(
+                        "Cannot delete property '{}' of [object Array]",
+                        key
+                    )));
+                }
+            }
+        }
+
+        self.resize(length);
+        Ok(())
+    }
+
+    fn resize(&self, length: u32) {
+        let mut data = self.data.borrow_mut();
+        let old_length = data.length;
+
+        if let ArrayStorage::Dense(elements) = &data.elements
+            && length > old_length.saturating_add(MAX_DENSE_GAP)
+        {
+            data.elements = ArrayStorage::Sparse(Self::to_sparse(elements));
+        }
+
+        match &mut data.elements {
+            ArrayStorage::Dense(elements) => elements.resize(length as usize, None),
+            ArrayStorage::Sparse(elements) => {
+                elements.split_off(&length);
+            }
+        }
+
+        data.length = length;
+    }
+
+    /// The element at `index`, or `None` for holes and indices past the end.
+    pub fn get(&self, index: u32) -> Option<JSValueVariant> {
+        match &self.data.borrow().elements {
+            ArrayStorage::Dense(elements) => elements.get(index as usize).cloned().flatten(),
+            ArrayStorage::Sparse(elements) => elements.get(&index).cloned(),
+        }
+    }
+
+    pub fn has_index(&self, index: u32) -> bool {
+        match &self.data.borrow().elements {
+            ArrayStorage::Dense(elements) => {
+                matches!(elements.get(index as usize), Some(Some(_)))
+            }
+            ArrayStorage::Sparse(elements) => elements.contains_key(&index),
+        }
+    }
+
+    /// Stores `value` at `index`, growing the array past it if needed. There
+    /// is no room for an element at u32::MAX, the largest length.
+    pub fn set(&self, index: u32, value: JSValueVariant) -> Result<(), EndiumError> {
+        if index >= self.length() {
+            if !self.is_length_writable() {
+                return Err(EndiumError::type_error(format!(
+                    "Cannot add property {}, object is not extensible",
+                    index
+                )));
+            }
+
+            let length = index
+                .checked_add(1)
+                .ok_or_else(EndiumError::invalid_array_length)?;
+            self.set_length(length)?;
+        }
+
+        match &mut self.data.borrow_mut().elements {
+            ArrayStorage::Dense(elements) => elements[index as usize] = Some(value),
+            ArrayStorage::Sparse(elements) => {
+                elements.insert(index, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn push(&self, value: JSValueVariant) -> Result<(), EndiumError> {
+        self.set(self.length(), value)
+    }
+
+    /// Removes the element at `index`, leaving a hole.
+    pub fn delete(&self, index: u32) {
+        match &mut self.data.borrow_mut().elements {
+            ArrayStorage::Dense(elements) => {
+                if let Some(element) = elements.get_mut(index as usize) {
+                    *element = None;
+                }
+            }
+            ArrayStorage::Sparse(elements) => {
+                elements.remove(&index);
+            }
+        }
+    }
+
+    /// Moves the element at `index`, if any, into the object as an ordinary
+    /// data property, where it can be given attributes and accessors.
+    pub fn detach(&self, index: u32) {
+        if let Some(element) = self.get(index) {
+            self.delete(index);
+            self.object
+                .define_property(&index.to_string(), PropertyDescriptor::from(element));
+        }
+
+        self.data.borrow_mut().index_properties = true;
+    }
+
+    /// The present elements with their indices, in ascending order.
+    pub fn entries(&self) -> Vec<(u32, JSValueVariant)> {
+        match &self.data.borrow().elements {
+            ArrayStorage::Dense(elements) => elements
+                .iter()
+                .enumerate()
+                .filter_map(|(index, element)| Some((index as u32, element.clone()?)))
+                .collect(),
+            ArrayStorage::Sparse(elements) => elements
+                .iter()
+                .map(|(index, element)| (*index, element.clone()))
+                .collect(),
+        }
+    }
+
+    /// Replaces every element with `entries`, given in ascending order of
+    /// index, and the length with `length`. Only the entries are stored when
+    /// the array would have more holes than `MAX_DENSE_GAP`.
+    pub fn set_entries(&self, entries: Vec<(u32, JSValueVariant)>, length: u32) {
+        let elements = if length as usize - entries.len() > MAX_DENSE_GAP as usize {
+            ArrayStorage::Sparse(entries.into_iter().collect())
+        } else {
+            let mut elements = vec![None; length as usize];
+            for (index, element) in entries {
+                elements[index as usize] = Some(element);
+            }
+            ArrayStorage::Dense(elements)
+        };
+
+        let mut data = self.data.borrow_mut();
+
+        data.length = length;
+        data.elements = elements;
+    }
+
+    /// Indices that hold an element, in ascending order.
+    pub fn indices(&self) -> Vec<u32> {
+        match &self.data.borrow().elements {
+            ArrayStorage::Dense(elements) => elements
+                .iter()
+                .enumerate()
+                .filter(|(_, element)| element.is_some())
+                .map(|(index, _)| index as u32)
+                .collect(),
+            ArrayStorage::Sparse(elements) => elements.keys().copied().collect(),
+        }
+    }
+
+    /// Indices that hold an element or a property of the object, in
+    /// ascending order.
+    pub fn own_indices(&self) -> Vec<u32> {
+        let mut indices = self.indices();
+
+        if self.data.borrow().index_properties {
+            indices.extend(self.object.keys().iter().filter_map(|key| array_index(key)));
+            indices.sort_unstable();
+        }
+
+        indices
+    }
+
+    /// The ordinary object holding the array's named properties and prototype.
+    pub fn object(&self) -> &JSObject {
+        &self.object
+    }
+
+    pub fn ptr_eq(&self, other: &JSArray) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
+    /// Identifies the element storage the array's clones share.
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.data) as usize
+    }
+
+    /// How many handles share the array's element storage.
+    pub fn reference_count(&self) -> usize {
+        Rc::strong_count(&self.data)
+    }
+
+    /// Calls `visit` with every element, skipping holes.
+    pub fn for_each_element(&self, mut visit: impl FnMut(&JSValueVariant)) {
+        match &self.data.borrow().elements {
+            ArrayStorage::Dense(elements) => elements.iter().flatten().for_each(&mut visit),
+            ArrayStorage::Sparse(elements) => elements.values().for_each(&mut visit),
+        }
+    }
+
+    /// Drops every element, for an array nothing can reach anymore.
+    pub fn clear(&self) {
+        let elements = std::mem::replace(
+            &mut self.data.borrow_mut().elements,
+            ArrayStorage::Dense(vec![]),
+        );
+        self.data.borrow_mut().length = 0;
+
+        drop(elements);
+    }
+
+    fn to_sparse(elements: &[Option<JSValueVariant>]) -> BTreeMap<u32, JSValueVariant> {
+        elements
+            .iter()
+            .enumerate()
+            .filter_map(|(index, element)| Some((index as u32, element.clone()?)))
+            .collect()
+    }
+
+    pub fn new(prototype: Option<JSObject>) -> Self {
+        Self::from(vec![], prototype)
+    }
+
+    pub fn from(elements: Vec<Option<JSValueVariant>>, prototype: Option<JSObject>) -> Self {
+        let length = elements.len() as u32;
+
+        Self {
+            data: Rc::new(RefCell::new(ArrayData {
+                elements: ArrayStorage::Dense(elements),
+                length,
+                length_writable: true,
+                index_properties: false,
+            })),
+            object: JSObject::from(prototype),
+        }
+    }
+}
+
+// Arrays can contain themselves, so only the length is shown.
+impl fmt::Debug for JSArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSArray")
+            .field("length", &self.length())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_sparse(js_array: &JSArray) -> bool {
+        matches!(js_array.data.borrow().elements, ArrayStorage::Sparse(_))
+    }
+
+    fn number(js_array: &JSArray, index: u32) -> Option<f64> {
+        match js_array.get(index)? {
+            JSValueVariant::JSNumber(js_number) => Some(js_number.number_value()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn stays_dense_for_small_gaps() {
+        let js_array = JSArray::new(None);
+        js_array
+            .set(MAX_DENSE_GAP - 1, JSValueVariant::from(1.0))
+            .unwrap();
+
+        assert!(!is_sparse(&js_array));
+        assert_eq!(js_array.length(), MAX_DENSE_GAP);
+        assert!(!js_array.has_index(0));
+
+        js_array
+            .set(2 * MAX_DENSE_GAP - 1, JSValueVariant::from(2.0))
+            .unwrap();
+
+        assert!(!is_sparse(&js_array));
+    }
+
+    #[test]
+    fn becomes_sparse_on_a_large_gap() {
+        let js_array = JSArray::from(vec![Some(JSValueVariant::from(1.0))], None);
+        js_array
+            .set(MAX_DENSE_GAP + 1, JSValueVariant::from(2.0))
+            .unwrap();
+
+        assert!(is_sparse(&js_array));
+        assert_eq!(js_array.length(), MAX_DENSE_GAP + 2);
+        assert_eq!(js_array.indices(), [0, MAX_DENSE_GAP + 1]);
+        assert_eq!(number(&js_array, 0), Some(1.0));
+        assert_eq!(number(&js_array, MAX_DENSE_GAP + 1), Some(2.0));
+    }
+
+    #[test]
+    fn becomes_sparse_when_the_length_grows_far() {
+        let js_array = JSArray::new(None);
+        js_array.set_length(u32::MAX).unwrap();
+
+        assert!(is_sparse(&js_array));
+        assert!(js_array.entries().is_empty());
+    }
+
+    #[test]
+    fn truncates_sparse_elements_with_the_length() {
+        let js_array = JSArray::new(None);
+        js_array.set(10, JSValueVariant::from(1.0)).unwrap();
+        js_array.set(5_000, JSValueVariant::from(2.0)).unwrap();
+        js_array.set_length(100).unwrap();
+
+        assert_eq!(js_array.indices(), [10]);
+        assert_eq!(js_array.length(), 100);
+    }
+
+    #[test]
+    fn chooses_the_storage_for_new_entries() {
+        let js_array = JSArray::new(None);
+        let entries = vec![
+            (0, JSValueVariant::from(1.0)),
+            (3, JSValueVariant::from(2.0)),
+        ];
+
+        js_array.set_entries(entries.clone(), 4);
+        assert!(!is_sparse(&js_array));
+
+        js_array.set_entries(entries, 1_000_000);
+        assert!(is_sparse(&js_array));
+        assert_eq!(number(&js_array, 3), Some(2.0));
+    }
+
+    #[test]
+    fn has_no_room_past_the_largest_length() {
+        let js_array = JSArray::new(None);
+
+        assert!(js_array.set(u32::MAX, JSValueVariant::Undefined).is_err());
+        assert!(
+            js_array
+                .set(u32::MAX - 1, JSValueVariant::Undefined)
+                .is_ok()
+        );
+        assert_eq!(js_array.length(), u32::MAX);
+    }
+}
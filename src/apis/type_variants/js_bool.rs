@@ -1,6 +1,5 @@
 #[derive(Clone, Debug)]
 pub struct JSBool {
-    is_primitive: bool,
     bool_value: bool,
 }
 
@@ -9,10 +8,7 @@ impl JSBool {
         self.bool_value
     }
 
-    fn new(bool_value: bool) -> Self {
-        Self {
-            is_primitive: true,
-            bool_value,
-        }
+    pub fn new(bool_value: bool) -> Self {
+        Self { bool_value }
     }
 }
@@ -1,41 +1,230 @@
-use crate::apis::type_variants::js_string::JSString;
-use crate::engine::interpretter;
-use crate::engine::state::State;
-use crate::engine::tokens::Token;
+use crate::apis::type_variants::js_object::{JSObject, ObjectKey, PropertyDescriptor};
+use crate::engine::ast::{Expression, FunctionNode};
+use crate::engine::interpretter::Interpretter;
 use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
 use crate::scope::Scope;
+use std::cell::{Ref, RefCell};
+use std::fmt;
+use std::rc::Rc;
 
-#[derive(Clone, Debug)]
+/// A function implemented in Rust. It receives the interpretter, the `this`
+/// value and the call's arguments.
+pub type NativeFunction = fn(
+    &mut Interpretter,
+    &JSValueVariant,
+    &[JSValueVariant],
+) -> Result<JSValueVariant, EndiumError>;
+
+#[derive(Clone)]
+pub enum FunctionKind {
+    Script {
+        node: Rc<FunctionNode>,
+        scope: Rc<RefCell<Scope>>,
+    },
+    /// An arrow function, which takes `this` and `arguments` from the scope
+    /// it was created in and cannot be called with `new`.
+    Arrow {
+        node: Rc<FunctionNode>,
+        scope: Rc<RefCell<Scope>>,
+    },
+    /// A class constructor, which can only be called with `new`.
+    Class {
+        node: Rc<FunctionNode>,
+        scope: Rc<RefCell<Scope>>,
+        definition: Rc<ClassDefinition>,
+    },
+    Native {
+        name: String,
+        function: NativeFunction,
+    },
+    /// A function made by `bind`, which calls its target with the bound
+    /// `this` and the bound arguments before its own.
+    Bound {
+        name: String,
+        target: Box<JSFunction>,
+        this: Box<JSValueVariant>,
+        arguments: Vec<JSValueVariant>,
+    },
+}
+
+/// What a class puts on each instance it constructs, before its
+/// constructor body runs or, in a derived class, once `super(...)` returns.
+pub struct ClassDefinition {
+    derived: bool,
+    elements: RefCell<Vec<InstanceElement>>,
+}
+
+impl ClassDefinition {
+    /// Whether the class extends another, leaving `this` to be created by
+    /// the constructor it calls with `super(...)`.
+    pub fn is_derived(&self) -> bool {
+        self.derived
+    }
+
+    pub fn elements(&self) -> Ref<'_, Vec<InstanceElement>> {
+        self.elements.borrow()
+    }
+
+    pub fn set_elements(&self, elements: Vec<InstanceElement>) {
+        *self.elements.borrow_mut() = elements;
+    }
+
+    pub fn new(derived: bool) -> Self {
+        Self {
+            derived,
+            elements: RefCell::new(vec![]),
+        }
+    }
+}
+
+pub enum InstanceElement {
+    /// A private method or accessor, shared by all instances.
+    PrivateMethod {
+        key: ObjectKey,
+        descriptor: PropertyDescriptor,
+    },
+    /// A field, whose initializer runs for each instance with `this` bound
+    /// to it.
+    Field {
+        key: ObjectKey,
+        value: Option<Expression>,
+    },
+}
+
+/// A callable object. Both script functions and natives keep their own
+/// properties, such as `name` and `length`, in an ordinary object.
+#[derive(Clone)]
 pub struct JSFunction {
-    is_primitive: bool,
-    argument_names: Vec<String>,
-    scope: Scope,
+    kind: FunctionKind,
+    object: JSObject,
 }
 
 impl JSFunction {
-    pub fn scope(&self) -> &Scope {
-        &self.scope
+    pub fn kind(&self) -> &FunctionKind {
+        &self.kind
     }
 
-    pub fn execute(&mut self, arguments: Vec<JSValueVariant>) {
-        self.argument_names
-            .iter()
-            .zip(arguments)
-            .for_each(|(key, state)| {
-                self.scope
-                    .insert_state(key.to_string(), State::new(state, true));
-            });
-        let mut interpretter = interpretter::Interpretter::new(None, Some(self.scope.clone()));
-        let _ = interpretter.execute();
-        println!("Function scope {:#?}", self.scope);
-        self.scope.clear_state();
+    pub fn name(&self) -> &str {
+        match &self.kind {
+            FunctionKind::Script { node, .. }
+            | FunctionKind::Arrow { node, .. }
+            | FunctionKind::Class { node, .. } => node.name.as_deref().unwrap_or_default(),
+            FunctionKind::Native { name, .. } | FunctionKind::Bound { name, .. } => name,
+        }
     }
 
-    pub fn new(instructions: Vec<Token>, argument_names: Vec<String>, parent_scope: Scope) -> Self {
-        Self {
-            is_primitive: true,
-            argument_names,
-            scope: Scope::new(Some(parent_scope), instructions),
+    /// Whether the function can be called with `new`, which arrow functions
+    /// cannot.
+    pub fn is_constructor(&self) -> bool {
+        match &self.kind {
+            FunctionKind::Arrow { .. } => false,
+            FunctionKind::Bound { target, .. } => target.is_constructor(),
+            _ => true,
         }
     }
+
+    /// The object holding the function's properties and prototype.
+    pub fn object(&self) -> &JSObject {
+        &self.object
+    }
+
+    pub fn ptr_eq(&self, other: &JSFunction) -> bool {
+        self.object.ptr_eq(&other.object)
+    }
+
+    pub fn new(node: Rc<FunctionNode>, scope: Rc<RefCell<Scope>>, prototype: JSObject) -> Self {
+        let length = node.length();
+
+        Self::from(FunctionKind::Script { node, scope }, length, prototype)
+    }
+
+    pub fn arrow(node: Rc<FunctionNode>, scope: Rc<RefCell<Scope>>, prototype: JSObject) -> Self {
+        let length = node.length();
+
+        Self::from(FunctionKind::Arrow { node, scope }, length, prototype)
+    }
+
+    pub fn class(
+        node: Rc<FunctionNode>,
+        scope: Rc<RefCell<Scope>>,
+        definition: Rc<ClassDefinition>,
+        prototype: JSObject,
+    ) -> Self {
+        let length = node.length();
+
+        Self::from(
+            FunctionKind::Class {
+                node,
+                scope,
+                definition,
+            },
+            length,
+            prototype,
+        )
+    }
+
+    pub fn native(
+        name: &str,
+        length: usize,
+        function: NativeFunction,
+        prototype: JSObject,
+    ) -> Self {
+        Self::from(
+            FunctionKind::Native {
+                name: name.to_string(),
+                function,
+            },
+            length,
+            prototype,
+        )
+    }
+
+    /// A function calling `target`, named after it as `bound name`.
+    pub fn bound(
+        target: JSFunction,
+        this: JSValueVariant,
+        arguments: Vec<JSValueVariant>,
+        target_name: &str,
+        length: usize,
+        prototype: JSObject,
+    ) -> Self {
+        Self::from(
+            FunctionKind::Bound {
+                name: format!("bound {}", target_name),
+                target: Box::new(target),
+                this: Box::new(this),
+                arguments,
+            },
+            length,
+            prototype,
+        )
+    }
+
+    fn from(kind: FunctionKind, length: usize, prototype: JSObject) -> Self {
+        let function = Self {
+            kind,
+            object: JSObject::from(Some(prototype)),
+        };
+
+        function.object.define_property(
+            "length",
+            PropertyDescriptor::data(JSValueVariant::from(length as f64), false, false, true),
+        );
+        function.object.define_property(
+            "name",
+            PropertyDescriptor::data(JSValueVariant::from(function.name()), false, false, true),
+        );
+
+        function
+    }
+}
+
+// The captured scope usually contains the function itself, so it is left out.
+impl fmt::Debug for JSFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSFunction")
+            .field("name", &self.name())
+            .finish_non_exhaustive()
+    }
 }
@@ -1,18 +1,5 @@
-use crate::{
-    apis::{
-        features::assignment::{
-            addition_assignment::AdditionAssignment, decrement_assignment::DecrementAssignment,
-            division_assignment::DivisionAssignment,
-            multiplication_assignment::MultiplicationAssignment,
-        },
-        type_variants::js_string::JSString,
-    },
-    engine::value_variant::JSValueVariant,
-};
-
 #[derive(Clone, Debug)]
 pub struct JSNumber {
-    is_primitive: bool,
     number_value: f64,
 }
 
@@ -22,53 +9,6 @@ impl JSNumber {
     }
 
     pub fn new(number_value: f64) -> Self {
-        Self {
-            is_primitive: true,
-            number_value,
-        }
-    }
-}
-
-impl AdditionAssignment for JSNumber {
-    fn addition_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            JSValueVariant::JSNumber(js_num) => {
-                self.number_value = self.number_value + js_num.number_value;
-            }
-            _ => {}
-        }
-    }
-}
-
-impl DecrementAssignment for JSNumber {
-    fn decrement_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            JSValueVariant::JSNumber(js_num) => {
-                self.number_value = self.number_value - js_num.number_value;
-            }
-            _ => {}
-        }
-    }
-}
-
-impl MultiplicationAssignment for JSNumber {
-    fn multiplication_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            JSValueVariant::JSNumber(js_num) => {
-                self.number_value = self.number_value * js_num.number_value;
-            }
-            _ => {}
-        }
-    }
-}
-
-impl DivisionAssignment for JSNumber {
-    fn division_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            JSValueVariant::JSNumber(js_num) => {
-                self.number_value = self.number_value / js_num.number_value;
-            }
-            _ => {}
-        }
+        Self { number_value }
     }
 }
@@ -0,0 +1,480 @@
+use crate::engine::operations;
+use crate::engine::value_variant::JSValueVariant;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// What a property holds: a plain value, or the functions run when it is read
+/// and written.
+#[derive(Clone, Debug)]
+pub enum PropertyKind {
+    Data {
+        value: JSValueVariant,
+        writable: bool,
+    },
+    Accessor {
+        get: Option<JSValueVariant>,
+        set: Option<JSValueVariant>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct PropertyDescriptor {
+    kind: PropertyKind,
+    enumerable: bool,
+    configurable: bool,
+}
+
+impl PropertyDescriptor {
+    pub fn kind(&self) -> &PropertyKind {
+        &self.kind
+    }
+
+    pub fn is_enumerable(&self) -> bool {
+        self.enumerable
+    }
+
+    pub fn is_configurable(&self) -> bool {
+        self.configurable
+    }
+
+    /// The stored value of a data property; `None` for accessors.
+    pub fn value(&self) -> Option<&JSValueVariant> {
+        match &self.kind {
+            PropertyKind::Data { value, .. } => Some(value),
+            PropertyKind::Accessor { .. } => None,
+        }
+    }
+
+    /// A writable, enumerable and configurable data property, as created by
+    /// plain assignment and object literals.
+    pub fn from(value: JSValueVariant) -> Self {
+        Self::data(value, true, true, true)
+    }
+
+    pub fn data(
+        value: JSValueVariant,
+        writable: bool,
+        enumerable: bool,
+        configurable: bool,
+    ) -> Self {
+        Self {
+            kind: PropertyKind::Data { value, writable },
+            enumerable,
+            configurable,
+        }
+    }
+
+    pub fn accessor(
+        get: Option<JSValueVariant>,
+        set: Option<JSValueVariant>,
+        enumerable: bool,
+        configurable: bool,
+    ) -> Self {
+        Self {
+            kind: PropertyKind::Accessor { get, set },
+            enumerable,
+            configurable,
+        }
+    }
+}
+
+/// A property key or a private name, as class elements and member
+/// expressions name them.
+#[derive(Clone, Debug)]
+pub enum ObjectKey {
+    Property(String),
+    /// `#name`, with the id of the class element it refers to. Each
+    /// evaluation of a class gives its private names new ids.
+    Private {
+        name: String,
+        id: String,
+    },
+}
+
+impl ObjectKey {
+    /// The key as it appears in error messages.
+    pub fn name(&self) -> String {
+        match self {
+            ObjectKey::Property(key) => key.clone(),
+            ObjectKey::Private { name, .. } => format!("#{}", name),
+        }
+    }
+}
+
+/// A property descriptor as `Object.defineProperty` takes it, where any
+/// field may be left out. A getter or setter given as undefined is `Some`.
+#[derive(Clone, Debug, Default)]
+pub struct PartialDescriptor {
+    pub value: Option<JSValueVariant>,
+    pub writable: Option<bool>,
+    pub get: Option<JSValueVariant>,
+    pub set: Option<JSValueVariant>,
+    pub enumerable: Option<bool>,
+    pub configurable: Option<bool>,
+}
+
+impl PartialDescriptor {
+    pub fn is_accessor(&self) -> bool {
+        self.get.is_some() || self.set.is_some()
+    }
+
+    pub fn is_data(&self) -> bool {
+        self.value.is_some() || self.writable.is_some()
+    }
+}
+
+#[derive(Default)]
+struct ObjectData {
+    keys: Vec<String>,
+    properties: HashMap<String, PropertyDescriptor>,
+    /// Private fields, methods and accessors, by private name id. They are
+    /// not properties: no key lookup, prototype or enumeration sees them.
+    private_elements: HashMap<String, PropertyDescriptor>,
+    prototype: Option<JSObject>,
+    /// Set on the `arguments` object of a call, which iterates like an array.
+    is_arguments: bool,
+}
+
+/// A heap-allocated object. Clones share the same properties, so objects keep
+/// their identity when passed around.
+#[derive(Clone)]
+pub struct JSObject {
+    data: Rc<RefCell<ObjectData>>,
+}
+
+impl JSObject {
+    pub fn get_own_property(&self, property_key: &str) -> Option<PropertyDescriptor> {
+        self.data.borrow().properties.get(property_key).cloned()
+    }
+
+    /// Looks `property_key` up on the object and then along its prototype chain.
+    pub fn find_property(&self, property_key: &str) -> Option<PropertyDescriptor> {
+        let mut object = Some(self.clone());
+
+        while let Some(current) = object {
+            if let Some(property) = current.get_own_property(property_key) {
+                return Some(property);
+            }
+            object = current.prototype();
+        }
+
+        None
+    }
+
+    /// The value of a data property found on the object or its prototypes.
+    pub fn get_property(&self, property_key: &str) -> Option<JSValueVariant> {
+        self.find_property(property_key)?.value().cloned()
+    }
+
+    /// Stores `value` as an own data property. An existing own data property
+    /// keeps its attributes, anything else is replaced by a default one.
+    pub fn set_property(&self, property_key: &str, value: JSValueVariant) {
+        let mut data = self.data.borrow_mut();
+
+        if let Some(PropertyDescriptor {
+            kind: PropertyKind::Data { value: current, .. },
+            ..
+        }) = data.properties.get_mut(property_key)
+        {
+            *current = value;
+            return;
+        }
+
+        drop(data);
+        self.define_property(property_key, PropertyDescriptor::from(value));
+    }
+
+    pub fn define_property(&self, property_key: &str, descriptor: PropertyDescriptor) {
+        let mut data = self.data.borrow_mut();
+
+        if !data.properties.contains_key(property_key) {
+            data.keys.push(property_key.to_string());
+        }
+
+        data.properties.insert(property_key.to_string(), descriptor);
+    }
+
+    /// Defines or updates an own property from a partial descriptor, filling
+    /// in left out fields from the current property, or with defaults for a
+    /// new one. Returns false, changing nothing, when the current property
+    /// is non-configurable and the change is not allowed.
+    pub fn define_own_property(&self, property_key: &str, descriptor: PartialDescriptor) -> bool {
+        // An accessor half given as undefined is stored as absent.
+        let function = |value: Option<JSValueVariant>| {
+            value.filter(|value| !matches!(value, JSValueVariant::Undefined))
+        };
+
+        let Some(current) = self.get_own_property(property_key) else {
+            let kind = if descriptor.is_accessor() {
+                PropertyKind::Accessor {
+                    get: function(descriptor.get),
+                    set: function(descriptor.set),
+                }
+            } else {
+                PropertyKind::Data {
+                    value: descriptor.value.unwrap_or(JSValueVariant::Undefined),
+                    writable: descriptor.writable.unwrap_or(false),
+                }
+            };
+
+            self.define_property(
+                property_key,
+                PropertyDescriptor {
+                    kind,
+                    enumerable: descriptor.enumerable.unwrap_or(false),
+                    configurable: descriptor.configurable.unwrap_or(false),
+                },
+            );
+            return true;
+        };
+
+        if !current.configurable && !Self::is_compatible(&current, &descriptor) {
+            return false;
+        }
+
+        let kind = match current.kind {
+            PropertyKind::Data { .. } if descriptor.is_accessor() => PropertyKind::Accessor {
+                get: function(descriptor.get),
+                set: function(descriptor.set),
+            },
+            PropertyKind::Accessor { .. } if descriptor.is_data() => PropertyKind::Data {
+                value: descriptor.value.unwrap_or(JSValueVariant::Undefined),
+                writable: descriptor.writable.unwrap_or(false),
+            },
+            PropertyKind::Data { value, writable } => PropertyKind::Data {
+                value: descriptor.value.unwrap_or(value),
+                writable: descriptor.writable.unwrap_or(writable),
+            },
+            PropertyKind::Accessor { get, set } => PropertyKind::Accessor {
+                get: descriptor.get.map_or(get, |get| function(Some(get))),
+                set: descriptor.set.map_or(set, |set| function(Some(set))),
+            },
+        };
+
+        self.define_property(
+            property_key,
+            PropertyDescriptor {
+                kind,
+                enumerable: descriptor.enumerable.unwrap_or(current.enumerable),
+                configurable: descriptor.configurable.unwrap_or(current.configurable),
+            },
+        );
+        true
+    }
+
+    /// Whether `descriptor` leaves what a non-configurable property fixes
+    /// unchanged: its configurability, enumerability and kind, and the value
+    /// of a read-only data property or the functions of an accessor.
+    fn is_compatible(current: &PropertyDescriptor, descriptor: &PartialDescriptor) -> bool {
+        let same_function = |new: &Option<JSValueVariant>, current: &Option<JSValueVariant>| {
+            new.as_ref().is_none_or(|new| {
+                operations::same_value(new, current.as_ref().unwrap_or(&JSValueVariant::Undefined))
+            })
+        };
+
+        if descriptor.configurable == Some(true)
+            || descriptor
+                .enumerable
+                .is_some_and(|enumerable| enumerable != current.enumerable)
+        {
+            return false;
+        }
+
+        match &current.kind {
+            PropertyKind::Data { .. } if descriptor.is_accessor() => false,
+            PropertyKind::Accessor { .. } if descriptor.is_data() => false,
+            PropertyKind::Data {
+                value,
+                writable: false,
+            } => {
+                descriptor.writable != Some(true)
+                    && descriptor
+                        .value
+                        .as_ref()
+                        .is_none_or(|new| operations::same_value(new, value))
+            }
+            PropertyKind::Data { .. } => true,
+            PropertyKind::Accessor { get, set } => {
+                same_function(&descriptor.get, get) && same_function(&descriptor.set, set)
+            }
+        }
+    }
+
+    pub fn delete_property(&self, property_key: &str) -> bool {
+        let mut data = self.data.borrow_mut();
+
+        match data.properties.get(property_key) {
+            Some(property) if !property.configurable => false,
+            Some(_) => {
+                data.properties.remove(property_key);
+                data.keys.retain(|key| key != property_key);
+                true
+            }
+            None => true,
+        }
+    }
+
+    pub fn has_property(&self, property_key: &str) -> bool {
+        self.find_property(property_key).is_some()
+    }
+
+    /// Own keys in property order: array indices ascending, then the
+    /// remaining keys in insertion order.
+    pub fn keys(&self) -> Vec<String> {
+        let data = self.data.borrow();
+
+        let mut indices: Vec<(u32, &String)> = data
+            .keys
+            .iter()
+            .filter_map(|key| Some((array_index(key)?, key)))
+            .collect();
+        indices.sort_by_key(|(index, _)| *index);
+
+        indices
+            .into_iter()
+            .map(|(_, key)| key.clone())
+            .chain(
+                data.keys
+                    .iter()
+                    .filter(|key| array_index(key).is_none())
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    pub fn enumerable_keys(&self) -> Vec<String> {
+        self.keys()
+            .into_iter()
+            .filter(|key| {
+                self.get_own_property(key)
+                    .is_some_and(|property| property.enumerable)
+            })
+            .collect()
+    }
+
+    pub fn get_private_element(&self, id: &str) -> Option<PropertyDescriptor> {
+        self.data.borrow().private_elements.get(id).cloned()
+    }
+
+    pub fn define_private_element(&self, id: &str, descriptor: PropertyDescriptor) {
+        self.data
+            .borrow_mut()
+            .private_elements
+            .insert(id.to_string(), descriptor);
+    }
+
+    pub fn has_private_element(&self, id: &str) -> bool {
+        self.data.borrow().private_elements.contains_key(id)
+    }
+
+    pub fn prototype(&self) -> Option<JSObject> {
+        self.data.borrow().prototype.clone()
+    }
+
+    /// Replaces the prototype, refusing links that would make the chain cyclic.
+    pub fn set_prototype(&self, prototype: Option<JSObject>) -> bool {
+        let mut ancestor = prototype.clone();
+
+        while let Some(current) = ancestor {
+            if current.ptr_eq(self) {
+                return false;
+            }
+            ancestor = current.prototype();
+        }
+
+        self.data.borrow_mut().prototype = prototype;
+        true
+    }
+
+    pub fn ptr_eq(&self, other: &JSObject) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
+    /// Identifies the storage the object's clones share.
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.data) as usize
+    }
+
+    /// How many handles share the object's storage.
+    pub fn reference_count(&self) -> usize {
+        Rc::strong_count(&self.data)
+    }
+
+    /// Calls `visit` with every value the object holds on to: property
+    /// values, getters, setters and private elements. The prototype is left
+    /// out.
+    pub fn for_each_value(&self, mut visit: impl FnMut(&JSValueVariant)) {
+        let data = self.data.borrow();
+
+        for descriptor in data
+            .properties
+            .values()
+            .chain(data.private_elements.values())
+        {
+            match &descriptor.kind {
+                PropertyKind::Data { value, .. } => visit(value),
+                PropertyKind::Accessor { get, set } => {
+                    get.iter().chain(set.iter()).for_each(&mut visit)
+                }
+            }
+        }
+    }
+
+    /// Drops every property and private element, for an object nothing can
+    /// reach anymore.
+    pub fn clear(&self) {
+        let mut data = self.data.borrow_mut();
+        let properties = std::mem::take(&mut data.properties);
+        let private_elements = std::mem::take(&mut data.private_elements);
+        data.keys.clear();
+        drop(data);
+
+        drop((properties, private_elements));
+    }
+
+    pub fn new() -> Self {
+        Self {
+            data: Rc::new(RefCell::new(ObjectData::default())),
+        }
+    }
+
+    pub fn from(prototype: Option<JSObject>) -> Self {
+        let object = Self::new();
+        object.data.borrow_mut().prototype = prototype;
+        object
+    }
+
+    /// An `arguments` object, iterated through its `length` and indices.
+    pub fn arguments(prototype: Option<JSObject>) -> Self {
+        let object = Self::from(prototype);
+        object.data.borrow_mut().is_arguments = true;
+        object
+    }
+
+    pub fn is_arguments(&self) -> bool {
+        self.data.borrow().is_arguments
+    }
+}
+
+/// Parses canonical array index keys such as "0" and "42", but not "01".
+pub fn array_index(key: &str) -> Option<u32> {
+    if key.is_empty()
+        || !key.bytes().all(|byte| byte.is_ascii_digit())
+        || (key.len() > 1 && key.starts_with('0'))
+    {
+        return None;
+    }
+
+    key.parse::<u32>().ok().filter(|index| *index != u32::MAX)
+}
+
+// Objects can reference themselves, so only the keys are shown.
+impl fmt::Debug for JSObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSObject")
+            .field("keys", &self.keys())
+            .finish_non_exhaustive()
+    }
+}
@@ -1,105 +1,60 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
 
 use crate::{
-    apis::{
-        features::{
-            assignment::{
-                addition_assignment::AdditionAssignment, decrement_assignment::DecrementAssignment,
-                division_assignment::DivisionAssignment,
-                multiplication_assignment::MultiplicationAssignment,
-            },
-            object_features::ObjectFeatures,
-        },
-        type_variants::js_number::JSNumber,
-    },
+    apis::{features::object_features::ObjectFeatures, type_variants::js_number::JSNumber},
     engine::value_variant::JSValueVariant,
 };
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct JSString {
-    is_primitive: bool,
     properties: HashMap<String, JSValueVariant>,
-    str_value: String,
+    str_value: Rc<str>,
+    /// The UTF-16 code units, encoded once and shared by the string's clones.
+    code_units: Rc<[u16]>,
 }
 
 impl JSString {
-    pub fn is_primitive(&self) -> bool {
-        self.is_primitive
-    }
-
-    pub fn str_value(&self) -> &String {
+    pub fn str_value(&self) -> &str {
         &self.str_value
     }
 
-    pub fn new() -> Self {
-        Self {
-            is_primitive: false,
-            str_value: String::new(),
-            properties: HashMap::from([(
-                String::from("length"),
-                JSValueVariant::JSNumber(JSNumber::new(0f64)),
-            )]),
-        }
-    }
-
     pub fn from(str_value: String) -> Self {
-        let str_len = str_value.len();
+        let code_units: Rc<[u16]> = str_value.encode_utf16().collect();
 
-        let str_self = Self {
-            is_primitive: false,
-            str_value,
+        Self {
+            str_value: Rc::from(str_value),
             properties: HashMap::from([(
                 String::from("length"),
-                JSValueVariant::JSNumber(JSNumber::new(str_len as f64)),
+                JSValueVariant::JSNumber(JSNumber::new(code_units.len() as f64)),
             )]),
-        };
-
-        str_self
+            code_units,
+        }
     }
 
-    pub fn set_str_value(&mut self, str_addition: &String) {
-        self.str_value = str_addition.to_string();
+    pub fn code_units(&self) -> &Rc<[u16]> {
+        &self.code_units
     }
-}
 
-impl AdditionAssignment for JSString {
-    fn addition_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            JSValueVariant::JSNumber(js_num) => {
-                self.str_value += &js_num.number_value().to_string();
-            }
-            JSValueVariant::JSString(string) => {
-                self.str_value += &string.str_value;
-            }
-            _ => {}
-        }
-        if let Some(length) = self.properties.get_mut("length") {
-            *length = JSValueVariant::JSNumber(JSNumber::new(self.str_value.len() as f64));
-        }
+    /// The UTF-16 code unit at `index`, if the string is that long.
+    pub fn code_unit(&self, index: usize) -> Option<u16> {
+        self.code_units.get(index).copied()
     }
 }
 
-impl DecrementAssignment for JSString {
-    fn decrement_assignment(&mut self, value: &JSValueVariant) {
-        match value {
-            _ => {}
-        }
+// The code units only repeat the string, so they are left out.
+impl fmt::Debug for JSString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSString")
+            .field("properties", &self.properties)
+            .field("str_value", &self.str_value)
+            .finish()
     }
 }
 
-impl MultiplicationAssignment for JSString {
-    fn multiplication_assignment(&mut self, value: &JSValueVariant) {}
-}
-
-impl DivisionAssignment for JSString {
-    fn division_assignment(&mut self, value: &JSValueVariant) {}
-}
-
 impl ObjectFeatures for JSString {
-    fn get_property(&self, property_key: &String) -> Option<&JSValueVariant> {
+    fn get_property(&self, property_key: &str) -> Option<&JSValueVariant> {
         self.properties.get(property_key)
     }
-    fn get_property_mut(&mut self, property_key: &String) -> Option<&mut JSValueVariant> {
-        self.properties.get_mut(property_key)
-    }
 }
@@ -1,5 +1,6 @@
-pub mod js_pointer;
 pub mod js_string;
 pub mod js_number;
 pub mod js_bool;
-pub mod js_function;
\ No newline at end of file
+pub mod js_function;
+pub mod js_object;
+pub mod js_array;
\ No newline at end of file
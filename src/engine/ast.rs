@@ -0,0 +1,533 @@
+use crate::engine::position::Position;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub body: Vec<Statement>,
+    /// The names the script declares with `var` or as top-level functions.
+    pub var_names: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Statement {
+    VariableDeclaration(VariableDeclaration),
+    FunctionDeclaration(Rc<FunctionNode>),
+    ClassDeclaration(Rc<ClassNode>),
+    Return {
+        argument: Option<Expression>,
+    },
+    If {
+        test: Expression,
+        consequent: Box<Statement>,
+        alternate: Option<Box<Statement>>,
+    },
+    While {
+        test: Expression,
+        body: Box<Statement>,
+    },
+    DoWhile {
+        body: Box<Statement>,
+        test: Expression,
+    },
+    For {
+        init: Option<ForInit>,
+        test: Option<Expression>,
+        update: Option<Expression>,
+        body: Box<Statement>,
+    },
+    ForIn {
+        left: ForBinding,
+        right: Expression,
+        body: Box<Statement>,
+    },
+    ForOf {
+        left: ForBinding,
+        right: Expression,
+        body: Box<Statement>,
+    },
+    Break {
+        label: Option<String>,
+    },
+    Continue {
+        label: Option<String>,
+    },
+    Switch {
+        discriminant: Expression,
+        cases: Vec<SwitchCase>,
+    },
+    Labeled {
+        label: String,
+        body: Box<Statement>,
+    },
+    Block {
+        body: Vec<Statement>,
+    },
+    Throw {
+        argument: Expression,
+        position: Position,
+    },
+    Try {
+        block: Vec<Statement>,
+        handler: Option<CatchClause>,
+        finalizer: Option<Vec<Statement>>,
+    },
+    Expression {
+        expression: Expression,
+    },
+    Empty,
+}
+
+/// A `catch` block. The binding may be left out, as in `catch { ... }`.
+#[derive(Debug, Clone)]
+pub struct CatchClause {
+    pub param: Option<Pattern>,
+    pub body: Vec<Statement>,
+}
+
+/// A clause of a `switch` statement. `default` has no test.
+#[derive(Debug, Clone)]
+pub struct SwitchCase {
+    pub test: Option<Expression>,
+    pub consequent: Vec<Statement>,
+}
+
+/// What a for-in or for-of loop binds each value to: a declaration, or a
+/// pattern that is assigned to.
+#[derive(Debug, Clone)]
+pub enum ForBinding {
+    Declaration { kind: VariableKind, target: Pattern },
+    Pattern(Pattern),
+}
+
+#[derive(Debug, Clone)]
+pub enum ForInit {
+    VariableDeclaration(VariableDeclaration),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariableDeclaration {
+    pub kind: VariableKind,
+    pub declarations: Vec<VariableDeclarator>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariableDeclarator {
+    pub target: Pattern,
+    pub init: Option<Expression>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionNode {
+    pub name: Option<String>,
+    pub params: Vec<Pattern>,
+    pub body: Vec<Statement>,
+    /// The names the body declares with `var` or as top-level functions,
+    /// which are hoisted to the function's scope.
+    pub var_names: Vec<String>,
+}
+
+impl FunctionNode {
+    /// The number of parameters before the first one with a default or the
+    /// rest parameter, which is the function's `length`.
+    pub fn length(&self) -> usize {
+        self.params
+            .iter()
+            .take_while(|param| !matches!(param, Pattern::Default { .. } | Pattern::Rest(_)))
+            .count()
+    }
+}
+
+/// A class, whose constructor is always present: when the class leaves it
+/// out, the parser supplies the default one.
+#[derive(Debug, Clone)]
+pub struct ClassNode {
+    pub name: Option<String>,
+    pub super_class: Option<Expression>,
+    pub constructor: Rc<FunctionNode>,
+    pub members: Vec<ClassMember>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ClassMember {
+    Method {
+        key: ClassKey,
+        kind: MethodKind,
+        function: Rc<FunctionNode>,
+        is_static: bool,
+    },
+    Field {
+        key: ClassKey,
+        value: Option<Expression>,
+        is_static: bool,
+    },
+    /// `static { ... }`, run like a method of the class with no parameters.
+    StaticBlock(Rc<FunctionNode>),
+}
+
+#[derive(Debug, Clone)]
+pub enum ClassKey {
+    Property(PropertyKey),
+    /// A private name such as `#count`, without the `#`.
+    Private(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MethodKind {
+    Method,
+    Getter,
+    Setter,
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Identifier(String),
+    /// `[a, , ...rest]`, where a hole is `None`.
+    Array(Vec<Option<Pattern>>),
+    /// `{ a, b: c, ...rest }`.
+    Object {
+        properties: Vec<PatternProperty>,
+        rest: Option<Box<Pattern>>,
+    },
+    /// A member expression assigned to by a destructuring assignment, as in
+    /// `[obj.a, obj.b] = list`.
+    Expression(Box<Expression>),
+    /// A pattern whose value is `default` when it would be `undefined`.
+    Default {
+        target: Box<Pattern>,
+        default: Box<Expression>,
+    },
+    /// A rest parameter, which collects the remaining arguments.
+    Rest(Box<Pattern>),
+}
+
+impl Pattern {
+    /// The names the pattern binds.
+    pub fn bound_names(&self) -> Vec<&str> {
+        match self {
+            Pattern::Identifier(name) => vec![name],
+            Pattern::Array(elements) => elements
+                .iter()
+                .flatten()
+                .flat_map(Pattern::bound_names)
+                .collect(),
+            Pattern::Object { properties, rest } => properties
+                .iter()
+                .map(|property| &property.value)
+                .chain(rest.as_deref())
+                .flat_map(Pattern::bound_names)
+                .collect(),
+            Pattern::Expression(_) => vec![],
+            Pattern::Default { target, .. } | Pattern::Rest(target) => target.bound_names(),
+        }
+    }
+
+    /// Whether binding the pattern can evaluate an expression, through a
+    /// default or a computed key.
+    pub fn has_expressions(&self) -> bool {
+        match self {
+            Pattern::Identifier(_) => false,
+            Pattern::Array(elements) => elements.iter().flatten().any(Pattern::has_expressions),
+            Pattern::Object { properties, rest } => {
+                properties.iter().any(|property| {
+                    matches!(property.key, PropertyKey::Computed(_))
+                        || property.value.has_expressions()
+                }) || rest.as_deref().is_some_and(Pattern::has_expressions)
+            }
+            Pattern::Expression(_) | Pattern::Default { .. } => true,
+            Pattern::Rest(target) => target.has_expressions(),
+        }
+    }
+}
+
+/// A property of an object pattern: the key to read and what to bind its
+/// value to. In `{ a }` the value is the identifier `a` itself.
+#[derive(Debug, Clone)]
+pub struct PatternProperty {
+    pub key: PropertyKey,
+    pub value: Pattern,
+}
+
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Number(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    Undefined,
+    TemplateLiteral(Vec<TemplatePart>),
+    Identifier {
+        name: String,
+        position: Position,
+    },
+    Function(Rc<FunctionNode>),
+    ArrowFunction(Rc<FunctionNode>),
+    Class(Rc<ClassNode>),
+    This,
+    /// `new.target`.
+    NewTarget,
+    Object(Vec<ObjectProperty>),
+    Array(Vec<ArrayElement>),
+    // Expressions that can fail at runtime record where they are, which
+    // errors raised while evaluating them report.
+    /// `object.name` or `object[key]`, or with `?.` when `optional`.
+    Member {
+        object: Box<Expression>,
+        property: MemberProperty,
+        optional: bool,
+        position: Position,
+    },
+    /// `callee(...)`, or `callee?.(...)` when `optional`.
+    Call {
+        callee: Box<Expression>,
+        arguments: Vec<Argument>,
+        optional: bool,
+        position: Position,
+    },
+    /// `#name in object`, which checks for a private name without reading it.
+    PrivateIn {
+        name: String,
+        object: Box<Expression>,
+        position: Position,
+    },
+    /// A chain of member accesses and calls with at least one `?.` in it,
+    /// all of which a `?.` that meets null or undefined skips.
+    OptionalChain(Box<Expression>),
+    New {
+        callee: Box<Expression>,
+        arguments: Vec<Argument>,
+        position: Position,
+    },
+    /// `super(...)` in a derived class constructor.
+    SuperCall {
+        arguments: Vec<Argument>,
+        position: Position,
+    },
+    /// `super.name` and `super[key]` in a class method.
+    SuperMember {
+        property: MemberProperty,
+        position: Position,
+    },
+    Unary {
+        operator: UnaryOperator,
+        argument: Box<Expression>,
+    },
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    Logical {
+        operator: LogicalOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// `test ? consequent : alternate`.
+    Conditional {
+        test: Box<Expression>,
+        consequent: Box<Expression>,
+        alternate: Box<Expression>,
+    },
+    Sequence(Vec<Expression>),
+    Assignment {
+        operator: AssignmentOperator,
+        target: Box<Expression>,
+        value: Box<Expression>,
+        position: Position,
+    },
+    /// `[a, b] = [b, a]` and `({ a, b } = object)`.
+    DestructuringAssignment {
+        target: Pattern,
+        value: Box<Expression>,
+        position: Position,
+    },
+    Update {
+        operator: UpdateOperator,
+        prefix: bool,
+        target: Box<Expression>,
+        position: Position,
+    },
+}
+
+impl Expression {
+    /// Where the expression is in the source, for those that record it.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Expression::Identifier { position, .. }
+            | Expression::Member { position, .. }
+            | Expression::Call { position, .. }
+            | Expression::New { position, .. }
+            | Expression::SuperCall { position, .. }
+            | Expression::SuperMember { position, .. }
+            | Expression::Assignment { position, .. }
+            | Expression::DestructuringAssignment { position, .. }
+            | Expression::Update { position, .. } => Some(*position),
+            Expression::OptionalChain(chain) => chain.position(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ObjectProperty {
+    Property {
+        key: PropertyKey,
+        value: Expression,
+    },
+    /// A method, or a `get`/`set` accessor function.
+    Method {
+        key: PropertyKey,
+        kind: MethodKind,
+        function: Rc<FunctionNode>,
+    },
+    /// `__proto__: value`, which sets the prototype instead of a property.
+    Prototype(Expression),
+    Spread(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub enum ArrayElement {
+    Hole,
+    Expression(Expression),
+    Spread(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub enum Argument {
+    Expression(Expression),
+    Spread(Expression),
+}
+
+#[derive(Debug, Clone)]
+pub enum PropertyKey {
+    Static(String),
+    Computed(Box<Expression>),
+}
+
+#[derive(Debug, Clone)]
+pub enum MemberProperty {
+    Identifier(String),
+    Computed(Box<Expression>),
+    /// `object.#name`, without the `#`.
+    Private(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum TemplatePart {
+    String(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOperator {
+    Minus,
+    Plus,
+    Not,
+    BitwiseNot,
+    Typeof,
+    Void,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOperator {
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    Modulo,
+    Exponent,
+    Equal,
+    NotEqual,
+    StrictEqual,
+    StrictNotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    InstanceOf,
+    In,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogicalOperator {
+    And,
+    Or,
+    Nullish,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssignmentOperator {
+    Assign,
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    Modulo,
+    Exponent,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    LogicalAnd,
+    LogicalOr,
+    Nullish,
+}
+
+impl AssignmentOperator {
+    /// The logical operator that decides whether a logical assignment
+    /// stores anything at all.
+    pub fn logical_operator(&self) -> Option<LogicalOperator> {
+        match self {
+            AssignmentOperator::LogicalAnd => Some(LogicalOperator::And),
+            AssignmentOperator::LogicalOr => Some(LogicalOperator::Or),
+            AssignmentOperator::Nullish => Some(LogicalOperator::Nullish),
+            _ => None,
+        }
+    }
+
+    /// The binary operator a compound assignment applies before storing.
+    pub fn binary_operator(&self) -> Option<BinaryOperator> {
+        let operator = match self {
+            AssignmentOperator::Assign
+            | AssignmentOperator::LogicalAnd
+            | AssignmentOperator::LogicalOr
+            | AssignmentOperator::Nullish => return None,
+            AssignmentOperator::Addition => BinaryOperator::Addition,
+            AssignmentOperator::Subtraction => BinaryOperator::Subtraction,
+            AssignmentOperator::Multiplication => BinaryOperator::Multiplication,
+            AssignmentOperator::Division => BinaryOperator::Division,
+            AssignmentOperator::Modulo => BinaryOperator::Modulo,
+            AssignmentOperator::Exponent => BinaryOperator::Exponent,
+            AssignmentOperator::BitwiseAnd => BinaryOperator::BitwiseAnd,
+            AssignmentOperator::BitwiseOr => BinaryOperator::BitwiseOr,
+            AssignmentOperator::BitwiseXor => BinaryOperator::BitwiseXor,
+            AssignmentOperator::LeftShift => BinaryOperator::LeftShift,
+            AssignmentOperator::RightShift => BinaryOperator::RightShift,
+            AssignmentOperator::UnsignedRightShift => BinaryOperator::UnsignedRightShift,
+        };
+
+        Some(operator)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateOperator {
+    Increment,
+    Decrement,
+}
@@ -0,0 +1,270 @@
+use crate::apis::type_variants::js_array::JSArray;
+use crate::apis::type_variants::js_function::{FunctionKind, JSFunction};
+use crate::apis::type_variants::js_object::JSObject;
+use crate::engine::value_variant::JSValueVariant;
+use crate::scope::Scope;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
+
+/// Something shared through an `Rc` that can take part in a cycle.
+#[derive(Clone)]
+enum Node {
+    Scope(Rc<RefCell<Scope>>),
+    Object(JSObject),
+    Array(JSArray),
+}
+
+struct Entry {
+    node: Node,
+    /// Handles on the node held by other nodes of the graph.
+    internal: usize,
+    /// Handles on the node when it was found, before the collector made any.
+    total: usize,
+    edges: Vec<usize>,
+}
+
+/// How many scopes left behind by closures wait before they are collected,
+/// at the least.
+const MIN_BATCH: usize = 64;
+
+/// How much of the heap a collection may visit for each scope it waited
+/// for. The batch grows with the heap the last collection visited, which
+/// keeps what collecting costs in proportion to the scopes released.
+const VISITS_PER_SCOPE: usize = 1024;
+
+/// Frees the cycles closures leave behind. Closures hold the scope they were
+/// created in, which usually holds them in turn, so reference counting alone
+/// never frees either. Scopes a closure still holds when execution leaves
+/// them are collected in batches.
+pub struct Collector {
+    released: Vec<Weak<RefCell<Scope>>>,
+    batch: usize,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            released: vec![],
+            batch: MIN_BATCH,
+        }
+    }
+
+    /// Takes a scope execution has left. Returns straight away, when no
+    /// closure holds on to the scope.
+    pub fn release(&mut self, scope: Rc<RefCell<Scope>>) {
+        if Rc::strong_count(&scope) == 1 {
+            return;
+        }
+
+        self.released.push(Rc::downgrade(&scope));
+        drop(scope);
+
+        if self.released.len() >= self.batch {
+            self.collect();
+        }
+    }
+
+    /// Frees the cycles of the scopes released since the last collection
+    /// that are still around.
+    pub fn collect(&mut self) {
+        let roots: Vec<Rc<RefCell<Scope>>> = self
+            .released
+            .drain(..)
+            .filter_map(|scope| scope.upgrade())
+            .collect();
+
+        let mut graph = Graph {
+            roots: roots.iter().map(|root| Rc::as_ptr(root) as usize).collect(),
+            nested: HashMap::new(),
+            entries: HashMap::new(),
+            pending: vec![],
+            visits: 0,
+        };
+
+        // The only handle the collector holds on a root is the one in its node.
+        for root in roots {
+            graph.discover(Node::Scope(root), 1);
+        }
+        while let Some(id) = graph.pending.pop() {
+            graph.scan(id);
+        }
+
+        self.batch = MIN_BATCH.max(graph.visits / VISITS_PER_SCOPE);
+        graph.sweep();
+    }
+}
+
+/// The part of the heap reachable from the scopes being collected. Nodes
+/// with more handles than the graph accounts for are held from outside, and
+/// whatever they cannot reach is garbage.
+///
+/// Scopes other than the roots and those nested in them are not part of the
+/// graph, nor are prototypes. Handles from them count as outside ones, which
+/// only ever keeps more alive.
+struct Graph {
+    roots: HashSet<usize>,
+    /// Whether each scope seen so far is a root or nested in one.
+    nested: HashMap<usize, bool>,
+    entries: HashMap<usize, Entry>,
+    pending: Vec<usize>,
+    /// How many handles and values the graph has looked at.
+    visits: usize,
+}
+
+impl Graph {
+    /// Adds `node` to the graph the first time it is seen and returns its id.
+    /// `handles` is how many of its handles the collector holds.
+    fn discover(&mut self, node: Node, handles: usize) -> usize {
+        let (id, total) = match &node {
+            Node::Scope(scope) => (Rc::as_ptr(scope) as usize, Rc::strong_count(scope)),
+            Node::Object(object) => (object.id(), object.reference_count()),
+            Node::Array(array) => (array.id(), array.reference_count()),
+        };
+
+        self.entries.entry(id).or_insert_with(|| {
+            self.pending.push(id);
+            Entry {
+                node,
+                internal: 0,
+                total: total - handles,
+                edges: vec![],
+            }
+        });
+
+        id
+    }
+
+    /// Records the handles `value` holds as edges from the node `from`.
+    fn visit(&mut self, from: usize, value: &JSValueVariant) {
+        self.visits += 1;
+
+        match value {
+            JSValueVariant::JSObject(object) => self.link(from, Node::Object(object.clone()), 1),
+            JSValueVariant::JSArray(array) => {
+                self.link(from, Node::Array(array.clone()), 1);
+                // The array node holds a handle on its object as well.
+                self.link(from, Node::Object(array.object().clone()), 2);
+            }
+            JSValueVariant::JSFunction(function) => self.visit_function(from, function),
+            _ => {}
+        }
+    }
+
+    fn visit_function(&mut self, from: usize, function: &JSFunction) {
+        self.link(from, Node::Object(function.object().clone()), 1);
+
+        match function.kind() {
+            FunctionKind::Script { scope, .. }
+            | FunctionKind::Arrow { scope, .. }
+            | FunctionKind::Class { scope, .. } => {
+                if self.is_nested(scope) {
+                    self.link(from, Node::Scope(Rc::clone(scope)), 1);
+                }
+            }
+            // Clones of a bound function clone what it binds.
+            FunctionKind::Bound {
+                target,
+                this,
+                arguments,
+                ..
+            } => {
+                self.visit_function(from, target);
+                self.visit(from, this);
+                for argument in arguments {
+                    self.visit(from, argument);
+                }
+            }
+            FunctionKind::Native { .. } => {}
+        }
+    }
+
+    /// Counts a handle on `node` held by the node `from`.
+    fn link(&mut self, from: usize, node: Node, handles: usize) {
+        let id = self.discover(node, handles);
+
+        self.entries.get_mut(&id).unwrap().internal += 1;
+        self.entries.get_mut(&from).unwrap().edges.push(id);
+    }
+
+    /// Visits the handles the node holds.
+    fn scan(&mut self, id: usize) {
+        match self.entries[&id].node.clone() {
+            Node::Scope(scope) => {
+                let scope = scope.borrow();
+
+                if let Some(parent) = scope.parent()
+                    && self.is_nested(parent)
+                {
+                    self.link(id, Node::Scope(Rc::clone(parent)), 1);
+                }
+                for state in scope.state().values() {
+                    self.visit(id, state.value());
+                }
+            }
+            Node::Object(object) => object.for_each_value(|value| self.visit(id, value)),
+            Node::Array(array) => array.for_each_element(|value| self.visit(id, value)),
+        }
+    }
+
+    /// Whether `scope` is one of the roots or nested in one.
+    fn is_nested(&mut self, scope: &Rc<RefCell<Scope>>) -> bool {
+        let mut path: Vec<usize> = vec![];
+        let mut scope = Rc::clone(scope);
+
+        let nested = loop {
+            let id = Rc::as_ptr(&scope) as usize;
+            if let Some(nested) = self.nested.get(&id) {
+                break *nested;
+            }
+            if self.roots.contains(&id) {
+                break true;
+            }
+            path.push(id);
+
+            let parent = match scope.borrow().parent() {
+                Some(parent) => Rc::clone(parent),
+                None => break false,
+            };
+            scope = parent;
+        };
+
+        for id in path {
+            self.nested.insert(id, nested);
+        }
+
+        nested
+    }
+
+    /// Clears every node that no outside handle can reach.
+    fn sweep(self) {
+        let mut reachable: Vec<usize> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.total > entry.internal)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut live = HashSet::new();
+
+        while let Some(id) = reachable.pop() {
+            if live.insert(id) {
+                reachable.extend(self.entries[&id].edges.iter().copied());
+            }
+        }
+
+        for (id, entry) in self.entries {
+            if live.contains(&id) {
+                continue;
+            }
+
+            match entry.node {
+                Node::Scope(scope) => {
+                    let state = scope.borrow_mut().clear();
+                    drop(state);
+                }
+                Node::Object(object) => object.clear(),
+                Node::Array(array) => array.clear(),
+            }
+        }
+    }
+}
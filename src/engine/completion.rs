@@ -0,0 +1,11 @@
+use crate::engine::value_variant::JSValueVariant;
+
+/// How a statement finished executing. Anything other than `Normal` unwinds
+/// the enclosing statement lists until something consumes it.
+#[derive(Debug, Clone)]
+pub enum Completion {
+    Normal,
+    Return(JSValueVariant),
+    Break(Option<String>),
+    Continue(Option<String>),
+}
@@ -1,536 +1,3185 @@
-use crate::apis::features::assignment::addition_assignment::AdditionAssignment;
-use crate::apis::features::assignment::decrement_assignment::DecrementAssignment;
-use crate::apis::features::assignment::division_assignment::DivisionAssignment;
-use crate::apis::features::assignment::multiplication_assignment::MultiplicationAssignment;
 use crate::apis::features::object_features::ObjectFeatures;
-use crate::apis::type_variants::js_function::JSFunction;
+use crate::apis::stdlib;
+use crate::apis::type_variants::js_array::JSArray;
+use crate::apis::type_variants::js_bool::JSBool;
+use crate::apis::type_variants::js_function::{
+    ClassDefinition, FunctionKind, InstanceElement, JSFunction,
+};
+use crate::apis::type_variants::js_object::{
+    JSObject, ObjectKey, PropertyDescriptor, PropertyKind, array_index,
+};
+use crate::engine::ast::*;
+use crate::engine::collector::Collector;
+use crate::engine::completion::Completion;
+use crate::engine::operations;
+use crate::engine::position::Position;
 use crate::engine::stack::Stack;
 use crate::engine::state::State;
+use crate::errors::EndiumError;
 use crate::{
     apis::type_variants::{js_number::JSNumber, js_string::JSString},
-    engine::{tokens::Token, value_variant::JSValueVariant},
+    engine::value_variant::JSValueVariant,
     scope::Scope,
 };
-use std::error::Error;
-use std::os;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Deepest JS call nesting before a RangeError is raised, well within the
+/// native stack the CLI reserves for the interpretter thread.
+const MAX_CALL_STACK_SIZE: usize = 10_000;
+
+// Bindings the engine keeps in scopes for itself. Apart from `this`, none of
+// them is an identifier, so scripts cannot refer to them by name.
+/// The `this` of a call, bound in every call to a function that is not an
+/// arrow function. It is uninitialized in a derived class constructor until
+/// `super(...)` returns.
+const THIS: &str = "this";
+const NEW_TARGET: &str = "new.target";
+/// The object whose prototype `super.name` looks `name` up on: a class's
+/// prototype object for its methods, or the class itself for static ones.
+const HOME_OBJECT: &str = "[[HomeObject]]";
+/// The constructor a derived class calls with `super(...)`.
+const SUPER_CONSTRUCTOR: &str = "[[SuperConstructor]]";
+/// The class whose constructor is running, which initializes the instance
+/// fields once `super(...)` returns.
+const CLASS_CONSTRUCTOR: &str = "[[ClassConstructor]]";
+
+/// A script function call in progress: the scope to return to, the name the
+/// call is shown under in stack traces and where it was called from.
+struct CallFrame {
+    caller_scope: Rc<RefCell<Scope>>,
+    function_name: String,
+    call_position: Position,
+}
+
+/// How `bind_pattern` binds the names in a pattern.
+#[derive(Clone, Copy)]
+enum Binding {
+    /// Creates the bindings in the current scope, as `let`, `const` and
+    /// parameters do.
+    Initialize { mutable: bool },
+    /// Assigns to existing bindings, as `var` declarations, which were
+    /// hoisted, and destructuring assignments do.
+    Assign,
+}
+
+/// What a member expression reads and writes: the key, the object it is
+/// looked up on and the `this` accessors run with, which for `super.name` is
+/// not the object the lookup starts from.
+struct MemberReference {
+    object: JSValueVariant,
+    key: ObjectKey,
+    receiver: JSValueVariant,
+}
+
+/// A static field or block, run in order once the rest of its class is
+/// defined.
+enum StaticElement<'a> {
+    Field {
+        key: ObjectKey,
+        value: Option<&'a Expression>,
+    },
+    Block(&'a Rc<FunctionNode>),
+}
+
+impl Binding {
+    fn from(kind: VariableKind) -> Self {
+        match kind {
+            VariableKind::Var => Binding::Assign,
+            VariableKind::Let => Binding::Initialize { mutable: true },
+            VariableKind::Const => Binding::Initialize { mutable: false },
+        }
+    }
+}
 
 pub struct Interpretter {
-    scope: Scope,
-    instructions: Vec<Token>,
-    interpretted_value: JSValueVariant,
-    position: usize,
-    is_running: bool,
+    scope: Rc<RefCell<Scope>>,
+    call_stack: Stack<CallFrame>,
+    /// The position of the call or throw being made, which stack traces
+    /// start from.
+    position: Position,
+    object_prototype: JSObject,
+    array_prototype: JSObject,
+    function_prototype: JSObject,
+    string_prototype: JSObject,
+    /// `Error.prototype` and the native error prototypes, by constructor name.
+    error_prototypes: HashMap<String, JSObject>,
+    /// How many private names have been created, which numbers the next.
+    private_name_count: usize,
+    /// The arrays whose elements are being joined, innermost last, so that an
+    /// array found inside itself joins to an empty string.
+    joining: Vec<usize>,
+    collector: Collector,
 }
 
 impl Interpretter {
-    pub fn execute(&mut self) -> Result<(), Box<dyn Error>> {
-        let scope_instructions = self.scope.instructions().clone();
+    pub fn execute(&mut self, program: &Program) -> Result<(), EndiumError> {
+        self.hoist_var_names(&program.var_names);
+        self.declare_lexical(&program.body);
 
-        while let Some(token) = scope_instructions.get(self.position) {
-            if !self.is_running {
-                break;
-            }
+        if let Err(error) = self.execute_statements(&program.body) {
+            return Err(self.exception(error));
+        }
 
-            let _ = match token {
-                Token::Return => self.handle_return(),
+        Ok(())
+    }
+
+    pub fn execute_statements(
+        &mut self,
+        statements: &[Statement],
+    ) -> Result<Completion, EndiumError> {
+        for statement in statements {
+            let completion = self.execute_statement(statement)?;
 
-                Token::Const => self.handle_const(),
+            if !matches!(completion, Completion::Normal) {
+                return Ok(completion);
+            }
+        }
 
-                Token::Let => self.handle_let(),
+        Ok(Completion::Normal)
+    }
 
-                Token::String(s) => self.handle_string(s),
+    /// Runs the statements of a block in a scope of their own, when they
+    /// declare anything scoped to it.
+    fn execute_block(&mut self, statements: &[Statement]) -> Result<Completion, EndiumError> {
+        if !Self::has_lexical_declarations(statements) {
+            return self.execute_statements(statements);
+        }
 
-                Token::TemplateString(t_s) => self.handle_template_string(t_s),
+        let block_scope = Scope::new(Some(Rc::clone(&self.scope)));
+        let outer_scope = std::mem::replace(&mut self.scope, Rc::new(RefCell::new(block_scope)));
 
-                Token::Number(n) => self.handle_number(n),
+        self.declare_lexical(statements);
+        let completion = self.execute_statements(statements);
+        self.leave_scope(outer_scope);
 
-                Token::Function => self.handle_function(),
+        completion
+    }
 
-                Token::Identifier(identifier) => self.handle_identifier(identifier),
+    /// Goes back to `outer_scope` from a scope of its own, freeing whatever
+    /// the closures created in the scope left behind.
+    fn leave_scope(&mut self, outer_scope: Rc<RefCell<Scope>>) {
+        let scope = std::mem::replace(&mut self.scope, outer_scope);
+        self.collector.release(scope);
+    }
 
-                Token::For => self.handle_for(),
+    fn has_lexical_declarations(statements: &[Statement]) -> bool {
+        statements.iter().any(|statement| match statement {
+            Statement::VariableDeclaration(declaration) => declaration.kind != VariableKind::Var,
+            Statement::FunctionDeclaration(_) | Statement::ClassDeclaration(_) => true,
+            _ => false,
+        })
+    }
 
-                Token::Null => {
-                    self.position += 1;
-                    self.interpretted_value = JSValueVariant::Null;
+    /// Binds the declarations scoped to a block or function body in the
+    /// current scope before any of its statements run. Functions are created
+    /// up front, while `let` and `const` bindings stay uninitialized until
+    /// their declaration runs.
+    fn declare_lexical(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            match statement {
+                Statement::VariableDeclaration(declaration)
+                    if declaration.kind != VariableKind::Var =>
+                {
+                    self.declare_uninitialized(declaration)
                 }
-
-                Token::Undefined => {
-                    self.position += 1;
-                    self.interpretted_value = JSValueVariant::Undefined;
+                Statement::FunctionDeclaration(function) => {
+                    self.handle_function_declaration(function)
                 }
-
-                // Token::Eof => break,
-                _ => {
-                    self.position += 1;
+                Statement::ClassDeclaration(class) => {
+                    self.scope.borrow_mut().insert_state(
+                        class.name.clone().unwrap_or_default(),
+                        State::uninitialized(true),
+                    );
                 }
-            };
+                _ => {}
+            }
         }
-        Ok(())
     }
 
-    pub fn stop(&mut self) {
-        self.is_running = false;
-    }
-
-    pub fn handle_string(&mut self, s: &String) {
-        let mut value: JSValueVariant = JSValueVariant::JSString(JSString::from(s.clone()));
-        while let Some(next_operator) = self.instructions.get(self.position + 1) {
-            match next_operator {
-                Token::Dot => {
-                    self.position += 1;
-                    continue;
-                }
-                Token::Identifier(identifier) => {
-                    self.position += 1;
-                    if let Some(next_operator) = self.instructions.get(self.position + 1) {
-                        match next_operator {
-                            Token::Dot => {
-                                match value {
-                                    JSValueVariant::JSString(js_string) => {
-                                        value = js_string.get_property(identifier).unwrap().clone();
-                                    }
-                                    _ => {}
-                                }
-                                self.position += 1;
-                                continue;
-                            }
-                            _ => {
-                                self.interpretted_value = value.clone();
-                                self.position += 1;
-                                break;
-                            }
-                        }
-                    } else {
-                        match value {
-                            JSValueVariant::JSString(js_string) => {
-                                value = js_string.get_property(identifier).unwrap().clone();
-                            }
-                            _ => {}
-                        }
-                        self.interpretted_value = value.clone();
-                        self.position += 1;
-                    }
-                }
-                _ => {
-                    self.interpretted_value = value.clone();
-                    self.position += 1;
-                    break;
+    fn declare_uninitialized(&mut self, declaration: &VariableDeclaration) {
+        let mutable = declaration.kind != VariableKind::Const;
+
+        for declarator in declaration.declarations.iter() {
+            for name in declarator.target.bound_names() {
+                self.scope
+                    .borrow_mut()
+                    .insert_state(name.to_string(), State::uninitialized(mutable));
+            }
+        }
+    }
+
+    /// Binds the `var` names of a function body or script to `undefined` in
+    /// the current scope, unless a parameter already binds them.
+    fn hoist_var_names(&mut self, var_names: &[String]) {
+        let mut scope = self.scope.borrow_mut();
+
+        for name in var_names {
+            if scope.get_state(name).is_none() {
+                scope.insert_state(
+                    name.to_string(),
+                    State::new(JSValueVariant::Undefined, true),
+                );
+            }
+        }
+    }
+
+    fn execute_statement(&mut self, statement: &Statement) -> Result<Completion, EndiumError> {
+        match statement {
+            Statement::VariableDeclaration(declaration) => {
+                self.handle_variable_declaration(declaration)?
+            }
+            // Functions are created when their scope is entered.
+            Statement::FunctionDeclaration(_) => {}
+            Statement::ClassDeclaration(class) => {
+                let value = self.handle_class(class)?;
+                self.scope
+                    .borrow_mut()
+                    .initialize(class.name.as_deref().unwrap_or_default(), value);
+            }
+            Statement::Return { argument } => return self.handle_return(argument.as_ref()),
+            Statement::If {
+                test,
+                consequent,
+                alternate,
+            } => {
+                let test = self.evaluate_expression(test)?;
+
+                if operations::to_boolean(&test) {
+                    return self.execute_statement(consequent);
+                } else if let Some(alternate) = alternate {
+                    return self.execute_statement(alternate);
                 }
             }
+            Statement::While { .. }
+            | Statement::DoWhile { .. }
+            | Statement::For { .. }
+            | Statement::ForIn { .. }
+            | Statement::ForOf { .. } => {
+                return self.handle_loop(statement, &[]);
+            }
+            Statement::Switch {
+                discriminant,
+                cases,
+            } => return self.handle_switch(discriminant, cases),
+            Statement::Break { label } => return Ok(Completion::Break(label.clone())),
+            Statement::Continue { label } => return Ok(Completion::Continue(label.clone())),
+            Statement::Labeled { .. } => return self.handle_labeled(statement),
+            Statement::Block { body } => return self.execute_block(body),
+            Statement::Throw { argument, position } => {
+                let value = self.evaluate_expression(argument)?;
+                return Err(EndiumError::thrown(value).at(*position));
+            }
+            Statement::Try {
+                block,
+                handler,
+                finalizer,
+            } => return self.handle_try(block, handler.as_ref(), finalizer.as_deref()),
+            Statement::Expression { expression } => {
+                self.evaluate_expression(expression)?;
+            }
+            Statement::Empty => {}
+        }
+
+        Ok(Completion::Normal)
+    }
+
+    fn handle_labeled(&mut self, statement: &Statement) -> Result<Completion, EndiumError> {
+        let mut labels = vec![];
+        let mut body = statement;
+
+        while let Statement::Labeled { label, body: inner } = body {
+            labels.push(label.clone());
+            body = inner;
+        }
+
+        let completion = match body {
+            Statement::While { .. }
+            | Statement::DoWhile { .. }
+            | Statement::For { .. }
+            | Statement::ForIn { .. }
+            | Statement::ForOf { .. } => self.handle_loop(body, &labels)?,
+            _ => self.execute_statement(body)?,
+        };
+
+        match completion {
+            Completion::Break(Some(label)) if labels.contains(&label) => Ok(Completion::Normal),
+            completion => Ok(completion),
         }
     }
-    pub fn handle_for(&mut self) {}
 
-    pub fn handle_return(&mut self) {
-        self.position += 1; // Skip return keyword
+    /// Runs a switch statement. The first clause whose test is strictly equal
+    /// to the discriminant is entered, or else the `default` clause, and the
+    /// clauses after it run too until a `break`. All clauses share one scope.
+    fn handle_switch(
+        &mut self,
+        discriminant: &Expression,
+        cases: &[SwitchCase],
+    ) -> Result<Completion, EndiumError> {
+        let discriminant = self.evaluate_expression(discriminant)?;
+
+        let has_lexical_declarations = cases
+            .iter()
+            .any(|case| Self::has_lexical_declarations(&case.consequent));
 
-        if let Some(next_keyword) = self.instructions.get(self.position + 1) {
-            let value_tokens = self.value_collector(vec![Token::Comma, Token::Newline]);
-            let parent_scope = self.scope.clone();
+        let outer_scope = Rc::clone(&self.scope);
+        if has_lexical_declarations {
+            self.scope = Rc::new(RefCell::new(Scope::new(Some(Rc::clone(&outer_scope)))));
 
-            let value_scope = Scope::new(Some(parent_scope), value_tokens);
-            let mut value_interpretter = Interpretter::new(None, Some(value_scope));
-            value_interpretter.execute().unwrap();
-            let value = value_interpretter.interpretted_value;
+            for case in cases {
+                self.declare_lexical(&case.consequent);
+            }
+        }
 
-            self.interpretted_value = value;
+        let completion = self.execute_switch_cases(&discriminant, cases);
+        if has_lexical_declarations {
+            self.leave_scope(outer_scope);
         }
 
-        self.stop();
+        match completion? {
+            Completion::Break(None) => Ok(Completion::Normal),
+            completion => Ok(completion),
+        }
     }
 
-    pub fn handle_function(&mut self) {
-        self.position += 1; // Skip `function`
+    fn execute_switch_cases(
+        &mut self,
+        discriminant: &JSValueVariant,
+        cases: &[SwitchCase],
+    ) -> Result<Completion, EndiumError> {
+        let mut start = None;
+
+        for (index, case) in cases.iter().enumerate() {
+            if let Some(test) = &case.test
+                && operations::strict_equals(discriminant, &self.evaluate_expression(test)?)
+            {
+                start = Some(index);
+                break;
+            }
+        }
 
-        let fn_name = match self.instructions.get(self.position).unwrap() {
-            Token::Identifier(name) => name,
-            _ => panic!("Function name must be an identifier."),
+        let Some(start) = start.or_else(|| cases.iter().position(|case| case.test.is_none()))
+        else {
+            return Ok(Completion::Normal);
         };
 
-        self.position += 2; // Skip identifier name and left paren.
+        for case in &cases[start..] {
+            let completion = self.execute_statements(&case.consequent)?;
 
-        let mut arg_names: Vec<String> = vec![];
+            if !matches!(completion, Completion::Normal) {
+                return Ok(completion);
+            }
+        }
 
-        while let Some(v_token) = self.instructions.get(self.position) {
-            let v_token = v_token.clone();
+        Ok(Completion::Normal)
+    }
 
-            match v_token {
-                Token::Identifier(identifier) => {
-                    self.position += 1;
-                    arg_names.push(identifier);
-                }
-                Token::RightParen => {
-                    self.position += 1;
-                    break;
-                }
-                _ => {
-                    self.position += 1;
-                    continue;
-                }
-            }
+    /// Runs a try statement. A `finally` block always runs, and any abrupt
+    /// completion of its own replaces that of the try and catch blocks.
+    fn handle_try(
+        &mut self,
+        block: &[Statement],
+        handler: Option<&CatchClause>,
+        finalizer: Option<&[Statement]>,
+    ) -> Result<Completion, EndiumError> {
+        let mut result = self.execute_block(block);
+
+        if let Some(handler) = handler
+            && let Err(error) = result
+        {
+            let error = self.exception(error);
+
+            result = match error.value() {
+                Some(exception) => self.handle_catch(handler, exception.clone()),
+                None => Err(error),
+            };
         }
 
-        let mut level = 0;
-        let mut fn_instructions: Vec<Token> = vec![];
+        if let Some(finalizer) = finalizer {
+            let completion = self.execute_block(finalizer)?;
 
-        while let Some(token) = self.instructions.get(self.position) {
-            self.position += 1;
-            match token {
-                Token::LeftBrace => {
-                    level = level + 1;
-                }
-                Token::RightBrace => {
-                    level = level - 1;
-                    if level == 0 {
-                        break;
-                    }
-                }
-                _ => {
-                    fn_instructions.push(token.clone());
-                }
+            if !matches!(completion, Completion::Normal) {
+                return Ok(completion);
             }
         }
 
-        let scope_clone = self.scope.clone();
+        result
+    }
 
-        let fn_value =
-            JSValueVariant::JSFunction(JSFunction::new(fn_instructions, arg_names, scope_clone));
+    fn handle_catch(
+        &mut self,
+        handler: &CatchClause,
+        exception: JSValueVariant,
+    ) -> Result<Completion, EndiumError> {
+        let catch_scope = Scope::new(Some(Rc::clone(&self.scope)));
 
-        self.scope
-            .insert_state(fn_name.clone(), State::new(fn_value, true));
-        println!(
-            "{:?} {}",
-            self.instructions.get(self.position),
-            self.position
-        );
+        let outer_scope = std::mem::replace(&mut self.scope, Rc::new(RefCell::new(catch_scope)));
+
+        let completion = match &handler.param {
+            Some(param) => self.bind_pattern(param, exception, Binding::from(VariableKind::Let)),
+            None => Ok(()),
+        }
+        .and_then(|()| {
+            self.declare_lexical(&handler.body);
+            self.execute_statements(&handler.body)
+        });
+        self.leave_scope(outer_scope);
+
+        completion
     }
 
-    pub fn handle_template_string(&mut self, template_tokens: &Vec<Token>) {
-        self.position += 1;
-        let mut templated_string = JSString::new();
-        let mut position = 0;
+    /// Turns a failure raised by the engine into a thrown error object of the
+    /// matching kind, whose stack is the one in effect now. Values already
+    /// thrown, and errors script cannot observe, are returned unchanged.
+    fn exception(&mut self, error: EndiumError) -> EndiumError {
+        let position = error.position().unwrap_or(self.position);
 
-        while let Some(expr) = template_tokens.get(position) {
-            position += 1;
-            match expr {
-                Token::String(js_string) => {
-                    templated_string.addition_assignment(&JSValueVariant::JSString(
-                        JSString::from(js_string.to_string()),
-                    ));
-                }
-                Token::TemplateExpr(template_expr) => {
-                    let parent_scope = self.scope.clone();
+        if error.value().is_some() || !error.kind().is_catchable() {
+            return error.at(position);
+        }
 
-                    let value_scope = Scope::new(Some(parent_scope), template_expr.clone());
-                    let mut value_interpretter = Interpretter::new(None, Some(value_scope)); // Make an interpretter with the value as instructions.
-                    value_interpretter.execute().unwrap();
+        // The stack trace starts where the error was raised.
+        self.position = position;
 
-                    match value_interpretter.interpretted_value {
-                        JSValueVariant::JSNumber(js_number) => templated_string
-                            .addition_assignment(&JSValueVariant::JSNumber(js_number)),
-                        JSValueVariant::JSString(js_string) => templated_string
-                            .addition_assignment(&JSValueVariant::JSString(js_string)),
+        let error_object =
+            stdlib::error::create(self, error.kind().name(), Some(error.message().to_string()));
 
-                        _ => {}
-                    }
+        error
+            .raised(JSValueVariant::JSObject(error_object))
+            .at(position)
+    }
+
+    /// Runs a loop statement. `labels` are the labels placed directly on it,
+    /// which `break` and `continue` may target.
+    fn handle_loop(
+        &mut self,
+        statement: &Statement,
+        labels: &[String],
+    ) -> Result<Completion, EndiumError> {
+        match statement {
+            Statement::While { test, body } => loop {
+                let test = self.evaluate_expression(test)?;
+                if !operations::to_boolean(&test) {
+                    break;
                 }
-                _ => {}
-            };
+
+                let completion = self.execute_statement(body)?;
+                if let Some(completion) = Self::loop_exit(completion, labels) {
+                    return Ok(completion);
+                }
+            },
+            Statement::DoWhile { body, test } => loop {
+                let completion = self.execute_statement(body)?;
+                if let Some(completion) = Self::loop_exit(completion, labels) {
+                    return Ok(completion);
+                }
+
+                let test = self.evaluate_expression(test)?;
+                if !operations::to_boolean(&test) {
+                    break;
+                }
+            },
+            Statement::For { init, .. } => {
+                let lexical_declaration = match init {
+                    Some(ForInit::VariableDeclaration(declaration))
+                        if declaration.kind != VariableKind::Var =>
+                    {
+                        Some(declaration)
+                    }
+                    _ => None,
+                };
+
+                let Some(declaration) = lexical_declaration else {
+                    return self.handle_for(statement, labels, &[]);
+                };
+
+                // `let` and `const` in the head get a scope around the loop.
+                let loop_scope = Scope::new(Some(Rc::clone(&self.scope)));
+                let outer_scope =
+                    std::mem::replace(&mut self.scope, Rc::new(RefCell::new(loop_scope)));
+
+                let per_iteration: Vec<&str> = match declaration.kind {
+                    VariableKind::Let => declaration
+                        .declarations
+                        .iter()
+                        .flat_map(|declarator| declarator.target.bound_names())
+                        .collect(),
+                    _ => vec![],
+                };
+
+                self.declare_uninitialized(declaration);
+                let completion = self.handle_for(statement, labels, &per_iteration);
+                self.leave_scope(outer_scope);
+
+                return completion;
+            }
+            Statement::ForIn { left, right, body } => {
+                return self.handle_for_in_of(left, right, body, labels, false);
+            }
+            Statement::ForOf { left, right, body } => {
+                return self.handle_for_in_of(left, right, body, labels, true);
+            }
+            _ => return self.execute_statement(statement),
         }
 
-        let value = JSValueVariant::JSString(templated_string);
-        self.interpretted_value = value;
+        Ok(Completion::Normal)
     }
 
-    pub fn handle_number(&mut self, n: &String) {
-        self.position += 1;
+    /// Runs a for-of loop over the values `right` iterates, or a for-in loop
+    /// over its enumerable keys. A `let` or `const` binding is created anew
+    /// for each iteration.
+    fn handle_for_in_of(
+        &mut self,
+        left: &ForBinding,
+        right: &Expression,
+        body: &Statement,
+        labels: &[String],
+        is_of: bool,
+    ) -> Result<Completion, EndiumError> {
+        let outer_scope = Rc::clone(&self.scope);
+
+        // The loop's own bindings cannot be used by the expression it loops over.
+        if let ForBinding::Declaration { kind, target } = left
+            && *kind != VariableKind::Var
+        {
+            let mut tdz_scope = Scope::new(Some(Rc::clone(&outer_scope)));
+            for name in target.bound_names() {
+                tdz_scope.insert_state(name.to_string(), State::uninitialized(true));
+            }
+            self.scope = Rc::new(RefCell::new(tdz_scope));
+        }
+
+        let right = self.evaluate_expression(right);
+        self.scope = Rc::clone(&outer_scope);
+        let right = right?;
 
-        let number = n.parse::<f64>().unwrap();
-        let mut number = JSNumber::new(number);
+        let values = if is_of {
+            self.iterate(&right)?
+        } else {
+            self.for_in_keys(&right)
+                .into_iter()
+                .map(JSValueVariant::from)
+                .collect()
+        };
 
-        if let Some(operator) = self.instructions.get(self.position) {
-            match operator {
-                Token::Increment => {
-                    number.addition_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
-                    self.interpretted_value = JSValueVariant::JSNumber(number);
-                    self.position += 1;
-                }
-                Token::Decrement => {
-                    number.decrement_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
-                    self.interpretted_value = JSValueVariant::JSNumber(number);
-                    self.position += 1;
+        for value in values {
+            let completion = match left {
+                ForBinding::Declaration { kind, target } if *kind != VariableKind::Var => {
+                    let iteration_scope = Scope::new(Some(Rc::clone(&outer_scope)));
+                    self.scope = Rc::new(RefCell::new(iteration_scope));
+                    self.bind_pattern(target, value, Binding::from(*kind))
                 }
-                _ => {
-                    self.interpretted_value = JSValueVariant::JSNumber(number);
+                ForBinding::Declaration { target, .. } | ForBinding::Pattern(target) => {
+                    self.bind_pattern(target, value, Binding::Assign)
                 }
             }
-        } else {
-            self.interpretted_value = JSValueVariant::JSNumber(number);
+            .and_then(|()| self.execute_statement(body));
+
+            if !Rc::ptr_eq(&self.scope, &outer_scope) {
+                self.leave_scope(Rc::clone(&outer_scope));
+            }
+
+            if let Some(completion) = Self::loop_exit(completion?, labels) {
+                return Ok(completion);
+            }
         }
+
+        Ok(Completion::Normal)
     }
 
-    pub fn value_collector(&mut self, stop_at: Vec<Token>) -> Vec<Token> {
-        let mut value_tokens: Vec<Token> = vec![];
+    /// The keys a for-in loop visits: the enumerable keys of `value` and of
+    /// its prototypes, each once. Keys shadowed by a non-enumerable property
+    /// are left out.
+    fn for_in_keys(&self, value: &JSValueVariant) -> Vec<String> {
+        let mut keys: Vec<String> = vec![];
+        let mut visited: HashSet<String> = HashSet::new();
+
+        let mut object = match value {
+            JSValueVariant::JSObject(js_object) => Some(js_object.clone()),
+            JSValueVariant::JSArray(js_array) => {
+                for index in js_array.own_indices() {
+                    let key = index.to_string();
+                    let enumerable = js_array
+                        .object()
+                        .get_own_property(&key)
+                        .is_none_or(|property| property.is_enumerable());
+
+                    if enumerable {
+                        keys.push(key.clone());
+                    }
+                    visited.insert(key);
+                }
+                Some(js_array.object().clone())
+            }
+            JSValueVariant::JSFunction(js_function) => Some(js_function.object().clone()),
+            JSValueVariant::JSString(js_string) => {
+                return (0..js_string.code_units().len())
+                    .map(|index| index.to_string())
+                    .collect();
+            }
+            _ => None,
+        };
 
-        while let Some(v_token) = self.instructions.get(self.position) {
-            let v_token = v_token.clone();
+        while let Some(current) = object {
+            for key in current.keys() {
+                let is_enumerable = current
+                    .get_own_property(&key)
+                    .is_some_and(|property| property.is_enumerable());
 
-            if stop_at.iter().find(|e| e == &&v_token).is_some() {
-                return value_tokens;
+                if visited.insert(key.clone()) && is_enumerable {
+                    keys.push(key);
+                }
             }
 
-            value_tokens.push(v_token);
-            self.position += 1;
+            object = current.prototype();
         }
 
-        value_tokens
+        keys
     }
 
-    pub fn handle_const(&mut self) {
-        self.position += 1; // Skip the `const` keyword.
-
-        let current_token = self.instructions[self.position].clone();
+    /// Runs a for statement. Each iteration gets its own copy of the
+    /// `per_iteration` bindings, so closures created in one iteration keep
+    /// the values of that iteration.
+    fn handle_for(
+        &mut self,
+        statement: &Statement,
+        labels: &[String],
+        per_iteration: &[&str],
+    ) -> Result<Completion, EndiumError> {
+        let Statement::For {
+            init,
+            test,
+            update,
+            body,
+        } = statement
+        else {
+            return self.execute_statement(statement);
+        };
 
-        match current_token {
-            Token::Identifier(variable_name) => {
-                self.position += 2; // Skip variable_name and `=`
+        match init {
+            Some(ForInit::VariableDeclaration(declaration)) => {
+                self.handle_variable_declaration(declaration)?
+            }
+            Some(ForInit::Expression(expression)) => {
+                self.evaluate_expression(expression)?;
+            }
+            None => {}
+        }
 
-                let value_tokens: Vec<Token> =
-                    self.value_collector(vec![Token::Semicolon, Token::Newline]);
-                let parent_scope = self.scope.clone();
+        self.copy_iteration_scope(per_iteration);
 
-                let value_scope = Scope::new(Some(parent_scope), value_tokens);
-                let mut value_interpretter = Interpretter::new(None, Some(value_scope)); // Make an interpretter with the value as instructions.
-                value_interpretter.execute().unwrap();
-                let value = value_interpretter.interpretted_value;
-                let state: State = State::new(value, false);
+        loop {
+            if let Some(test) = test {
+                let test = self.evaluate_expression(test)?;
+                if !operations::to_boolean(&test) {
+                    break;
+                }
+            }
 
-                self.scope.insert_state(variable_name.to_string(), state);
+            let completion = self.execute_statement(body)?;
+            if let Some(completion) = Self::loop_exit(completion, labels) {
+                return Ok(completion);
             }
 
-            Token::LeftBrace => {
-                //Manage destruction.
+            self.copy_iteration_scope(per_iteration);
+
+            if let Some(update) = update {
+                self.evaluate_expression(update)?;
             }
-            _ => {}
         }
+
+        Ok(Completion::Normal)
     }
 
-    pub fn handle_let(&mut self) {
-        self.position += 1; // Skip the `const` keyword.
+    /// Replaces the scope of a for loop with a new one holding the current
+    /// values of `names`.
+    fn copy_iteration_scope(&mut self, names: &[&str]) {
+        if names.is_empty() {
+            return;
+        }
 
-        let current_token = self.instructions[self.position].clone();
+        let mut iteration_scope = Scope::new(self.scope.borrow().parent().cloned());
 
-        match current_token {
-            Token::Identifier(variable_name) => {
-                self.position += 2; // Skip variable_name and `=`
+        for name in names {
+            if let Some(state) = self.scope.borrow().get_state(name) {
+                iteration_scope.insert_state(name.to_string(), state.clone());
+            }
+        }
 
-                let value_tokens: Vec<Token> =
-                    self.value_collector(vec![Token::Semicolon, Token::Newline]);
-                let parent_scope = self.scope.clone();
+        let scope = std::mem::replace(&mut self.scope, Rc::new(RefCell::new(iteration_scope)));
+        self.collector.release(scope);
+    }
 
-                let value_scope = Scope::new(Some(parent_scope), value_tokens);
-                let mut value_interpretter = Interpretter::new(None, Some(value_scope)); // Make an interpretter with the value as instructions.
-                value_interpretter.execute().unwrap();
-                let value = value_interpretter.interpretted_value;
-                let state: State = State::new(value, true);
+    /// Decides what a loop does with the completion of one iteration of its
+    /// body: `None` keeps looping, `Some` leaves the loop with that completion.
+    fn loop_exit(completion: Completion, labels: &[String]) -> Option<Completion> {
+        match completion {
+            Completion::Normal | Completion::Continue(None) => None,
+            Completion::Continue(Some(label)) if labels.contains(&label) => None,
+            Completion::Break(None) => Some(Completion::Normal),
+            Completion::Break(Some(label)) if labels.contains(&label) => Some(Completion::Normal),
+            completion => Some(completion),
+        }
+    }
 
-                self.scope.insert_state(variable_name.to_string(), state);
-            }
+    fn handle_variable_declaration(
+        &mut self,
+        declaration: &VariableDeclaration,
+    ) -> Result<(), EndiumError> {
+        for declarator in declaration.declarations.iter() {
+            let value = match &declarator.init {
+                Some(init) => self.evaluate_expression(init)?,
+                // `var x;` leaves the hoisted binding as it is.
+                None if declaration.kind == VariableKind::Var => continue,
+                None => JSValueVariant::Undefined,
+            };
 
-            Token::LeftBrace => {
-                //Manage destruction.
-            }
-            _ => {}
+            self.bind_pattern(&declarator.target, value, Binding::from(declaration.kind))?;
         }
+
+        Ok(())
     }
 
-    pub fn handle_identifier(&mut self, identifier: &String) {
-        if let Some(operator) = self.instructions.get(self.position + 1) {
-            self.position += 1;
-            let operator = operator.clone();
-            let value_tokens = self.value_collector(vec![Token::Semicolon, Token::Newline]);
-            let parent_scope = self.scope.clone();
-            match operator {
-                Token::LeftParen => self.handle_function_execution(identifier),
-                Token::Increment => self.handle_increment(identifier),
-                Token::Decrement => self.handle_decrement(identifier),
-                Token::PlusAssign
-                | Token::MinusAssign
-                | Token::DivideAssign
-                | Token::MultiplyAssign => {
-                    let value_scope = Scope::new(Some(parent_scope), value_tokens);
-                    let mut value_interpretter = Interpretter::new(None, Some(value_scope));
-                    value_interpretter.execute().unwrap();
-                    let value = value_interpretter.interpretted_value;
+    /// Creates a function from a declaration or expression, which unlike
+    /// methods and arrow functions gets a `prototype` object for the
+    /// instances it constructs.
+    fn create_function(
+        &self,
+        function: &Rc<FunctionNode>,
+        scope: Rc<RefCell<Scope>>,
+    ) -> JSValueVariant {
+        let js_function =
+            JSFunction::new(Rc::clone(function), scope, self.function_prototype.clone());
+
+        let prototype = JSObject::from(Some(self.object_prototype.clone()));
+        prototype.define_property(
+            "constructor",
+            PropertyDescriptor::data(
+                JSValueVariant::JSFunction(js_function.clone()),
+                true,
+                false,
+                true,
+            ),
+        );
+        js_function.object().define_property(
+            "prototype",
+            PropertyDescriptor::data(JSValueVariant::JSObject(prototype), true, false, false),
+        );
 
-                    match operator {
-                        Token::PlusAssign => self.handle_addition_assignment(identifier, value),
-                        Token::MinusAssign => self.handle_subtraction_assignment(identifier, value),
-                        Token::MultiplyAssign => {
-                            self.handle_multiplication_assignment(identifier, value)
-                        }
-                        Token::DivideAssign => self.handle_division_assignment(identifier, value),
-                        _ => {}
-                    }
-                }
+        JSValueVariant::JSFunction(js_function)
+    }
 
-                _ => {}
-            };
-        } else {
-            let variable = self.scope.get_state(identifier).unwrap();
-            self.interpretted_value = variable.value().clone();
-            self.position += 1;
-        }
+    fn handle_function_declaration(&mut self, function: &Rc<FunctionNode>) {
+        let fn_name = function.name.clone().unwrap_or_default();
+        let fn_value = self.create_function(function, Rc::clone(&self.scope));
+
+        self.scope
+            .borrow_mut()
+            .insert_state(fn_name, State::new(fn_value, true));
     }
 
-    fn handle_function_execution(&mut self, fn_identifier: &String) {
-        self.position += 1 + 1 + 1; // Skip the fn_identifier declaration
+    fn handle_function_expression(&mut self, function: &Rc<FunctionNode>) -> JSValueVariant {
+        let fn_name = match &function.name {
+            Some(fn_name) => fn_name,
+            None => return self.create_function(function, Rc::clone(&self.scope)),
+        };
 
-        let function = self.scope.get_state_mut(fn_identifier).unwrap();
+        // A named function expression can refer to itself by name, which is
+        // bound in a scope of its own between the function and its surroundings.
+        let name_scope = Rc::new(RefCell::new(Scope::new(Some(Rc::clone(&self.scope)))));
+        let fn_value = self.create_function(function, Rc::clone(&name_scope));
 
-        match function.value_mut() {
-            JSValueVariant::JSFunction(js_func) => {
-                js_func.execute(vec![]);
-            }
-            _ => {}
-        }
+        name_scope
+            .borrow_mut()
+            .insert_state(fn_name.to_string(), State::new(fn_value.clone(), false));
+
+        fn_value
     }
 
-    fn handle_increment(&mut self, variable_identifier: &String) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
+    /// Evaluates a class in a scope of its own, which binds its name and
+    /// gives each of its private names a new id.
+    fn handle_class(&mut self, class: &ClassNode) -> Result<JSValueVariant, EndiumError> {
+        let mut class_scope = Scope::new(Some(Rc::clone(&self.scope)));
 
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
+        // The name cannot be used until the class is defined, as in `extends`.
+        if let Some(name) = &class.name {
+            class_scope.insert_state(name.to_string(), State::uninitialized(false));
         }
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.addition_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
-                self.interpretted_value = JSValueVariant::JSNumber(js_number.to_owned());
+        for member in class.members.iter() {
+            if let ClassMember::Method {
+                key: ClassKey::Private(name),
+                ..
             }
-            JSValueVariant::JSString(js_string) => {
-                js_string.addition_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
-                self.interpretted_value = JSValueVariant::JSString(js_string.to_owned());
+            | ClassMember::Field {
+                key: ClassKey::Private(name),
+                ..
+            } = member
+            {
+                let name = format!("#{}", name);
+
+                if class_scope.get_state(&name).is_none() {
+                    self.private_name_count += 1;
+                    let id = format!("{}:{}", name, self.private_name_count);
+                    class_scope.insert_state(name, State::new(JSValueVariant::from(id), false));
+                }
             }
-            _ => {}
         }
+
+        let outer_scope = std::mem::replace(&mut self.scope, Rc::new(RefCell::new(class_scope)));
+        let class = self.define_class(class);
+        self.leave_scope(outer_scope);
+
+        class
     }
 
-    fn handle_decrement(&mut self, variable_identifier: &String) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
+    fn define_class(&mut self, class: &ClassNode) -> Result<JSValueVariant, EndiumError> {
+        let (prototype_parent, constructor_parent, super_constructor) = match &class.super_class {
+            None => (
+                Some(self.object_prototype.clone()),
+                self.function_prototype.clone(),
+                None,
+            ),
+            Some(super_class) => {
+                let super_class = self.evaluate_expression(super_class)?;
+                self.class_heritage(super_class)?
+            }
+        };
+
+        let prototype = JSObject::from(prototype_parent);
+        let definition = Rc::new(ClassDefinition::new(class.super_class.is_some()));
 
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
+        // Methods on the prototype look `super.name` up on its prototype.
+        let mut prototype_home = Scope::new(Some(Rc::clone(&self.scope)));
+        prototype_home.insert_state(
+            String::from(HOME_OBJECT),
+            State::new(JSValueVariant::JSObject(prototype.clone()), false),
+        );
+        if let Some(super_constructor) = super_constructor {
+            prototype_home.insert_state(
+                String::from(SUPER_CONSTRUCTOR),
+                State::new(super_constructor, false),
+            );
         }
+        let prototype_home = Rc::new(RefCell::new(prototype_home));
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.decrement_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
-            }
-            JSValueVariant::JSString(js_string) => {
-                js_string.decrement_assignment(&JSValueVariant::JSNumber(JSNumber::new(1.0)));
+        let constructor = JSFunction::class(
+            Rc::clone(&class.constructor),
+            Rc::clone(&prototype_home),
+            Rc::clone(&definition),
+            constructor_parent,
+        );
+        let constructor_value = JSValueVariant::JSFunction(constructor.clone());
+
+        prototype_home.borrow_mut().insert_state(
+            String::from(CLASS_CONSTRUCTOR),
+            State::new(constructor_value.clone(), false),
+        );
+
+        // Static methods look `super.name` up on the parent class.
+        let mut static_home = Scope::new(Some(Rc::clone(&self.scope)));
+        static_home.insert_state(
+            String::from(HOME_OBJECT),
+            State::new(constructor_value.clone(), false),
+        );
+        let static_home = Rc::new(RefCell::new(static_home));
+
+        constructor.object().define_property(
+            "prototype",
+            PropertyDescriptor::data(
+                JSValueVariant::JSObject(prototype.clone()),
+                false,
+                false,
+                false,
+            ),
+        );
+        prototype.define_property(
+            "constructor",
+            PropertyDescriptor::data(constructor_value.clone(), true, false, true),
+        );
+
+        // Private methods are installed on an instance before its fields.
+        let mut private_methods: Vec<(String, String, PropertyDescriptor)> = vec![];
+        let mut fields: Vec<InstanceElement> = vec![];
+        let mut static_elements: Vec<StaticElement> = vec![];
+
+        for member in class.members.iter() {
+            match member {
+                ClassMember::Method {
+                    key,
+                    kind,
+                    function,
+                    is_static,
+                } => {
+                    let (target, home) = if *is_static {
+                        (constructor.object(), &static_home)
+                    } else {
+                        (&prototype, &prototype_home)
+                    };
+                    let method = JSValueVariant::JSFunction(JSFunction::new(
+                        Rc::clone(function),
+                        Rc::clone(home),
+                        self.function_prototype.clone(),
+                    ));
+
+                    match self.evaluate_class_key(key)? {
+                        ObjectKey::Property(key) => {
+                            let existing = target.get_own_property(&key);
+                            target.define_property(
+                                &key,
+                                Self::method(existing, *kind, method, false),
+                            );
+                        }
+                        ObjectKey::Private { id, .. } if *is_static => {
+                            let existing = target.get_private_element(&id);
+                            target.define_private_element(
+                                &id,
+                                Self::method(existing, *kind, method, false),
+                            );
+                        }
+                        ObjectKey::Private { name, id } => {
+                            match private_methods
+                                .iter_mut()
+                                .find(|(_, other, _)| *other == id)
+                            {
+                                Some((_, _, descriptor)) => {
+                                    *descriptor = Self::method(
+                                        Some(descriptor.clone()),
+                                        *kind,
+                                        method,
+                                        false,
+                                    );
+                                }
+                                None => private_methods.push((
+                                    name,
+                                    id,
+                                    Self::method(None, *kind, method, false),
+                                )),
+                            }
+                        }
+                    }
+                }
+                ClassMember::Field {
+                    key,
+                    value,
+                    is_static,
+                } => {
+                    let key = self.evaluate_class_key(key)?;
+
+                    if *is_static {
+                        static_elements.push(StaticElement::Field {
+                            key,
+                            value: value.as_ref(),
+                        });
+                    } else {
+                        fields.push(InstanceElement::Field {
+                            key,
+                            value: value.clone(),
+                        });
+                    }
+                }
+                ClassMember::StaticBlock(block) => {
+                    static_elements.push(StaticElement::Block(block))
+                }
             }
-            _ => {}
         }
-    }
 
-    fn handle_addition_assignment(&mut self, variable_identifier: &String, value: JSValueVariant) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
+        definition.set_elements(
+            private_methods
+                .into_iter()
+                .map(|(name, id, descriptor)| InstanceElement::PrivateMethod {
+                    key: ObjectKey::Private { name, id },
+                    descriptor,
+                })
+                .chain(fields)
+                .collect(),
+        );
 
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
+        if let Some(name) = &class.name {
+            self.scope
+                .borrow_mut()
+                .initialize(name, constructor_value.clone());
         }
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.addition_assignment(&value);
-            }
-            JSValueVariant::JSString(js_string) => {
-                js_string.addition_assignment(&value);
+        for element in static_elements {
+            match element {
+                StaticElement::Field { key, value } => {
+                    let value = self.evaluate_field(value, &static_home, &constructor_value)?;
+                    Self::define_element(
+                        constructor.object(),
+                        &key,
+                        PropertyDescriptor::from(value),
+                    )?;
+                }
+                StaticElement::Block(block) => {
+                    let block = JSFunction::new(
+                        Rc::clone(block),
+                        Rc::clone(&static_home),
+                        self.function_prototype.clone(),
+                    );
+                    self.call_function(&block, constructor_value.clone(), vec![])?;
+                }
             }
-            _ => {}
         }
+
+        Ok(constructor_value)
     }
 
-    fn handle_subtraction_assignment(
+    /// Checks the value a class extends, returning the parents of its
+    /// prototype and of the class itself, and the constructor `super(...)`
+    /// calls.
+    fn class_heritage(
         &mut self,
-        variable_identifier: &String,
-        value: JSValueVariant,
-    ) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
+        super_class: JSValueVariant,
+    ) -> Result<(Option<JSObject>, JSObject, Option<JSValueVariant>), EndiumError> {
+        let parent = match &super_class {
+            JSValueVariant::Null => {
+                return Ok((None, self.function_prototype.clone(), Some(super_class)));
+            }
+            JSValueVariant::JSFunction(parent) if parent.is_constructor() => {
+                parent.object().clone()
+            }
+            _ => {
+                return Err(EndiumError::type_error(format!(
+                    "Class extends value {} is not a constructor or null",
+                    operations::to_string(&super_class)
+                )));
+            }
+        };
 
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
+        match self.handle_member(&super_class, "prototype")? {
+            JSValueVariant::JSObject(prototype) => Ok((Some(prototype), parent, Some(super_class))),
+            JSValueVariant::Null => Ok((None, parent, Some(super_class))),
+            prototype => Err(EndiumError::type_error(format!(
+                "Class extends value does not have valid prototype property {}",
+                operations::to_string(&prototype)
+            ))),
         }
+    }
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.decrement_assignment(&value);
-            }
-            JSValueVariant::JSString(js_string) => {
-                js_string.decrement_assignment(&value);
+    fn evaluate_class_key(&mut self, key: &ClassKey) -> Result<ObjectKey, EndiumError> {
+        match key {
+            ClassKey::Property(key) => Ok(ObjectKey::Property(self.evaluate_object_key(key)?)),
+            ClassKey::Private(name) => {
+                self.evaluate_member_key(&MemberProperty::Private(name.clone()))
             }
-            _ => {}
         }
     }
 
-    fn handle_multiplication_assignment(
-        &mut self,
-        variable_identifier: &String,
-        value: JSValueVariant,
-    ) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
-
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
-        }
+    /// The descriptor of a method, which is enumerable in object literals but
+    /// not in classes. A getter or setter joins the other half of an accessor
+    /// already defined.
+    fn method(
+        existing: Option<PropertyDescriptor>,
+        kind: MethodKind,
+        function: JSValueVariant,
+        enumerable: bool,
+    ) -> PropertyDescriptor {
+        let (get, set) = match existing.as_ref().map(PropertyDescriptor::kind) {
+            Some(PropertyKind::Accessor { get, set }) => (get.clone(), set.clone()),
+            _ => (None, None),
+        };
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.multiplication_assignment(&value);
+        match kind {
+            MethodKind::Method => PropertyDescriptor::data(function, true, enumerable, true),
+            MethodKind::Getter => {
+                PropertyDescriptor::accessor(Some(function), set, enumerable, true)
             }
-            JSValueVariant::JSString(js_string) => {
-                js_string.multiplication_assignment(&value);
+            MethodKind::Setter => {
+                PropertyDescriptor::accessor(get, Some(function), enumerable, true)
             }
-            _ => {}
         }
     }
 
-    fn handle_division_assignment(&mut self, variable_identifier: &String, value: JSValueVariant) {
-        let variable_mut = self.scope.get_state_mut(variable_identifier).unwrap();
-
-        if !variable_mut.is_mutable() {
-            return; // Assignment to constant variable error.
-        }
+    /// Gives a newly constructed object the private methods and fields of
+    /// `class`.
+    fn initialize_instance(
+        &mut self,
+        this: &JSValueVariant,
+        class: &JSFunction,
+    ) -> Result<(), EndiumError> {
+        let FunctionKind::Class {
+            scope, definition, ..
+        } = class.kind()
+        else {
+            return Ok(());
+        };
+        let Some(object) = Self::object_of(this).cloned() else {
+            return Ok(());
+        };
 
-        match variable_mut.value_mut() {
-            JSValueVariant::JSNumber(js_number) => {
-                js_number.division_assignment(&value);
-            }
-            JSValueVariant::JSString(js_string) => {
-                js_string.division_assignment(&value);
+        for element in definition.elements().iter() {
+            match element {
+                InstanceElement::PrivateMethod { key, descriptor } => {
+                    Self::define_element(&object, key, descriptor.clone())?
+                }
+                InstanceElement::Field { key, value } => {
+                    let value = self.evaluate_field(value.as_ref(), scope, this)?;
+                    Self::define_element(&object, key, PropertyDescriptor::from(value))?
+                }
             }
-            _ => {}
         }
-    }
 
-    pub fn scope(&self) -> &Scope {
-        &self.scope
+        Ok(())
     }
 
-    pub fn new(tokens: Option<Vec<Token>>, scope: Option<Scope>) -> Self {
-        let scope = match scope {
-            Some(s) => s,
-            None => Scope::new(None, tokens.unwrap()),
+    /// Runs a field initializer as a method of the class would, with `this`
+    /// bound to the object the field is defined on.
+    fn evaluate_field(
+        &mut self,
+        value: Option<&Expression>,
+        home: &Rc<RefCell<Scope>>,
+        this: &JSValueVariant,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let Some(value) = value else {
+            return Ok(JSValueVariant::Undefined);
         };
 
-        Self {
-            instructions: scope.instructions().clone(),
-            scope,
-            interpretted_value: JSValueVariant::Undefined,
-            position: 0,
-            is_running: true,
-        }
+        let mut field_scope = Scope::new(Some(Rc::clone(home)));
+        field_scope.insert_state(String::from(THIS), State::new(this.clone(), false));
+        field_scope.insert_state(
+            String::from(NEW_TARGET),
+            State::new(JSValueVariant::Undefined, false),
+        );
+
+        let outer_scope = std::mem::replace(&mut self.scope, Rc::new(RefCell::new(field_scope)));
+        let value = self.evaluate_expression(value);
+        self.leave_scope(outer_scope);
+
+        value
+    }
+
+    fn define_element(
+        object: &JSObject,
+        key: &ObjectKey,
+        descriptor: PropertyDescriptor,
+    ) -> Result<(), EndiumError> {
+        match key {
+            ObjectKey::Property(key) => object.define_property(key, descriptor),
+            ObjectKey::Private { id, .. } if object.has_private_element(id) => {
+                return Err(EndiumError::type_error(format!(
+                    "Cannot initialize {} twice on the same object",
+                    key.name()
+                )));
+            }
+            ObjectKey::Private { id, .. } => object.define_private_element(id, descriptor),
+        }
+
+        Ok(())
+    }
+
+    fn handle_return(&mut self, argument: Option<&Expression>) -> Result<Completion, EndiumError> {
+        let value = match argument {
+            Some(argument) => self.evaluate_expression(argument)?,
+            None => JSValueVariant::Undefined,
+        };
+
+        Ok(Completion::Return(value))
+    }
+
+    pub fn evaluate_expression(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let value = match expression {
+            Expression::Number(number) => JSValueVariant::JSNumber(JSNumber::new(*number)),
+            Expression::String(string) => {
+                JSValueVariant::JSString(JSString::from(string.to_string()))
+            }
+            Expression::Boolean(boolean) => JSValueVariant::JSBoolean(JSBool::new(*boolean)),
+            Expression::Null => JSValueVariant::Null,
+            Expression::Undefined => JSValueVariant::Undefined,
+            Expression::TemplateLiteral(parts) => self.handle_template_literal(parts)?,
+            Expression::Identifier { name, position } => self
+                .handle_identifier(name)
+                .map_err(|error| error.at(*position))?,
+            Expression::Function(function) => self.handle_function_expression(function),
+            Expression::ArrowFunction(function) => JSValueVariant::JSFunction(JSFunction::arrow(
+                Rc::clone(function),
+                Rc::clone(&self.scope),
+                self.function_prototype.clone(),
+            )),
+            Expression::Class(class) => self.handle_class(class)?,
+            Expression::This => self.handle_this()?,
+            Expression::NewTarget => match self.scope.borrow().lookup(NEW_TARGET) {
+                Some(new_target) => new_target.value().clone(),
+                None => JSValueVariant::Undefined,
+            },
+            Expression::Object(properties) => self.handle_object_literal(properties)?,
+            Expression::Array(elements) => self.handle_array_literal(elements)?,
+            Expression::Member { .. }
+            | Expression::SuperMember { .. }
+            | Expression::Call { .. } => self
+                .evaluate_chain(expression)?
+                .unwrap_or(JSValueVariant::Undefined),
+            Expression::OptionalChain(chain) => self
+                .evaluate_chain(chain)?
+                .unwrap_or(JSValueVariant::Undefined),
+            Expression::PrivateIn {
+                name,
+                object,
+                position,
+            } => {
+                let object = self.evaluate_expression(object)?;
+                let ObjectKey::Private { id, .. } =
+                    self.evaluate_member_key(&MemberProperty::Private(name.clone()))?
+                else {
+                    return Ok(JSValueVariant::from(false));
+                };
+
+                match Self::object_of(&object) {
+                    Some(js_object) => JSValueVariant::from(js_object.has_private_element(&id)),
+                    None => {
+                        return Err(
+                            Self::in_operator_error(&format!("#{}", name), &object).at(*position)
+                        );
+                    }
+                }
+            }
+            Expression::New {
+                callee,
+                arguments,
+                position,
+            } => self
+                .handle_new(callee, arguments, *position)
+                .map_err(|error| error.at(*position))?,
+            Expression::SuperCall {
+                arguments,
+                position,
+            } => self
+                .handle_super_call(arguments, *position)
+                .map_err(|error| error.at(*position))?,
+            Expression::Unary { operator, argument } => self.handle_unary(*operator, argument)?,
+            Expression::Binary {
+                operator,
+                left,
+                right,
+            } => {
+                let left = self.evaluate_expression(left)?;
+                let right = self.evaluate_expression(right)?;
+                self.handle_binary_operation(*operator, &left, &right)?
+            }
+            Expression::Logical {
+                operator,
+                left,
+                right,
+            } => self.handle_logical(*operator, left, right)?,
+            Expression::Conditional {
+                test,
+                consequent,
+                alternate,
+            } => {
+                let test = self.evaluate_expression(test)?;
+
+                if operations::to_boolean(&test) {
+                    self.evaluate_expression(consequent)?
+                } else {
+                    self.evaluate_expression(alternate)?
+                }
+            }
+            Expression::Sequence(expressions) => {
+                let mut value = JSValueVariant::Undefined;
+                for expression in expressions {
+                    value = self.evaluate_expression(expression)?;
+                }
+                value
+            }
+            Expression::Assignment {
+                operator,
+                target,
+                value,
+                position,
+            } => self
+                .handle_assignment(*operator, target, value)
+                .map_err(|error| error.at(*position))?,
+            Expression::DestructuringAssignment {
+                target,
+                value,
+                position,
+            } => {
+                let value = self.evaluate_expression(value)?;
+                self.bind_pattern(target, value.clone(), Binding::Assign)
+                    .map_err(|error| error.at(*position))?;
+                value
+            }
+            Expression::Update {
+                operator,
+                prefix,
+                target,
+                position,
+            } => self
+                .handle_update(*operator, *prefix, target)
+                .map_err(|error| error.at(*position))?,
+        };
+
+        Ok(value)
+    }
+
+    fn handle_template_literal(
+        &mut self,
+        parts: &[TemplatePart],
+    ) -> Result<JSValueVariant, EndiumError> {
+        let mut templated_string = String::new();
+
+        for part in parts {
+            match part {
+                TemplatePart::String(js_string) => templated_string.push_str(js_string),
+                TemplatePart::Expression(template_expr) => {
+                    let value = self.evaluate_expression(template_expr)?;
+                    templated_string.push_str(&self.string_of(&value)?);
+                }
+            }
+        }
+
+        Ok(JSValueVariant::from(templated_string))
+    }
+
+    fn handle_object_literal(
+        &mut self,
+        properties: &[ObjectProperty],
+    ) -> Result<JSValueVariant, EndiumError> {
+        let js_object = JSObject::from(Some(self.object_prototype.clone()));
+
+        for property in properties {
+            match property {
+                ObjectProperty::Property { key, value } => {
+                    let property_key = self.evaluate_object_key(key)?;
+                    let value = self.evaluate_expression(value)?;
+                    js_object.define_property(&property_key, PropertyDescriptor::from(value));
+                }
+                ObjectProperty::Method {
+                    key,
+                    kind,
+                    function,
+                } => {
+                    let property_key = self.evaluate_object_key(key)?;
+                    let method = JSValueVariant::JSFunction(JSFunction::new(
+                        Rc::clone(function),
+                        Rc::clone(&self.scope),
+                        self.function_prototype.clone(),
+                    ));
+                    let existing = js_object.get_own_property(&property_key);
+                    js_object.define_property(
+                        &property_key,
+                        Self::method(existing, *kind, method, true),
+                    );
+                }
+                ObjectProperty::Prototype(expression) => {
+                    match self.evaluate_expression(expression)? {
+                        JSValueVariant::JSObject(prototype) => {
+                            js_object.set_prototype(Some(prototype));
+                        }
+                        JSValueVariant::Null => {
+                            js_object.set_prototype(None);
+                        }
+                        _ => {}
+                    }
+                }
+                ObjectProperty::Spread(expression) => {
+                    let source = self.evaluate_expression(expression)?;
+                    self.copy_data_properties(&js_object, &source)?;
+                }
+            }
+        }
+
+        Ok(JSValueVariant::JSObject(js_object))
+    }
+
+    fn handle_array_literal(
+        &mut self,
+        elements: &[ArrayElement],
+    ) -> Result<JSValueVariant, EndiumError> {
+        let mut values: Vec<Option<JSValueVariant>> = vec![];
+
+        for element in elements {
+            match element {
+                ArrayElement::Hole => values.push(None),
+                ArrayElement::Expression(expression) => {
+                    values.push(Some(self.evaluate_expression(expression)?));
+                }
+                ArrayElement::Spread(expression) => {
+                    let iterable = self.evaluate_expression(expression)?;
+                    values.extend(self.iterate(&iterable)?.into_iter().map(Some));
+                }
+            }
+        }
+
+        Ok(JSValueVariant::JSArray(JSArray::from(
+            values,
+            Some(self.array_prototype.clone()),
+        )))
+    }
+
+    /// Collects the values produced by iterating `iterable`: the elements of
+    /// an array, or the code points of a string.
+    pub fn iterate(
+        &mut self,
+        iterable: &JSValueVariant,
+    ) -> Result<Vec<JSValueVariant>, EndiumError> {
+        match iterable {
+            JSValueVariant::JSArray(js_array) => (0..js_array.length())
+                .map(|index| self.handle_member(iterable, &index.to_string()))
+                .collect(),
+            JSValueVariant::JSString(js_string) => Ok(js_string
+                .str_value()
+                .chars()
+                .map(|ch| JSValueVariant::from(ch.to_string()))
+                .collect()),
+            JSValueVariant::Null | JSValueVariant::Undefined => Err(EndiumError::type_error(
+                format!("{} is not iterable", operations::to_string(iterable)),
+            )),
+            JSValueVariant::JSObject(js_object) if js_object.is_arguments() => {
+                let length = self.handle_member(iterable, "length")?;
+                (0..operations::to_length(operations::to_number(&length)))
+                    .map(|index| self.handle_member(iterable, &index.to_string()))
+                    .collect()
+            }
+            JSValueVariant::JSObject(_) | JSValueVariant::JSFunction(_) => {
+                Err(EndiumError::type_error(format!(
+                    "{} is not iterable",
+                    operations::type_of(iterable)
+                )))
+            }
+            _ => Err(EndiumError::type_error(format!(
+                "{} {} is not iterable",
+                operations::type_of(iterable),
+                operations::to_string(iterable)
+            ))),
+        }
+    }
+
+    fn evaluate_object_key(&mut self, key: &PropertyKey) -> Result<String, EndiumError> {
+        match key {
+            PropertyKey::Static(name) => Ok(name.to_string()),
+            PropertyKey::Computed(expression) => {
+                let key = self.evaluate_expression(expression)?;
+                self.string_of(&key)
+            }
+        }
+    }
+
+    /// Copies the own enumerable properties of `source` onto `target`, as
+    /// object spread does. Null, undefined and other primitives copy nothing.
+    fn copy_data_properties(
+        &mut self,
+        target: &JSObject,
+        source: &JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        match source {
+            JSValueVariant::JSObject(js_object) => {
+                for key in js_object.enumerable_keys() {
+                    let value = self.handle_member(source, &key)?;
+                    target.define_property(&key, PropertyDescriptor::from(value));
+                }
+            }
+            JSValueVariant::JSArray(js_array) => {
+                for index in js_array.indices() {
+                    let value = js_array.get(index).unwrap_or(JSValueVariant::Undefined);
+                    target.define_property(&index.to_string(), PropertyDescriptor::from(value));
+                }
+                // Indices the object holds come with its enumerable keys.
+                for key in js_array.object().enumerable_keys() {
+                    let value = self.handle_member(source, &key)?;
+                    target.define_property(&key, PropertyDescriptor::from(value));
+                }
+            }
+            JSValueVariant::JSString(js_string) => {
+                for (index, &code_unit) in js_string.code_units().iter().enumerate() {
+                    target.define_property(
+                        &index.to_string(),
+                        PropertyDescriptor::from(JSValueVariant::from(String::from_utf16_lossy(
+                            &[code_unit],
+                        ))),
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// `this` is bound in the scope of every call to a function that is not
+    /// an arrow function, and in the global scope.
+    fn handle_this(&self) -> Result<JSValueVariant, EndiumError> {
+        match self.scope.borrow().lookup(THIS) {
+            Some(this) if !this.is_initialized() => Err(Self::this_uninitialized()),
+            Some(this) => Ok(this.value().clone()),
+            None => Ok(JSValueVariant::Undefined),
+        }
+    }
+
+    fn this_uninitialized() -> EndiumError {
+        EndiumError::reference_error(String::from(
+            "Must call super constructor in derived class before accessing 'this' or returning from derived constructor",
+        ))
+    }
+
+    fn handle_identifier(&mut self, identifier: &str) -> Result<JSValueVariant, EndiumError> {
+        match self.scope.borrow().lookup(identifier) {
+            Some(variable) if !variable.is_initialized() => {
+                Err(EndiumError::uninitialized(identifier))
+            }
+            Some(variable) => Ok(variable.value().clone()),
+            None => Err(EndiumError::not_defined(identifier)),
+        }
+    }
+
+    fn evaluate_member_key(&mut self, property: &MemberProperty) -> Result<ObjectKey, EndiumError> {
+        match property {
+            MemberProperty::Identifier(name) => Ok(ObjectKey::Property(name.to_string())),
+            MemberProperty::Computed(expression) => {
+                let key = self.evaluate_expression(expression)?;
+                Ok(ObjectKey::Property(self.string_of(&key)?))
+            }
+            // The parser makes sure an enclosing class declares the name.
+            MemberProperty::Private(name) => {
+                let id = match self.scope.borrow().lookup(&format!("#{}", name)) {
+                    Some(id) => operations::to_string(id.value()),
+                    None => String::new(),
+                };
+
+                Ok(ObjectKey::Private {
+                    name: name.to_string(),
+                    id,
+                })
+            }
+        }
+    }
+
+    /// Evaluates a member access or call that may be a link of an optional
+    /// chain, and any other expression as usual. `None` means a `?.` in the
+    /// chain met null or undefined, which skips the rest of the chain.
+    fn evaluate_chain(
+        &mut self,
+        expression: &Expression,
+    ) -> Result<Option<JSValueVariant>, EndiumError> {
+        match expression {
+            Expression::Member { position, .. } | Expression::SuperMember { position, .. } => {
+                let reference = self
+                    .evaluate_chain_reference(expression, false)
+                    .map_err(|error| error.at(*position))?;
+
+                match reference {
+                    Some(reference) => self
+                        .get_reference(&reference)
+                        .map(Some)
+                        .map_err(|error| error.at(*position)),
+                    None => Ok(None),
+                }
+            }
+            Expression::Call {
+                callee,
+                arguments,
+                optional,
+                position,
+            } => self
+                .handle_function_execution(callee, arguments, *optional, *position)
+                .map_err(|error| error.at(*position)),
+            _ => self.evaluate_expression(expression).map(Some),
+        }
+    }
+
+    /// Evaluates the object and key of a member expression that is written
+    /// to, which can not be part of an optional chain.
+    fn evaluate_member_reference(
+        &mut self,
+        target: &Expression,
+        setting: bool,
+    ) -> Result<MemberReference, EndiumError> {
+        match self.evaluate_chain_reference(target, setting)? {
+            Some(reference) => Ok(reference),
+            None => Err(EndiumError::syntax_error(String::from(
+                "Invalid member expression",
+            ))),
+        }
+    }
+
+    /// Evaluates the object and key of `object.key` or `super.key`, checking
+    /// that the object can have properties. `None` means the access is part
+    /// of an optional chain that was skipped.
+    fn evaluate_chain_reference(
+        &mut self,
+        target: &Expression,
+        setting: bool,
+    ) -> Result<Option<MemberReference>, EndiumError> {
+        let (object, property, receiver) = match target {
+            Expression::Member {
+                object,
+                property,
+                optional,
+                ..
+            } => {
+                let Some(object) = self.evaluate_chain(object)? else {
+                    return Ok(None);
+                };
+                if *optional && matches!(object, JSValueVariant::Null | JSValueVariant::Undefined) {
+                    return Ok(None);
+                }
+
+                (object.clone(), property, object)
+            }
+            Expression::SuperMember { property, .. } => {
+                let receiver = self.handle_this()?;
+                let home_object = self
+                    .scope
+                    .borrow()
+                    .lookup(HOME_OBJECT)
+                    .map(|home_object| home_object.value().clone());
+
+                let object = match home_object
+                    .as_ref()
+                    .and_then(Self::object_of)
+                    .and_then(JSObject::prototype)
+                {
+                    Some(prototype) => JSValueVariant::JSObject(prototype),
+                    None => JSValueVariant::Null,
+                };
+
+                (object, property, receiver)
+            }
+            _ => {
+                return Err(EndiumError::syntax_error(String::from(
+                    "Invalid member expression",
+                )));
+            }
+        };
+
+        let key = self.evaluate_member_key(property)?;
+        Self::check_member_access(&object, &key.name(), setting)?;
+
+        Ok(Some(MemberReference {
+            object,
+            key,
+            receiver,
+        }))
+    }
+
+    fn get_reference(
+        &mut self,
+        reference: &MemberReference,
+    ) -> Result<JSValueVariant, EndiumError> {
+        match &reference.key {
+            ObjectKey::Property(key) => {
+                self.get_member(&reference.object, key, &reference.receiver)
+            }
+            ObjectKey::Private { name, id } => self.get_private(&reference.object, name, id),
+        }
+    }
+
+    fn put_reference(
+        &mut self,
+        reference: &MemberReference,
+        value: JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        match &reference.key {
+            ObjectKey::Property(key) => {
+                self.assign_member(&reference.object, key, value, &reference.receiver)
+            }
+            ObjectKey::Private { name, id } => self.set_private(&reference.object, name, id, value),
+        }
+    }
+
+    /// The object holding the properties of `value`, for the values that are
+    /// objects.
+    pub fn object_of(value: &JSValueVariant) -> Option<&JSObject> {
+        match value {
+            JSValueVariant::JSObject(js_object) => Some(js_object),
+            JSValueVariant::JSFunction(js_function) => Some(js_function.object()),
+            JSValueVariant::JSArray(js_array) => Some(js_array.object()),
+            _ => None,
+        }
+    }
+
+    fn get_private(
+        &mut self,
+        object: &JSValueVariant,
+        name: &str,
+        id: &str,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let Some(element) =
+            Self::object_of(object).and_then(|object| object.get_private_element(id))
+        else {
+            return Err(EndiumError::type_error(format!(
+                "Cannot read private member #{} from an object whose class did not declare it",
+                name
+            )));
+        };
+
+        match element.kind() {
+            PropertyKind::Data { value, .. } => Ok(value.clone()),
+            PropertyKind::Accessor {
+                get: Some(JSValueVariant::JSFunction(getter)),
+                ..
+            } => self.call_function(getter, object.clone(), vec![]),
+            PropertyKind::Accessor { .. } => Err(EndiumError::type_error(format!(
+                "'#{}' was defined without a getter",
+                name
+            ))),
+        }
+    }
+
+    fn set_private(
+        &mut self,
+        object: &JSValueVariant,
+        name: &str,
+        id: &str,
+        value: JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        let Some(js_object) = Self::object_of(object) else {
+            return Err(Self::undeclared_private_write(name));
+        };
+        let Some(element) = js_object.get_private_element(id) else {
+            return Err(Self::undeclared_private_write(name));
+        };
+
+        match element.kind() {
+            PropertyKind::Data { writable: true, .. } => {
+                js_object.define_private_element(id, PropertyDescriptor::from(value));
+                Ok(())
+            }
+            PropertyKind::Data { .. } => Err(EndiumError::type_error(String::from(
+                "Private method is not writable",
+            ))),
+            PropertyKind::Accessor {
+                set: Some(JSValueVariant::JSFunction(setter)),
+                ..
+            } => {
+                self.call_function(setter, object.clone(), vec![value])?;
+                Ok(())
+            }
+            PropertyKind::Accessor { .. } => Err(EndiumError::type_error(format!(
+                "'#{}' was defined without a setter",
+                name
+            ))),
+        }
+    }
+
+    fn undeclared_private_write(name: &str) -> EndiumError {
+        EndiumError::type_error(format!(
+            "Cannot write private member #{} to an object whose class did not declare it",
+            name
+        ))
+    }
+
+    /// Null and undefined have no properties, so reading or writing one is a
+    /// TypeError.
+    fn check_member_access(
+        object: &JSValueVariant,
+        property_key: &str,
+        setting: bool,
+    ) -> Result<(), EndiumError> {
+        if !matches!(object, JSValueVariant::Null | JSValueVariant::Undefined) {
+            return Ok(());
+        }
+
+        let (action, verb) = if setting {
+            ("set", "setting")
+        } else {
+            ("read", "reading")
+        };
+
+        Err(EndiumError::type_error(format!(
+            "Cannot {} properties of {} ({} '{}')",
+            action,
+            operations::to_string(object),
+            verb,
+            property_key
+        )))
+    }
+
+    /// Reads a property of `object`, running its getter if it has one.
+    pub fn handle_member(
+        &mut self,
+        object: &JSValueVariant,
+        property_key: &str,
+    ) -> Result<JSValueVariant, EndiumError> {
+        self.get_member(object, property_key, object)
+    }
+
+    /// Reads a property of `object`, with `receiver` as the `this` of a
+    /// getter.
+    fn get_member(
+        &mut self,
+        object: &JSValueVariant,
+        property_key: &str,
+        receiver: &JSValueVariant,
+    ) -> Result<JSValueVariant, EndiumError> {
+        match object {
+            JSValueVariant::JSString(js_string) => {
+                if let Some(value) = js_string.get_property(property_key) {
+                    return Ok(value.clone());
+                }
+
+                // Strings index by UTF-16 code unit.
+                match array_index(property_key)
+                    .and_then(|index| js_string.code_unit(index as usize))
+                {
+                    Some(code_unit) => {
+                        Ok(JSValueVariant::from(String::from_utf16_lossy(&[code_unit])))
+                    }
+                    None => {
+                        let string_prototype = self.string_prototype.clone();
+                        self.handle_object_member(&string_prototype, property_key, receiver)
+                    }
+                }
+            }
+            JSValueVariant::JSObject(js_object) => {
+                self.handle_object_member(js_object, property_key, receiver)
+            }
+            JSValueVariant::JSFunction(js_function) => {
+                self.handle_object_member(js_function.object(), property_key, receiver)
+            }
+            JSValueVariant::JSArray(js_array) => {
+                if property_key == "length" {
+                    return Ok(JSValueVariant::from(js_array.length() as f64));
+                }
+
+                match array_index(property_key).and_then(|index| js_array.get(index)) {
+                    Some(element) => Ok(element),
+                    None => self.handle_object_member(js_array.object(), property_key, receiver),
+                }
+            }
+            _ => Ok(JSValueVariant::Undefined),
+        }
+    }
+
+    fn handle_object_member(
+        &mut self,
+        js_object: &JSObject,
+        property_key: &str,
+        receiver: &JSValueVariant,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let Some(property) = js_object.find_property(property_key) else {
+            return Ok(JSValueVariant::Undefined);
+        };
+
+        match property.kind() {
+            PropertyKind::Data { value, .. } => Ok(value.clone()),
+            PropertyKind::Accessor {
+                get: Some(JSValueVariant::JSFunction(getter)),
+                ..
+            } => self.call_function(getter, receiver.clone(), vec![]),
+            PropertyKind::Accessor { .. } => Ok(JSValueVariant::Undefined),
+        }
+    }
+
+    /// Calls `callee`, or returns `None` when an optional chain it is part
+    /// of is skipped, which `optional` does for a callee that is null or
+    /// undefined.
+    fn handle_function_execution(
+        &mut self,
+        callee: &Expression,
+        arguments: &[Argument],
+        optional: bool,
+        position: Position,
+    ) -> Result<Option<JSValueVariant>, EndiumError> {
+        // Calling a member passes the object it was read from as `this`, even
+        // through the parentheses of `(a?.b)()`.
+        let (function, this) = match callee {
+            Expression::Member { .. } | Expression::SuperMember { .. } => {
+                match self.evaluate_chain_reference(callee, false)? {
+                    Some(reference) => (self.get_reference(&reference)?, reference.receiver),
+                    None => return Ok(None),
+                }
+            }
+            Expression::OptionalChain(chain)
+                if matches!(chain.as_ref(), Expression::Member { .. }) =>
+            {
+                match self.evaluate_chain_reference(chain, false)? {
+                    Some(reference) => (self.get_reference(&reference)?, reference.receiver),
+                    None => (JSValueVariant::Undefined, JSValueVariant::Undefined),
+                }
+            }
+            _ => match self.evaluate_chain(callee)? {
+                Some(function) => (function, JSValueVariant::Undefined),
+                None => return Ok(None),
+            },
+        };
+
+        if optional && matches!(function, JSValueVariant::Null | JSValueVariant::Undefined) {
+            return Ok(None);
+        }
+
+        let argument_values = self.evaluate_arguments(arguments)?;
+
+        self.position = position;
+
+        match function {
+            JSValueVariant::JSFunction(js_func) => self
+                .call_function(&js_func, this, argument_values)
+                .map(Some),
+            _ => Err(EndiumError::type_error(format!(
+                "{} is not a function",
+                operations::to_string(&function)
+            ))),
+        }
+    }
+
+    fn evaluate_arguments(
+        &mut self,
+        arguments: &[Argument],
+    ) -> Result<Vec<JSValueVariant>, EndiumError> {
+        let mut argument_values: Vec<JSValueVariant> = vec![];
+
+        for argument in arguments {
+            match argument {
+                Argument::Expression(expression) => {
+                    argument_values.push(self.evaluate_expression(expression)?)
+                }
+                Argument::Spread(expression) => {
+                    let iterable = self.evaluate_expression(expression)?;
+                    argument_values.extend(self.iterate(&iterable)?);
+                }
+            }
+        }
+
+        Ok(argument_values)
+    }
+
+    fn handle_new(
+        &mut self,
+        callee: &Expression,
+        arguments: &[Argument],
+        position: Position,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let constructor = self.evaluate_expression(callee)?;
+        let argument_values = self.evaluate_arguments(arguments)?;
+
+        self.position = position;
+
+        match &constructor {
+            JSValueVariant::JSFunction(js_func) if js_func.is_constructor() => {
+                self.construct(js_func, argument_values, js_func)
+            }
+            _ => Err(EndiumError::type_error(format!(
+                "{} is not a constructor",
+                operations::to_string(&constructor)
+            ))),
+        }
+    }
+
+    /// `super(...)`: constructs `this` with the parent class, passing on the
+    /// `new.target` of the running constructor, then initializes the fields
+    /// of the class.
+    fn handle_super_call(
+        &mut self,
+        arguments: &[Argument],
+        position: Position,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let lookup = |name: &str| match self.scope.borrow().lookup(name) {
+            Some(state) => state.value().clone(),
+            None => JSValueVariant::Undefined,
+        };
+        let super_constructor = lookup(SUPER_CONSTRUCTOR);
+        let new_target = lookup(NEW_TARGET);
+        let class = lookup(CLASS_CONSTRUCTOR);
+
+        let argument_values = self.evaluate_arguments(arguments)?;
+
+        self.position = position;
+
+        let (JSValueVariant::JSFunction(super_constructor), JSValueVariant::JSFunction(new_target)) =
+            (&super_constructor, &new_target)
+        else {
+            return Err(EndiumError::type_error(format!(
+                "Super constructor {} of anonymous class is not a constructor",
+                operations::to_string(&super_constructor)
+            )));
+        };
+
+        let this = self.construct(super_constructor, argument_values, new_target)?;
+
+        if !self.scope.borrow_mut().initialize(THIS, this.clone()) {
+            return Err(EndiumError::reference_error(String::from(
+                "Super constructor may only be called once",
+            )));
+        }
+
+        if let JSValueVariant::JSFunction(class) = class {
+            self.initialize_instance(&this, &class)?;
+        }
+
+        Ok(this)
+    }
+
+    pub fn call_function(
+        &mut self,
+        function: &JSFunction,
+        this: JSValueVariant,
+        arguments: Vec<JSValueVariant>,
+    ) -> Result<JSValueVariant, EndiumError> {
+        if let FunctionKind::Class { .. } = function.kind() {
+            let name = match function.name() {
+                "" => String::new(),
+                name => format!(" {}", name),
+            };
+
+            return Err(EndiumError::type_error(format!(
+                "Class constructor{} cannot be invoked without 'new'",
+                name
+            )));
+        }
+
+        self.invoke(function, Some(this), arguments, None)
+    }
+
+    /// Calls `function` as a constructor. `new_target` is the constructor
+    /// `new` was applied to, whose `prototype` the new object inherits from,
+    /// and which `super(...)` passes on to the parent class.
+    pub fn construct(
+        &mut self,
+        function: &JSFunction,
+        arguments: Vec<JSValueVariant>,
+        new_target: &JSFunction,
+    ) -> Result<JSValueVariant, EndiumError> {
+        // `new` on a bound function constructs its target, ignoring the bound
+        // `this`.
+        if let FunctionKind::Bound {
+            target,
+            arguments: bound_arguments,
+            ..
+        } = function.kind()
+        {
+            let new_target = if function.ptr_eq(new_target) {
+                target
+            } else {
+                new_target
+            };
+            let arguments = [bound_arguments.clone(), arguments].concat();
+
+            return self.construct(target, arguments, new_target);
+        }
+
+        let this = match function.kind() {
+            // A derived class leaves creating `this` to `super(...)`.
+            FunctionKind::Class { definition, .. } if definition.is_derived() => None,
+            // Natives create the objects they return themselves.
+            FunctionKind::Native { .. } => Some(JSValueVariant::Undefined),
+            _ => {
+                let prototype = self.prototype_from_constructor(new_target)?;
+                Some(JSValueVariant::JSObject(JSObject::from(Some(prototype))))
+            }
+        };
+
+        let result = self.invoke(function, this, arguments, Some(new_target))?;
+
+        // A native constructor reached through `super(...)` creates an
+        // instance of the class `new` was applied to.
+        if let FunctionKind::Native { .. } = function.kind()
+            && !function.ptr_eq(new_target)
+            && let JSValueVariant::JSObject(js_object) = &result
+        {
+            let prototype = self.prototype_from_constructor(new_target)?;
+            js_object.set_prototype(Some(prototype));
+        }
+
+        Ok(result)
+    }
+
+    /// The `prototype` of a constructor, or `Object.prototype` when it is not
+    /// an object.
+    fn prototype_from_constructor(
+        &mut self,
+        constructor: &JSFunction,
+    ) -> Result<JSObject, EndiumError> {
+        let constructor = JSValueVariant::JSFunction(constructor.clone());
+
+        match self.handle_member(&constructor, "prototype")? {
+            JSValueVariant::JSObject(prototype) => Ok(prototype),
+            _ => Ok(self.object_prototype.clone()),
+        }
+    }
+
+    /// Runs a call to `function`. `this` is `None` in a derived class
+    /// constructor, until `super(...)` binds it, and `new_target` is `None`
+    /// unless the function is being constructed.
+    fn invoke(
+        &mut self,
+        function: &JSFunction,
+        this: Option<JSValueVariant>,
+        arguments: Vec<JSValueVariant>,
+        new_target: Option<&JSFunction>,
+    ) -> Result<JSValueVariant, EndiumError> {
+        if self.call_stack.size() >= MAX_CALL_STACK_SIZE {
+            return Err(EndiumError::range_error(String::from(
+                "Maximum call stack size exceeded",
+            )));
+        }
+
+        let (node, scope, is_arrow) = match function.kind() {
+            FunctionKind::Script { node, scope } | FunctionKind::Class { node, scope, .. } => {
+                (Rc::clone(node), Rc::clone(scope), false)
+            }
+            FunctionKind::Arrow { node, scope } => (Rc::clone(node), Rc::clone(scope), true),
+            FunctionKind::Native { function, .. } => {
+                let this = this.unwrap_or(JSValueVariant::Undefined);
+                return function(self, &this, &arguments);
+            }
+            FunctionKind::Bound {
+                target,
+                this,
+                arguments: bound_arguments,
+                ..
+            } => {
+                let arguments = [bound_arguments.clone(), arguments].concat();
+                return self.call_function(target, this.as_ref().clone(), arguments);
+            }
+        };
+
+        let mut frame = Scope::new(Some(scope));
+
+        // Arrow functions see the `this`, `arguments` and `new.target` of
+        // their surroundings.
+        if !is_arrow {
+            let arguments_object = JSObject::arguments(Some(self.object_prototype.clone()));
+            for (index, argument) in arguments.iter().enumerate() {
+                arguments_object.set_property(&index.to_string(), argument.clone());
+            }
+            arguments_object.set_property("length", JSValueVariant::from(arguments.len() as f64));
+
+            frame.insert_state(
+                String::from("arguments"),
+                State::new(JSValueVariant::JSObject(arguments_object), true),
+            );
+            frame.insert_state(
+                String::from(THIS),
+                match &this {
+                    Some(this) => State::new(this.clone(), false),
+                    None => State::uninitialized(false),
+                },
+            );
+            frame.insert_state(
+                String::from(NEW_TARGET),
+                State::new(
+                    match new_target {
+                        Some(new_target) => JSValueVariant::JSFunction(new_target.clone()),
+                        None => JSValueVariant::Undefined,
+                    },
+                    false,
+                ),
+            );
+        }
+
+        let frame = Rc::new(RefCell::new(frame));
+        let caller_scope = std::mem::replace(&mut self.scope, Rc::clone(&frame));
+        self.call_stack.push(CallFrame {
+            caller_scope,
+            function_name: function.name().to_string(),
+            call_position: self.position,
+        });
+
+        // A base class initializes its fields before the constructor body runs.
+        let fields = match (&this, new_target) {
+            (Some(this), Some(_)) => self.initialize_instance(this, function),
+            _ => Ok(()),
+        };
+
+        // Engine failures are turned into exceptions while this call is still
+        // on the stack, so their stack trace starts here.
+        let completion = match fields.and_then(|()| self.execute_function_body(&node, arguments)) {
+            Ok(completion) => Ok(completion),
+            Err(error) => Err(self.exception(error)),
+        };
+
+        if let Some(call_frame) = self.call_stack.pop() {
+            // Parameter defaults leave the body in a scope of its own.
+            if !Rc::ptr_eq(&self.scope, &frame) {
+                self.leave_scope(Rc::clone(&frame));
+            }
+            self.scope = call_frame.caller_scope;
+            self.position = call_frame.call_position;
+        }
+
+        let result = self.call_result(completion, this, new_target, &frame);
+        self.collector.release(frame);
+
+        result
+    }
+
+    /// The value a call evaluates to, given how its body completed.
+    fn call_result(
+        &mut self,
+        completion: Result<Completion, EndiumError>,
+        this: Option<JSValueVariant>,
+        new_target: Option<&JSFunction>,
+        frame: &Rc<RefCell<Scope>>,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let value = match completion? {
+            Completion::Return(value) => value,
+            // The parser keeps `break` and `continue` from leaving a function body.
+            Completion::Normal | Completion::Break(_) | Completion::Continue(_) => {
+                JSValueVariant::Undefined
+            }
+        };
+
+        // A constructor that returns an object replaces the one it constructed.
+        if new_target.is_none() || Self::object_of(&value).is_some() {
+            return Ok(value);
+        }
+
+        if this.is_none() && !matches!(value, JSValueVariant::Undefined) {
+            return Err(self.exception(EndiumError::type_error(String::from(
+                "Derived constructors may only return object or undefined",
+            ))));
+        }
+
+        match frame.borrow().get_state(THIS) {
+            Some(this) if this.is_initialized() => Ok(this.value().clone()),
+            _ => Err(self.exception(Self::this_uninitialized())),
+        }
+    }
+
+    /// Binds the parameters of a call in the current scope, then runs the
+    /// function body.
+    fn execute_function_body(
+        &mut self,
+        node: &FunctionNode,
+        mut arguments: Vec<JSValueVariant>,
+    ) -> Result<Completion, EndiumError> {
+        let has_parameter_expressions = node.params.iter().any(Pattern::has_expressions);
+
+        // A default can only refer to the parameters before it.
+        if has_parameter_expressions {
+            for param in node.params.iter() {
+                for name in param.bound_names() {
+                    self.scope
+                        .borrow_mut()
+                        .insert_state(name.to_string(), State::uninitialized(true));
+                }
+            }
+        }
+
+        for (index, param) in node.params.iter().enumerate() {
+            let value = match param {
+                Pattern::Rest(_) => JSValueVariant::JSArray(
+                    self.create_array(arguments.drain(index.min(arguments.len())..).collect()),
+                ),
+                _ => arguments
+                    .get(index)
+                    .cloned()
+                    .unwrap_or(JSValueVariant::Undefined),
+            };
+
+            self.bind_pattern(param, value, Binding::from(VariableKind::Let))?;
+        }
+
+        if has_parameter_expressions {
+            // Closures created by defaults do not see the body's declarations,
+            // which get a scope of their own. A `var` named after a parameter
+            // starts out with its value.
+            let mut body_scope = Scope::new(Some(Rc::clone(&self.scope)));
+
+            for name in node.var_names.iter() {
+                let value = match self.scope.borrow().get_state(name) {
+                    Some(state) => state.value().clone(),
+                    None => JSValueVariant::Undefined,
+                };
+                body_scope.insert_state(name.to_string(), State::new(value, true));
+            }
+
+            self.scope = Rc::new(RefCell::new(body_scope));
+        } else {
+            self.hoist_var_names(&node.var_names);
+        }
+
+        self.declare_lexical(&node.body);
+        self.execute_statements(&node.body)
+    }
+
+    /// Binds the names in `pattern` to the parts of `value` it selects.
+    fn bind_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: JSValueVariant,
+        binding: Binding,
+    ) -> Result<(), EndiumError> {
+        match pattern {
+            Pattern::Identifier(name) => match binding {
+                Binding::Initialize { mutable } => {
+                    self.scope
+                        .borrow_mut()
+                        .insert_state(name.to_string(), State::new(value, mutable));
+                    Ok(())
+                }
+                Binding::Assign => self.assign_variable(name, value),
+            },
+            Pattern::Array(elements) => {
+                let values = self.iterate(&value)?;
+
+                for (index, element) in elements.iter().enumerate() {
+                    match element {
+                        None => {}
+                        Some(Pattern::Rest(target)) => {
+                            let rest = values.get(index..).unwrap_or_default().to_vec();
+                            let rest = JSValueVariant::JSArray(self.create_array(rest));
+                            self.bind_pattern(target, rest, binding)?;
+                        }
+                        Some(element) => {
+                            let value = values
+                                .get(index)
+                                .cloned()
+                                .unwrap_or(JSValueVariant::Undefined);
+                            self.bind_pattern(element, value, binding)?;
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            Pattern::Object { properties, rest } => {
+                if matches!(value, JSValueVariant::Null | JSValueVariant::Undefined) {
+                    let value = operations::to_string(&value);
+                    return Err(EndiumError::type_error(format!(
+                        "Cannot destructure '{}' as it is {}.",
+                        value, value
+                    )));
+                }
+
+                let mut keys: Vec<String> = vec![];
+
+                for property in properties {
+                    let key = self.evaluate_object_key(&property.key)?;
+                    let property_value = self.handle_member(&value, &key)?;
+                    self.bind_pattern(&property.value, property_value, binding)?;
+                    keys.push(key);
+                }
+
+                // The rest object gets the own properties no key picked out.
+                if let Some(rest) = rest {
+                    let rest_object = JSObject::from(Some(self.object_prototype.clone()));
+                    self.copy_data_properties(&rest_object, &value)?;
+                    for key in keys.iter() {
+                        rest_object.delete_property(key);
+                    }
+
+                    self.bind_pattern(rest, JSValueVariant::JSObject(rest_object), binding)?;
+                }
+
+                Ok(())
+            }
+            Pattern::Expression(target) => match target.as_ref() {
+                Expression::Member { position, .. } | Expression::SuperMember { position, .. } => {
+                    let reference = self
+                        .evaluate_member_reference(target, true)
+                        .map_err(|error| error.at(*position))?;
+                    self.put_reference(&reference, value)
+                        .map_err(|error| error.at(*position))
+                }
+                _ => Ok(()),
+            },
+            Pattern::Default { target, default } => {
+                let value = match value {
+                    JSValueVariant::Undefined => self.evaluate_expression(default)?,
+                    value => value,
+                };
+                self.bind_pattern(target, value, binding)
+            }
+            Pattern::Rest(target) => self.bind_pattern(target, value, binding),
+        }
+    }
+
+    fn handle_assignment(
+        &mut self,
+        operator: AssignmentOperator,
+        target: &Expression,
+        value: &Expression,
+    ) -> Result<JSValueVariant, EndiumError> {
+        match target {
+            Expression::Identifier {
+                name: identifier, ..
+            } => {
+                // A logical assignment only stores when it does not short
+                // circuit.
+                if let Some(logical_operator) = operator.logical_operator() {
+                    let current_value = self.handle_identifier(identifier)?;
+                    if Self::short_circuits(logical_operator, &current_value) {
+                        return Ok(current_value);
+                    }
+                }
+
+                let value = match operator.binary_operator() {
+                    Some(binary_operator) => {
+                        let current_value = self.handle_identifier(identifier)?;
+                        let value = self.evaluate_expression(value)?;
+                        self.handle_binary_operation(binary_operator, &current_value, &value)?
+                    }
+                    None => self.evaluate_expression(value)?,
+                };
+
+                self.assign_variable(identifier, value.clone())?;
+                Ok(value)
+            }
+            Expression::Member { .. } | Expression::SuperMember { .. } => {
+                let reference = self.evaluate_member_reference(target, true)?;
+
+                if let Some(logical_operator) = operator.logical_operator() {
+                    let current_value = self.get_reference(&reference)?;
+                    if Self::short_circuits(logical_operator, &current_value) {
+                        return Ok(current_value);
+                    }
+                }
+
+                let value = match operator.binary_operator() {
+                    Some(binary_operator) => {
+                        let current_value = self.get_reference(&reference)?;
+                        let value = self.evaluate_expression(value)?;
+                        self.handle_binary_operation(binary_operator, &current_value, &value)?
+                    }
+                    None => self.evaluate_expression(value)?,
+                };
+
+                self.put_reference(&reference, value.clone())?;
+                Ok(value)
+            }
+            _ => self.evaluate_expression(value),
+        }
+    }
+
+    fn assign_variable(
+        &mut self,
+        identifier: &str,
+        value: JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        self.scope.borrow_mut().assign(identifier, value)
+    }
+
+    /// Writes a property of `object`, running its setter if it has one with
+    /// `receiver` as `this`. A data property is written to the receiver.
+    /// Writes a property of `object` as an assignment does.
+    pub fn set_member(
+        &mut self,
+        object: &JSValueVariant,
+        property_key: &str,
+        value: JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        self.assign_member(object, property_key, value, object)
+    }
+
+    fn assign_member(
+        &mut self,
+        object: &JSValueVariant,
+        property_key: &str,
+        value: JSValueVariant,
+        receiver: &JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        match object {
+            JSValueVariant::JSObject(js_object) => {
+                return self.assign_object_member(js_object, property_key, value, receiver);
+            }
+            JSValueVariant::JSFunction(js_function) => {
+                return self.assign_object_member(
+                    js_function.object(),
+                    property_key,
+                    value,
+                    receiver,
+                );
+            }
+            JSValueVariant::JSArray(js_array) => {
+                if property_key == "length" {
+                    let number = operations::to_number(&value);
+                    let length = operations::to_uint32(number);
+
+                    if length as f64 != number {
+                        return Err(EndiumError::invalid_array_length());
+                    }
+
+                    js_array.set_length(length)?;
+                } else if let Some(index) = array_index(property_key)
+                    && js_array.object().get_own_property(property_key).is_none()
+                {
+                    js_array.set(index, value)?;
+                } else {
+                    return self.assign_object_member(
+                        js_array.object(),
+                        property_key,
+                        value,
+                        receiver,
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn assign_object_member(
+        &mut self,
+        js_object: &JSObject,
+        property_key: &str,
+        value: JSValueVariant,
+        receiver: &JSValueVariant,
+    ) -> Result<(), EndiumError> {
+        let property = js_object.find_property(property_key);
+
+        match property.as_ref().map(PropertyDescriptor::kind) {
+            Some(PropertyKind::Accessor {
+                set: Some(JSValueVariant::JSFunction(setter)),
+                ..
+            }) => {
+                self.call_function(setter, receiver.clone(), vec![value])?;
+            }
+            // Read-only properties, own or inherited, and accessors without a
+            // setter cannot be assigned to.
+            Some(PropertyKind::Accessor { .. }) => {
+                return Err(EndiumError::type_error(format!(
+                    "Cannot set property {} of #<Object> which has only a getter",
+                    property_key
+                )));
+            }
+            Some(PropertyKind::Data {
+                writable: false, ..
+            }) => {
+                return Err(EndiumError::type_error(format!(
+                    "Cannot assign to read only property '{}' of object",
+                    property_key
+                )));
+            }
+            _ => {
+                if let Some(receiver) = Self::object_of(receiver) {
+                    receiver.set_property(property_key, value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_update(
+        &mut self,
+        operator: UpdateOperator,
+        prefix: bool,
+        target: &Expression,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let old_value = match target {
+            Expression::Identifier {
+                name: identifier, ..
+            } => self.handle_identifier(identifier)?,
+            Expression::Member { .. } | Expression::SuperMember { .. } => {
+                let reference = self.evaluate_member_reference(target, false)?;
+                let old_value = self.get_reference(&reference)?;
+                let old_value = self.number_of(&old_value)?;
+                let new_value = Self::apply_update(operator, old_value);
+
+                self.put_reference(&reference, JSValueVariant::from(new_value))?;
+                return Ok(JSValueVariant::from(if prefix {
+                    new_value
+                } else {
+                    old_value
+                }));
+            }
+            _ => return Ok(JSValueVariant::Undefined),
+        };
+
+        let old_value = self.number_of(&old_value)?;
+        let new_value = Self::apply_update(operator, old_value);
+
+        if let Expression::Identifier {
+            name: identifier, ..
+        } = target
+        {
+            self.assign_variable(identifier, JSValueVariant::from(new_value))?;
+        }
+
+        Ok(JSValueVariant::from(if prefix {
+            new_value
+        } else {
+            old_value
+        }))
+    }
+
+    fn apply_update(operator: UpdateOperator, value: f64) -> f64 {
+        match operator {
+            UpdateOperator::Increment => value + 1.0,
+            UpdateOperator::Decrement => value - 1.0,
+        }
+    }
+
+    fn handle_unary(
+        &mut self,
+        operator: UnaryOperator,
+        argument: &Expression,
+    ) -> Result<JSValueVariant, EndiumError> {
+        // `typeof` on an undeclared identifier yields "undefined" instead of throwing.
+        if let (
+            UnaryOperator::Typeof,
+            Expression::Identifier {
+                name: identifier, ..
+            },
+        ) = (operator, argument)
+            && !self.scope.borrow().has_binding(identifier)
+        {
+            return Ok(JSValueVariant::from("undefined"));
+        }
+
+        if operator == UnaryOperator::Delete {
+            return self.handle_delete(argument).map(JSValueVariant::from);
+        }
+
+        let value = self.evaluate_expression(argument)?;
+
+        let result = match operator {
+            UnaryOperator::Minus => JSValueVariant::from(-self.number_of(&value)?),
+            UnaryOperator::Plus => JSValueVariant::from(self.number_of(&value)?),
+            UnaryOperator::Not => JSValueVariant::from(!operations::to_boolean(&value)),
+            UnaryOperator::BitwiseNot => {
+                JSValueVariant::from(!operations::to_int32(self.number_of(&value)?) as f64)
+            }
+            UnaryOperator::Typeof => JSValueVariant::from(operations::type_of(&value)),
+            // `delete` is handled above, as it needs a reference.
+            UnaryOperator::Void | UnaryOperator::Delete => JSValueVariant::Undefined,
+        };
+
+        Ok(result)
+    }
+
+    /// Removes the property `argument` refers to. Anything that is not a
+    /// property reference, or an optional chain that was skipped, is deleted
+    /// trivially. A property that is not configurable can not be deleted.
+    fn handle_delete(&mut self, argument: &Expression) -> Result<bool, EndiumError> {
+        let reference = match argument {
+            Expression::Member { position, .. } => self
+                .evaluate_member_reference(argument, false)
+                .map_err(|error| error.at(*position))?,
+            Expression::OptionalChain(chain)
+                if matches!(chain.as_ref(), Expression::Member { .. }) =>
+            {
+                match self.evaluate_chain_reference(chain, false)? {
+                    Some(reference) => reference,
+                    None => return Ok(true),
+                }
+            }
+            Expression::SuperMember { position, .. } => {
+                return Err(EndiumError::reference_error(String::from(
+                    "Unsupported reference to 'super'",
+                ))
+                .at(*position));
+            }
+            _ => {
+                self.evaluate_expression(argument)?;
+                return Ok(true);
+            }
+        };
+
+        // The parser rejects deleting private names.
+        let ObjectKey::Property(key) = &reference.key else {
+            return Ok(true);
+        };
+
+        self.delete_member(&reference.object, key)?;
+
+        Ok(true)
+    }
+
+    /// Removes a property of `object` as `delete` does, failing when the
+    /// property cannot be deleted.
+    pub fn delete_member(&mut self, object: &JSValueVariant, key: &str) -> Result<(), EndiumError> {
+        let deleted = match object {
+            JSValueVariant::JSArray(js_array) => match array_index(key) {
+                Some(_) if js_array.object().get_own_property(key).is_some() => {
+                    js_array.object().delete_property(key)
+                }
+                Some(index) => {
+                    js_array.delete(index);
+                    true
+                }
+                None if key == "length" => false,
+                None => js_array.object().delete_property(key),
+            },
+            JSValueVariant::JSString(js_string) => {
+                key != "length"
+                    && array_index(key)
+                        .is_none_or(|index| js_string.code_unit(index as usize).is_none())
+            }
+            object => Self::object_of(object).is_none_or(|object| object.delete_property(key)),
+        };
+
+        if !deleted {
+            return Err(EndiumError::type_error(format!(
+                "Cannot delete property '{}' of {}",
+                key,
+                operations::to_string(object)
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn handle_logical(
+        &mut self,
+        operator: LogicalOperator,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let left = self.evaluate_expression(left)?;
+
+        if Self::short_circuits(operator, &left) {
+            Ok(left)
+        } else {
+            self.evaluate_expression(right)
+        }
+    }
+
+    /// Whether `operator` settles on its left operand `left` without
+    /// evaluating the right one.
+    fn short_circuits(operator: LogicalOperator, left: &JSValueVariant) -> bool {
+        match operator {
+            LogicalOperator::And => !operations::to_boolean(left),
+            LogicalOperator::Or => operations::to_boolean(left),
+            LogicalOperator::Nullish => {
+                !matches!(left, JSValueVariant::Null | JSValueVariant::Undefined)
+            }
+        }
+    }
+
+    pub fn handle_binary_operation(
+        &mut self,
+        operator: BinaryOperator,
+        left: &JSValueVariant,
+        right: &JSValueVariant,
+    ) -> Result<JSValueVariant, EndiumError> {
+        let result = match operator {
+            BinaryOperator::InstanceOf => JSValueVariant::from(self.instance_of(left, right)?),
+            BinaryOperator::In => {
+                let Some(object) = Self::object_of(right) else {
+                    let property_key = operations::to_string(left);
+                    return Err(Self::in_operator_error(&property_key, right));
+                };
+                let property_key = self.string_of(left)?;
+
+                let has_property = match right {
+                    JSValueVariant::JSArray(js_array) => {
+                        property_key == "length"
+                            || array_index(&property_key)
+                                .is_some_and(|index| js_array.has_index(index))
+                            || js_array.object().has_property(&property_key)
+                    }
+                    _ => object.has_property(&property_key),
+                };
+
+                JSValueVariant::from(has_property)
+            }
+            BinaryOperator::Addition => {
+                let left = self.ordinary_to_primitive(left)?;
+                let right = self.ordinary_to_primitive(right)?;
+
+                match (&left, &right) {
+                    (JSValueVariant::JSString(_), _) | (_, JSValueVariant::JSString(_)) => {
+                        let mut concatenated = operations::to_string(&left);
+                        concatenated.push_str(&operations::to_string(&right));
+                        JSValueVariant::from(concatenated)
+                    }
+                    _ => JSValueVariant::from(
+                        operations::to_number(&left) + operations::to_number(&right),
+                    ),
+                }
+            }
+            BinaryOperator::Subtraction
+            | BinaryOperator::Multiplication
+            | BinaryOperator::Division
+            | BinaryOperator::Modulo
+            | BinaryOperator::Exponent => {
+                let left = self.number_of(left)?;
+                let right = self.number_of(right)?;
+
+                JSValueVariant::from(match operator {
+                    BinaryOperator::Subtraction => left - right,
+                    BinaryOperator::Multiplication => left * right,
+                    BinaryOperator::Division => left / right,
+                    BinaryOperator::Modulo => left % right,
+                    _ => Self::exponentiate(left, right),
+                })
+            }
+            BinaryOperator::BitwiseAnd
+            | BinaryOperator::BitwiseOr
+            | BinaryOperator::BitwiseXor
+            | BinaryOperator::LeftShift
+            | BinaryOperator::RightShift => {
+                let left = operations::to_int32(self.number_of(left)?);
+                let right = self.number_of(right)?;
+
+                JSValueVariant::from(match operator {
+                    BinaryOperator::BitwiseAnd => left & operations::to_int32(right),
+                    BinaryOperator::BitwiseOr => left | operations::to_int32(right),
+                    BinaryOperator::BitwiseXor => left ^ operations::to_int32(right),
+                    BinaryOperator::LeftShift => left.wrapping_shl(operations::to_uint32(right)),
+                    _ => left.wrapping_shr(operations::to_uint32(right)),
+                } as f64)
+            }
+            BinaryOperator::UnsignedRightShift => {
+                let left = operations::to_uint32(self.number_of(left)?);
+                let right = operations::to_uint32(self.number_of(right)?);
+
+                JSValueVariant::from(left.wrapping_shr(right) as f64)
+            }
+            BinaryOperator::Equal => JSValueVariant::from(self.loose_equals(left, right)?),
+            BinaryOperator::NotEqual => JSValueVariant::from(!self.loose_equals(left, right)?),
+            BinaryOperator::StrictEqual => {
+                JSValueVariant::from(operations::strict_equals(left, right))
+            }
+            BinaryOperator::StrictNotEqual => {
+                JSValueVariant::from(!operations::strict_equals(left, right))
+            }
+            BinaryOperator::LessThan
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::GreaterThan
+            | BinaryOperator::GreaterThanOrEqual => {
+                // The left operand is always converted first.
+                let left = self.ordinary_to_primitive(left)?;
+                let right = self.ordinary_to_primitive(right)?;
+
+                // An undefined result, from NaN, makes every comparison false.
+                JSValueVariant::from(
+                    match operator {
+                        BinaryOperator::LessThan => operations::is_less_than(&left, &right),
+                        BinaryOperator::GreaterThan => operations::is_less_than(&right, &left),
+                        BinaryOperator::LessThanOrEqual => {
+                            operations::is_less_than(&right, &left).map(|result| !result)
+                        }
+                        _ => operations::is_less_than(&left, &right).map(|result| !result),
+                    } == Some(true),
+                )
+            }
+        };
+
+        Ok(result)
+    }
+
+    /// ToPrimitive with the number hint, which is also the default for
+    /// ordinary objects: tries `valueOf` and then `toString`, and takes the
+    /// first primitive either returns.
+    pub fn ordinary_to_primitive(
+        &mut self,
+        value: &JSValueVariant,
+    ) -> Result<JSValueVariant, EndiumError> {
+        self.primitive_of(value, ["valueOf", "toString"])
+    }
+
+    /// ToPrimitive, trying the methods in the order of the hint. Objects that
+    /// have neither method are rendered the built-in way, and arrays are
+    /// joined.
+    fn primitive_of(
+        &mut self,
+        value: &JSValueVariant,
+        method_names: [&str; 2],
+    ) -> Result<JSValueVariant, EndiumError> {
+        if Self::object_of(value).is_none() {
+            return Ok(operations::to_primitive(value));
+        }
+
+        let mut has_method = false;
+
+        for method_name in method_names {
+            if let JSValueVariant::JSFunction(method) = self.handle_member(value, method_name)? {
+                has_method = true;
+
+                let result = self.call_function(&method, value.clone(), vec![])?;
+                if Self::object_of(&result).is_none() {
+                    return Ok(result);
+                }
+            }
+        }
+
+        match value {
+            _ if has_method => Err(EndiumError::type_error(String::from(
+                "Cannot convert object to primitive value",
+            ))),
+            JSValueVariant::JSArray(js_array) => {
+                Ok(JSValueVariant::from(self.join(js_array, ",")?))
+            }
+            _ => Ok(operations::to_primitive(value)),
+        }
+    }
+
+    /// ToNumber, with an object converted by ToPrimitive first.
+    pub fn number_of(&mut self, value: &JSValueVariant) -> Result<f64, EndiumError> {
+        let primitive = self.ordinary_to_primitive(value)?;
+        Ok(operations::to_number(&primitive))
+    }
+
+    /// ToString, with an object converted by ToPrimitive with the string
+    /// hint, which tries `toString` before `valueOf`.
+    pub fn string_of(&mut self, value: &JSValueVariant) -> Result<String, EndiumError> {
+        let primitive = self.primitive_of(value, ["toString", "valueOf"])?;
+        Ok(operations::to_string(&primitive))
+    }
+
+    /// The present elements of `js_array` with their indices, in ascending
+    /// order. Those the array's object holds are read through their getters.
+    pub fn array_entries(
+        &mut self,
+        js_array: &JSArray,
+    ) -> Result<Vec<(u32, JSValueVariant)>, EndiumError> {
+        if js_array.is_plain() {
+            return Ok(js_array.entries());
+        }
+
+        let array = JSValueVariant::JSArray(js_array.clone());
+        js_array
+            .own_indices()
+            .into_iter()
+            .filter(|index| *index < js_array.length())
+            .map(|index| Ok((index, self.handle_member(&array, &index.to_string())?)))
+            .collect()
+    }
+
+    /// Array.prototype.join. Holes, null and undefined become empty strings,
+    /// and so does an array found inside itself.
+    pub fn join(&mut self, js_array: &JSArray, separator: &str) -> Result<String, EndiumError> {
+        if self.joining.contains(&js_array.id()) {
+            return Ok(String::new());
+        }
+
+        self.joining.push(js_array.id());
+        let joined = self.join_elements(js_array, separator);
+        self.joining.pop();
+
+        joined
+    }
+
+    /// Only the present elements are visited, with the separators between
+    /// them written at once. Fails once the result would be longer than
+    /// `MAX_STRING_LENGTH`.
+    fn join_elements(
+        &mut self,
+        js_array: &JSArray,
+        separator: &str,
+    ) -> Result<String, EndiumError> {
+        let separators = js_array.length().saturating_sub(1) as usize;
+        let mut joined_length = separator.encode_utf16().count().saturating_mul(separators);
+
+        if joined_length > operations::MAX_STRING_LENGTH {
+            return Err(EndiumError::invalid_string_length());
+        }
+
+        let mut joined = String::new();
+        let mut written = 0;
+
+        for (index, element) in self.array_entries(js_array)? {
+            joined.push_str(&separator.repeat(index as usize - written));
+            written = index as usize;
+
+            if !matches!(element, JSValueVariant::Null | JSValueVariant::Undefined) {
+                let string = self.string_of(&element)?;
+
+                joined_length += string.encode_utf16().count();
+                if joined_length > operations::MAX_STRING_LENGTH {
+                    return Err(EndiumError::invalid_string_length());
+                }
+
+                joined.push_str(&string);
+            }
+        }
+
+        joined.push_str(&separator.repeat(separators - written));
+
+        Ok(joined)
+    }
+
+    /// IsLooselyEqual, with an object compared to a primitive converted by
+    /// ToPrimitive first.
+    pub fn loose_equals(
+        &mut self,
+        left: &JSValueVariant,
+        right: &JSValueVariant,
+    ) -> Result<bool, EndiumError> {
+        let is_object = |value: &JSValueVariant| Self::object_of(value).is_some();
+        let is_nullish = |value: &JSValueVariant| {
+            matches!(value, JSValueVariant::Null | JSValueVariant::Undefined)
+        };
+
+        if is_object(left) && !is_object(right) && !is_nullish(right) {
+            let left = self.ordinary_to_primitive(left)?;
+            return Ok(operations::loose_equals(&left, right));
+        }
+        if is_object(right) && !is_object(left) && !is_nullish(left) {
+            let right = self.ordinary_to_primitive(right)?;
+            return Ok(operations::loose_equals(left, &right));
+        }
+
+        Ok(operations::loose_equals(left, right))
+    }
+
+    /// The error for `in` applied to something that can not have properties.
+    fn in_operator_error(property_key: &str, value: &JSValueVariant) -> EndiumError {
+        EndiumError::type_error(format!(
+            "Cannot use 'in' operator to search for '{}' in {}",
+            property_key,
+            operations::to_string(value)
+        ))
+    }
+
+    /// Whether the `prototype` of `constructor` is on the prototype chain of
+    /// `value`.
+    fn instance_of(
+        &mut self,
+        value: &JSValueVariant,
+        constructor: &JSValueVariant,
+    ) -> Result<bool, EndiumError> {
+        let JSValueVariant::JSFunction(js_function) = constructor else {
+            return Err(EndiumError::type_error(String::from(
+                "Right-hand side of 'instanceof' is not callable",
+            )));
+        };
+
+        // A bound function has the instances of its target.
+        if let FunctionKind::Bound { target, .. } = js_function.kind() {
+            let target = JSValueVariant::JSFunction(target.as_ref().clone());
+            return self.instance_of(value, &target);
+        }
+
+        let Some(object) = Self::object_of(value) else {
+            return Ok(false);
+        };
+
+        let prototype = match self.handle_member(constructor, "prototype")? {
+            JSValueVariant::JSObject(prototype) => prototype,
+            prototype => {
+                return Err(EndiumError::type_error(format!(
+                    "Function has non-object prototype '{}' in instanceof check",
+                    operations::to_string(&prototype)
+                )));
+            }
+        };
+
+        let mut ancestor = object.prototype();
+        while let Some(current) = ancestor {
+            if current.ptr_eq(&prototype) {
+                return Ok(true);
+            }
+            ancestor = current.prototype();
+        }
+
+        Ok(false)
+    }
+
+    fn exponentiate(base: f64, exponent: f64) -> f64 {
+        // Unlike `powf`, JS yields NaN for `1 ** NaN` and `(-1) ** Infinity`.
+        if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+            f64::NAN
+        } else {
+            base.powf(exponent)
+        }
+    }
+
+    pub fn scope(&self) -> &Rc<RefCell<Scope>> {
+        &self.scope
+    }
+
+    pub fn object_prototype(&self) -> &JSObject {
+        &self.object_prototype
+    }
+
+    pub fn array_prototype(&self) -> &JSObject {
+        &self.array_prototype
+    }
+
+    pub fn function_prototype(&self) -> &JSObject {
+        &self.function_prototype
+    }
+
+    pub fn string_prototype(&self) -> &JSObject {
+        &self.string_prototype
+    }
+
+    pub fn error_prototype(&self, name: &str) -> Option<&JSObject> {
+        self.error_prototypes.get(name)
+    }
+
+    /// The calls in progress, innermost first, one `at` line each with the
+    /// position execution has reached in that call.
+    pub fn stack_trace(&self) -> String {
+        let mut lines: Vec<String> = vec![];
+        let mut position = self.position;
+
+        for index in (0..self.call_stack.size()).rev() {
+            if let Some(call_frame) = self.call_stack.peek_at(index) {
+                let function_name = match call_frame.function_name.as_str() {
+                    "" => "<anonymous>",
+                    function_name => function_name,
+                };
+
+                lines.push(format!("    at {} ({})", function_name, position));
+                position = call_frame.call_position;
+            }
+        }
+
+        lines.push(format!("    at <global> ({})", position));
+
+        lines.join("\n")
+    }
+
+    pub fn create_array(&self, elements: Vec<JSValueVariant>) -> JSArray {
+        JSArray::from(
+            elements.into_iter().map(Some).collect(),
+            Some(self.array_prototype.clone()),
+        )
+    }
+
+    fn global_scope() -> Scope {
+        let mut global_scope = Scope::new(None);
+
+        global_scope.insert_state(
+            String::from("NaN"),
+            State::new(JSValueVariant::from(f64::NAN), false),
+        );
+        global_scope.insert_state(
+            String::from("Infinity"),
+            State::new(JSValueVariant::from(f64::INFINITY), false),
+        );
+        global_scope.insert_state(
+            String::from(THIS),
+            State::new(JSValueVariant::Undefined, false),
+        );
+
+        global_scope
+    }
+
+    pub fn new(scope: Option<Scope>) -> Self {
+        let scope = match scope {
+            Some(s) => s,
+            None => Self::global_scope(),
+        };
+
+        let object_prototype = JSObject::new();
+        let array_prototype = JSObject::from(Some(object_prototype.clone()));
+        let function_prototype = JSObject::from(Some(object_prototype.clone()));
+        let string_prototype = JSObject::from(Some(object_prototype.clone()));
+
+        let error_prototype = JSObject::from(Some(object_prototype.clone()));
+        let mut error_prototypes: HashMap<String, JSObject> = stdlib::error::NATIVE_ERRORS
+            .iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    JSObject::from(Some(error_prototype.clone())),
+                )
+            })
+            .collect();
+        error_prototypes.insert(String::from("Error"), error_prototype);
+
+        let mut interpretter = Self {
+            scope: Rc::new(RefCell::new(scope)),
+            call_stack: Stack::new(),
+            position: Position::default(),
+            object_prototype,
+            array_prototype,
+            function_prototype,
+            string_prototype,
+            error_prototypes,
+            private_name_count: 0,
+            joining: vec![],
+            collector: Collector::new(),
+        };
+
+        stdlib::object::install(&mut interpretter);
+        stdlib::array::install(&mut interpretter);
+        stdlib::string::install(&mut interpretter);
+        stdlib::error::install(&mut interpretter);
+        stdlib::function::install(&mut interpretter);
+
+        interpretter
     }
 }
@@ -1,16 +1,23 @@
-use crate::tokens::Token;
-use std::error::Error;
+use crate::engine::position::Position;
+use crate::errors::EndiumError;
+use crate::tokens::{PositionedToken, Token};
 
 pub struct Lexer {
     code_chars: Vec<char>,
     position: usize,
+    /// Where the code starts in the source.
+    origin: Position,
+    /// A character index and its source position, moved forward as tokens
+    /// are positioned so that each character is only counted once.
+    cursor: (usize, Position),
 }
 
 impl Lexer {
-    pub fn tokenize(&mut self) -> Result<Vec<Token>, Box<dyn Error>> {
-        let mut tokens: Vec<Token> = vec![];
+    pub fn tokenize(&mut self) -> Result<Vec<PositionedToken>, EndiumError> {
+        let mut tokens: Vec<PositionedToken> = vec![];
 
         let mut current_opcode = String::new();
+        let mut opcode_start = 0;
 
         while self.position < self.code_chars.len() {
             let ch = self.code_chars[self.position];
@@ -19,7 +26,7 @@ impl Lexer {
                 if !current_opcode.is_empty() {
                     let token = self.match_token(&current_opcode)?;
                     if token != Token::NoToken {
-                        tokens.push(token);
+                        self.push_token(&mut tokens, token, opcode_start);
                     }
                     current_opcode.clear();
                 }
@@ -27,21 +34,38 @@ impl Lexer {
                 continue;
             }
 
-            let token_result = self.match_token_char(ch);
+            let starts_number = ch.is_ascii_digit()
+                || (ch == '.' && self.peek_ahead(1).is_some_and(|next| next.is_ascii_digit()));
+
+            if current_opcode.is_empty() && starts_number {
+                let start = self.position;
+                let token = self.parse_number()?;
+                self.push_token(&mut tokens, token, start);
+                continue;
+            }
+
+            let start = self.position;
+            let token_result = self.match_token_char(ch)?;
 
             match token_result {
                 Some(token) => {
                     if !current_opcode.is_empty() {
                         let prev_token = self.match_token(&current_opcode)?;
                         if prev_token != Token::NoToken {
-                            tokens.push(prev_token);
+                            self.push_token(&mut tokens, prev_token, opcode_start);
                         }
                         current_opcode.clear();
                     }
-                    tokens.push(token);
+                    self.push_token(&mut tokens, token, start);
                 }
 
                 None => {
+                    if current_opcode.is_empty() {
+                        opcode_start = self.position;
+                    }
+                    if !Self::is_identifier_char(ch, current_opcode.is_empty()) {
+                        return Err(self.invalid_token());
+                    }
                     current_opcode.push(ch);
                     self.position += 1;
                 }
@@ -51,28 +75,56 @@ impl Lexer {
         if !current_opcode.is_empty() {
             let token = self.match_token(&current_opcode)?;
             if token != Token::NoToken {
-                tokens.push(token);
+                self.push_token(&mut tokens, token, opcode_start);
             }
         }
 
-        // tokens.push(Token::Eof);
+        let end = self.position;
+        self.push_token(&mut tokens, Token::Eof, end);
 
         Ok(tokens)
     }
 
+    fn push_token(&mut self, tokens: &mut Vec<PositionedToken>, token: Token, start: usize) {
+        let position = self.source_position(start);
+        tokens.push(PositionedToken { token, position });
+    }
+
+    /// The source position of the character at `index`.
+    fn source_position(&mut self, index: usize) -> Position {
+        let (mut cursor_index, mut position) = self.cursor;
+
+        if index < cursor_index {
+            (cursor_index, position) = (0, self.origin);
+        }
+
+        while cursor_index < index {
+            match self.code_chars.get(cursor_index) {
+                Some(ch) => position = position.advance(*ch),
+                None => break,
+            }
+            cursor_index += 1;
+        }
+
+        self.cursor = (cursor_index, position);
+        position
+    }
+
+    fn error(&mut self, message: &str) -> EndiumError {
+        let position = self.source_position(self.position);
+        EndiumError::syntax_error(message.to_string()).at(position)
+    }
+
     fn peek_ahead(&self, offset: usize) -> Option<char> {
         self.code_chars.get(self.position + offset).copied()
     }
 
-    pub fn match_token_char(&mut self, ch: char) -> Option<Token> {
-        match ch {
+    pub fn match_token_char(&mut self, ch: char) -> Result<Option<Token>, EndiumError> {
+        let token = match ch {
             '.' => {
                 if self.peek_ahead(1) == Some('.') && self.peek_ahead(2) == Some('.') {
                     self.position += 3;
                     Some(Token::Spread)
-                } else if self.peek_ahead(1) == Some('?') {
-                    self.position += 2;
-                    Some(Token::OptionalChaining)
                 } else {
                     self.position += 1;
                     Some(Token::Dot)
@@ -92,23 +144,48 @@ impl Lexer {
                 }
             }
             '&' => {
-                if self.peek_ahead(1) == Some('&') {
+                if self.peek_ahead(1) == Some('&') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
+                    Some(Token::LogicalAndAssign)
+                } else if self.peek_ahead(1) == Some('&') {
                     self.position += 2;
                     Some(Token::LogicalAnd)
+                } else if self.peek_ahead(1) == Some('=') {
+                    self.position += 2;
+                    Some(Token::BitwiseAndAssign)
                 } else {
                     self.position += 1;
                     Some(Token::BitwiseAnd)
                 }
             }
             '|' => {
-                if self.peek_ahead(1) == Some('|') {
+                if self.peek_ahead(1) == Some('|') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
+                    Some(Token::LogicalOrAssign)
+                } else if self.peek_ahead(1) == Some('|') {
                     self.position += 2;
                     Some(Token::LogicalOr)
+                } else if self.peek_ahead(1) == Some('=') {
+                    self.position += 2;
+                    Some(Token::BitwiseOrAssign)
                 } else {
                     self.position += 1;
                     Some(Token::BitwiseOr)
                 }
             }
+            '^' => {
+                if self.peek_ahead(1) == Some('=') {
+                    self.position += 2;
+                    Some(Token::BitwiseXorAssign)
+                } else {
+                    self.position += 1;
+                    Some(Token::BitwiseXor)
+                }
+            }
+            '~' => {
+                self.position += 1;
+                Some(Token::BitwiseNot)
+            }
 
             '(' => {
                 self.position += 1;
@@ -165,7 +242,13 @@ impl Lexer {
             }
 
             '<' => {
-                if self.peek_ahead(1) == Some('=') {
+                if self.peek_ahead(1) == Some('<') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
+                    Some(Token::LeftShiftAssign)
+                } else if self.peek_ahead(1) == Some('<') {
+                    self.position += 2;
+                    Some(Token::LeftShift)
+                } else if self.peek_ahead(1) == Some('=') {
                     self.position += 2;
                     Some(Token::LessThanOrEqual)
                 } else {
@@ -174,7 +257,22 @@ impl Lexer {
                 }
             }
             '>' => {
-                if self.peek_ahead(1) == Some('=') {
+                if self.peek_ahead(1) == Some('>')
+                    && self.peek_ahead(2) == Some('>')
+                    && self.peek_ahead(3) == Some('=')
+                {
+                    self.position += 4;
+                    Some(Token::UnsignedRightShiftAssign)
+                } else if self.peek_ahead(1) == Some('>') && self.peek_ahead(2) == Some('>') {
+                    self.position += 3;
+                    Some(Token::UnsignedRightShift)
+                } else if self.peek_ahead(1) == Some('>') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
+                    Some(Token::RightShiftAssign)
+                } else if self.peek_ahead(1) == Some('>') {
+                    self.position += 2;
+                    Some(Token::RightShift)
+                } else if self.peek_ahead(1) == Some('=') {
                     self.position += 2;
                     Some(Token::GreaterThanOrEqual)
                 } else {
@@ -208,8 +306,8 @@ impl Lexer {
                 }
             }
             '*' => {
-                if self.peek_ahead(1) == Some('*') && self.peek_ahead(1) == Some('=') {
-                    self.position += 2;
+                if self.peek_ahead(1) == Some('*') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
                     Some(Token::ExponentAssign)
                 } else if self.peek_ahead(1) == Some('*') {
                     self.position += 2;
@@ -223,7 +321,11 @@ impl Lexer {
                 }
             }
             '/' => {
-                if self.peek_ahead(1) == Some('=') {
+                if self.peek_ahead(1) == Some('/') {
+                    Some(self.parse_line_comment())
+                } else if self.peek_ahead(1) == Some('*') {
+                    Some(self.parse_block_comment()?)
+                } else if self.peek_ahead(1) == Some('=') {
                     self.position += 2;
                     Some(Token::DivideAssign)
                 } else {
@@ -246,16 +348,23 @@ impl Lexer {
                 Some(Token::Newline)
             }
 
-            '\'' | '"' | '`' => Some(self.parse_string_or_template(ch)),
+            '\'' | '"' | '`' => Some(self.parse_string_or_template(ch)?),
 
             '?' => {
-                if self.peek_ahead(1) == Some('?') {
+                if self.peek_ahead(1) == Some('?') && self.peek_ahead(2) == Some('=') {
+                    self.position += 3;
+                    Some(Token::NullishAssign)
+                } else if self.peek_ahead(1) == Some('?') {
                     self.position += 2;
                     Some(Token::NullishCoalescing)
-                } else if self.peek_ahead(1) == Some('.') {
+                } else if self.peek_ahead(1) == Some('.')
+                    // `a?.5:0` is a conditional with a number in it.
+                    && !self.peek_ahead(2).is_some_and(|ch| ch.is_ascii_digit())
+                {
                     self.position += 2;
                     Some(Token::OptionalChaining)
                 } else {
+                    self.position += 1;
                     Some(Token::Ternary)
                 }
             }
@@ -263,18 +372,12 @@ impl Lexer {
             _ if ch.is_ascii_digit() => None,
 
             _ => None,
-        }
-    }
+        };
 
-    pub fn match_token(&mut self, current_opcode: &String) -> Result<Token, Box<dyn Error>> {
-        if current_opcode
-            .chars()
-            .next()
-            .map_or(false, |c| c.is_ascii_digit())
-        {
-            return Ok(self.parse_number(current_opcode));
-        }
+        Ok(token)
+    }
 
+    pub fn match_token(&mut self, current_opcode: &String) -> Result<Token, EndiumError> {
         let token = match current_opcode.as_str() {
             "const" => Token::Const,
             "let" => Token::Let,
@@ -321,7 +424,9 @@ impl Lexer {
             "undefined" => Token::Undefined,
 
             _ => {
-                if !current_opcode.is_empty() {
+                if let Some(name) = current_opcode.strip_prefix('#') {
+                    Token::PrivateName(name.to_string())
+                } else if !current_opcode.is_empty() {
                     Token::Identifier(current_opcode.to_string())
                 } else {
                     Token::NoToken
@@ -332,37 +437,185 @@ impl Lexer {
         Ok(token)
     }
 
-    fn parse_number(&self, start: &str) -> Token {
-        let mut num_str = start.to_string();
-        let mut pos = self.position;
-
-        while pos < self.code_chars.len() {
-            let ch = self.code_chars[pos];
-            if ch.is_ascii_digit() || ch == '.' {
-                num_str.push(ch);
-                pos += 1;
-            } else if ch == 'n' {
-                pos += 1;
-                return Token::BigNumber(num_str);
+    /// Lexes a numeric literal. The token keeps the literal's text, minus
+    /// any `_` separators, in a form `string_to_number` understands.
+    fn parse_number(&mut self) -> Result<Token, EndiumError> {
+        let radix = match (self.peek_ahead(0), self.peek_ahead(1)) {
+            (Some('0'), Some('x' | 'X')) => Some(16),
+            (Some('0'), Some('o' | 'O')) => Some(8),
+            (Some('0'), Some('b' | 'B')) => Some(2),
+            _ => None,
+        };
+
+        let mut number = String::new();
+        let mut is_integer = true;
+
+        match radix {
+            Some(radix) => {
+                number.push('0');
+                number.extend(self.peek_ahead(1));
+                self.position += 2;
+
+                let digits = self.parse_digits(radix)?;
+                if digits.is_empty() {
+                    return Err(self.invalid_token());
+                }
+                number.push_str(&digits);
+            }
+            None => {
+                let start = self.position;
+                number.push_str(&self.parse_digits(10)?);
+
+                // Literals with a leading zero, such as legacy octal ones,
+                // take no separators.
+                if number.len() > 1
+                    && number.starts_with('0')
+                    && let Some(offset) = self.code_chars[start..self.position]
+                        .iter()
+                        .position(|ch| *ch == '_')
+                {
+                    self.position = start + offset;
+                    return Err(self.error("Numeric separator can not be used after leading 0"));
+                }
+
+                // A leading zero followed only by octal digits is a legacy octal literal.
+                if number.len() > 1
+                    && number.starts_with('0')
+                    && number.chars().all(|ch| ch.is_digit(8))
+                {
+                    number.replace_range(..1, "0o");
+                } else {
+                    if self.peek_ahead(0) == Some('.') {
+                        self.position += 1;
+                        number.push('.');
+                        number.push_str(&self.parse_digits(10)?);
+                        is_integer = false;
+                    }
+
+                    if let Some(exponent @ ('e' | 'E')) = self.peek_ahead(0) {
+                        self.position += 1;
+                        number.push(exponent);
+
+                        if let Some(sign @ ('+' | '-')) = self.peek_ahead(0) {
+                            self.position += 1;
+                            number.push(sign);
+                        }
+
+                        let digits = self.parse_digits(10)?;
+                        if digits.is_empty() {
+                            return Err(self.invalid_token());
+                        }
+                        number.push_str(&digits);
+                        is_integer = false;
+                    }
+                }
+            }
+        }
+
+        let token = if is_integer && self.peek_ahead(0) == Some('n') {
+            self.position += 1;
+            Token::BigNumber(number)
+        } else {
+            Token::Number(number)
+        };
+
+        // A literal cannot run straight into an identifier or another number.
+        if self
+            .peek_ahead(0)
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_' || ch == '$')
+        {
+            return Err(self.invalid_token());
+        }
+
+        Ok(token)
+    }
+
+    /// Lexes a run of digits in `radix`, dropping `_` separators, which may
+    /// only appear between two digits.
+    fn parse_digits(&mut self, radix: u32) -> Result<String, EndiumError> {
+        let mut digits = String::new();
+
+        while let Some(ch) = self.peek_ahead(0) {
+            if ch == '_' {
+                let between_digits = !digits.is_empty()
+                    && self.peek_ahead(1).is_some_and(|next| next.is_digit(radix));
+                if !between_digits {
+                    return Err(self.error("Numeric separators are not allowed here"));
+                }
+            } else if ch.is_digit(radix) {
+                digits.push(ch);
             } else {
                 break;
             }
+
+            self.position += 1;
+        }
+
+        Ok(digits)
+    }
+
+    fn invalid_token(&mut self) -> EndiumError {
+        self.error("Invalid or unexpected token")
+    }
+
+    /// Whether `ch` can be part of an identifier, or of a private name when
+    /// it is the first character.
+    fn is_identifier_char(ch: char, is_first: bool) -> bool {
+        ch.is_alphanumeric()
+            || matches!(ch, '_' | '$' | '\u{200c}' | '\u{200d}')
+            || (ch == '#' && is_first)
+    }
+
+    fn parse_line_comment(&mut self) -> Token {
+        self.position += 2;
+        let mut comment = String::new();
+
+        while let Some(&ch) = self.code_chars.get(self.position) {
+            if ch == '\n' || ch == '\r' {
+                break;
+            }
+            comment.push(ch);
+            self.position += 1;
         }
 
-        Token::Number(num_str)
+        Token::Comment(comment)
     }
 
-    fn parse_string_or_template(&mut self, quote: char) -> Token {
+    fn parse_block_comment(&mut self) -> Result<Token, EndiumError> {
+        let start = self.position;
+        self.position += 2;
+        let mut comment = String::new();
+
+        while let Some(&ch) = self.code_chars.get(self.position) {
+            if ch == '*' && self.peek_ahead(1) == Some('/') {
+                self.position += 2;
+                return Ok(Token::BlockComment(comment));
+            }
+            comment.push(ch);
+            self.position += 1;
+        }
+
+        self.position = start;
+        Err(self.invalid_token())
+    }
+
+    /// Lexes a string literal, which must end on the line it starts on, or a
+    /// template literal.
+    fn parse_string_or_template(&mut self, quote: char) -> Result<Token, EndiumError> {
+        let start = self.position;
         self.position += 1;
 
         if quote == '`' {
-            return self.parse_template_string();
+            return self.parse_template_string(start);
         }
 
         let mut string = String::new();
 
-        while self.position < self.code_chars.len() {
-            let ch = self.code_chars[self.position];
+        loop {
+            let Some(ch) = self.peek_ahead(0).filter(|ch| *ch != '\n' && *ch != '\r') else {
+                self.position = start;
+                return Err(self.invalid_token());
+            };
             self.position += 1;
 
             if ch == quote {
@@ -384,10 +637,11 @@ impl Lexer {
             }
         }
 
-        Token::String(string)
+        Ok(Token::String(string))
     }
 
-    fn parse_template_string(&mut self) -> Token {
+    /// Lexes the rest of a template literal that starts at `start`.
+    fn parse_template_string(&mut self, start: usize) -> Result<Token, EndiumError> {
         let mut tokens = Vec::new();
         let mut current_string = String::new();
 
@@ -401,6 +655,7 @@ impl Lexer {
                 }
 
                 self.position += 2;
+                let expr_start = self.source_position(self.position);
 
                 let mut depth = 1;
                 let mut expr = String::new();
@@ -422,31 +677,45 @@ impl Lexer {
                     }
                 }
 
-                if let Ok(expr_tokens) = Lexer::new(&expr).tokenize() {
-                    tokens.push(Token::TemplateExpr(expr_tokens));
+                if depth > 0 {
+                    self.position = start;
+                    return Err(self.error("Unterminated template literal"));
                 }
+
+                let expr_tokens = Lexer::starting_at(&expr, expr_start).tokenize()?;
+                tokens.push(Token::TemplateExpr(expr_tokens));
             } else if ch == '`' {
                 self.position += 1;
-                break;
+
+                if !current_string.is_empty() {
+                    tokens.push(Token::String(current_string));
+                }
+
+                return Ok(Token::TemplateString(tokens));
             } else {
                 current_string.push(ch);
                 self.position += 1;
             }
         }
 
-        if !current_string.is_empty() {
-            tokens.push(Token::String(current_string));
-        }
+        self.position = start;
+        Err(self.error("Unterminated template literal"))
+    }
 
-        Token::TemplateString(tokens)
+    pub fn new(code: &str) -> Self {
+        Self::starting_at(code, Position::default())
     }
 
-    pub fn new(code: &String) -> Self {
+    /// A lexer for code that begins at `origin` in a larger source, such as
+    /// an expression inside a template literal.
+    fn starting_at(code: &str, origin: Position) -> Self {
         let code_chars = code.chars().collect();
 
         Self {
             position: 0,
             code_chars,
+            origin,
+            cursor: (0, origin),
         }
     }
 }
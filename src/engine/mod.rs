@@ -1,5 +1,11 @@
+pub mod ast;
+pub mod collector;
+pub mod completion;
 pub mod interpretter;
 pub mod lexer;
+pub mod operations;
+pub mod parser;
+pub mod position;
 pub mod scope;
 pub mod stack;
 pub mod state;
@@ -0,0 +1,407 @@
+use crate::apis::type_variants::js_array::JSArray;
+use crate::engine::value_variant::JSValueVariant;
+
+/// The longest string that can be made, in UTF-16 code units.
+pub const MAX_STRING_LENGTH: usize = (1 << 29) - 24;
+
+/// Characters the spec treats as white space or line terminators when
+/// converting strings to numbers and trimming.
+pub fn is_js_whitespace(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{9}'
+            | '\u{A}'
+            | '\u{B}'
+            | '\u{C}'
+            | '\u{D}'
+            | '\u{20}'
+            | '\u{A0}'
+            | '\u{1680}'
+            | '\u{2000}'
+            ..='\u{200A}'
+                | '\u{2028}'
+                | '\u{2029}'
+                | '\u{202F}'
+                | '\u{205F}'
+                | '\u{3000}'
+                | '\u{FEFF}'
+    )
+}
+
+pub fn to_boolean(value: &JSValueVariant) -> bool {
+    match value {
+        JSValueVariant::JSBoolean(js_bool) => js_bool.bool_value(),
+        JSValueVariant::JSNumber(js_number) => {
+            let number = js_number.number_value();
+            number != 0.0 && !number.is_nan()
+        }
+        JSValueVariant::JSString(js_string) => !js_string.str_value().is_empty(),
+        JSValueVariant::JSObject(_)
+        | JSValueVariant::JSArray(_)
+        | JSValueVariant::JSFunction(_) => true,
+        JSValueVariant::Null | JSValueVariant::Undefined => false,
+    }
+}
+
+/// ToPrimitive for values that are already primitives or can be rendered
+/// without running user code.
+pub fn to_primitive(value: &JSValueVariant) -> JSValueVariant {
+    match value {
+        JSValueVariant::JSObject(_)
+        | JSValueVariant::JSArray(_)
+        | JSValueVariant::JSFunction(_) => JSValueVariant::from(to_string(value)),
+        _ => value.clone(),
+    }
+}
+
+pub fn to_number(value: &JSValueVariant) -> f64 {
+    match value {
+        JSValueVariant::JSNumber(js_number) => js_number.number_value(),
+        JSValueVariant::JSString(js_string) => string_to_number(js_string.str_value()),
+        JSValueVariant::JSBoolean(js_bool) => {
+            if js_bool.bool_value() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        JSValueVariant::Null => 0.0,
+        JSValueVariant::Undefined => f64::NAN,
+        JSValueVariant::JSObject(_)
+        | JSValueVariant::JSArray(_)
+        | JSValueVariant::JSFunction(_) => to_number(&to_primitive(value)),
+    }
+}
+
+pub fn string_to_number(string: &str) -> f64 {
+    let trimmed = string.trim_matches(is_js_whitespace);
+
+    if trimmed.is_empty() {
+        return 0.0;
+    }
+
+    let radix = match trimmed.get(..2) {
+        Some("0x") | Some("0X") => Some(16),
+        Some("0o") | Some("0O") => Some(8),
+        Some("0b") | Some("0B") => Some(2),
+        _ => None,
+    };
+
+    if let Some(radix) = radix {
+        let digits = &trimmed[2..];
+        if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+            return f64::NAN;
+        }
+
+        return digits.chars().fold(0.0, |number, ch| {
+            number * radix as f64 + ch.to_digit(radix).unwrap_or(0) as f64
+        });
+    }
+
+    let unsigned = trimmed.trim_start_matches(['+', '-']);
+    if unsigned == "Infinity" {
+        return if trimmed.starts_with('-') {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        };
+    }
+
+    // Rust also accepts spellings such as `inf` and `nan`, which JS does not.
+    if !unsigned
+        .chars()
+        .all(|ch| ch.is_ascii_digit() || matches!(ch, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return f64::NAN;
+    }
+
+    trimmed.parse::<f64>().unwrap_or(f64::NAN)
+}
+
+pub fn number_to_string(number: f64) -> String {
+    if number.is_nan() {
+        String::from("NaN")
+    } else if number == 0.0 {
+        String::from("0")
+    } else if number.is_infinite() {
+        if number > 0.0 {
+            String::from("Infinity")
+        } else {
+            String::from("-Infinity")
+        }
+    } else {
+        finite_number_to_string(number)
+    }
+}
+
+/// Number::toString for finite, nonzero numbers: the shortest digits that
+/// round-trip, written out in full when the exponent is small and in
+/// scientific notation otherwise.
+fn finite_number_to_string(number: f64) -> String {
+    let sign = if number < 0.0 { "-" } else { "" };
+
+    // Rust's `{:e}` already prints the shortest round-tripping digits.
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+
+    let digits: String = mantissa.chars().filter(|ch| *ch != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().unwrap_or(0) + 1;
+
+    let body = if k <= n && n <= 21 {
+        digits + &"0".repeat((n - k) as usize)
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let exponent_sign = if n - 1 < 0 { '-' } else { '+' };
+        let exponent = (n - 1).abs();
+
+        if k == 1 {
+            format!("{}e{}{}", digits, exponent_sign, exponent)
+        } else {
+            format!(
+                "{}.{}e{}{}",
+                &digits[..1],
+                &digits[1..],
+                exponent_sign,
+                exponent
+            )
+        }
+    };
+
+    format!("{}{}", sign, body)
+}
+
+pub fn to_string(value: &JSValueVariant) -> String {
+    string_of(value, &mut vec![])
+}
+
+/// ToString, where `joining` holds the arrays whose elements are being
+/// joined further up.
+fn string_of(value: &JSValueVariant, joining: &mut Vec<usize>) -> String {
+    match value {
+        JSValueVariant::JSString(js_string) => js_string.str_value().to_string(),
+        JSValueVariant::JSNumber(js_number) => number_to_string(js_number.number_value()),
+        JSValueVariant::JSBoolean(js_bool) => js_bool.bool_value().to_string(),
+        JSValueVariant::Null => String::from("null"),
+        JSValueVariant::Undefined => String::from("undefined"),
+        JSValueVariant::JSFunction(js_function) => {
+            format!("function {}() {{ [native code] }}", js_function.name())
+        }
+        JSValueVariant::JSObject(_) => String::from("[object Object]"),
+        JSValueVariant::JSArray(js_array) => join_elements(js_array, ",", joining),
+    }
+}
+
+/// Holes, null and undefined become empty strings, and so does an array
+/// found inside itself. Only the present elements are visited, with the
+/// separators between them written at once.
+fn join_elements(js_array: &JSArray, separator: &str, joining: &mut Vec<usize>) -> String {
+    if joining.contains(&js_array.id()) {
+        return String::new();
+    }
+    joining.push(js_array.id());
+
+    let mut joined = String::new();
+    let mut separators = 0;
+
+    for (index, element) in js_array.entries() {
+        joined.push_str(&separator.repeat(index as usize - separators));
+        separators = index as usize;
+
+        if !matches!(element, JSValueVariant::Null | JSValueVariant::Undefined) {
+            joined.push_str(&string_of(&element, joining));
+        }
+    }
+
+    let length = js_array.length() as usize;
+    if length > 0 {
+        joined.push_str(&separator.repeat(length - 1 - separators));
+    }
+
+    joining.pop();
+
+    joined
+}
+
+/// ToIntegerOrInfinity: truncates towards zero and maps NaN to 0.
+pub fn to_integer_or_infinity(number: f64) -> f64 {
+    if number.is_nan() {
+        0.0
+    } else {
+        number.trunc() + 0.0
+    }
+}
+
+/// ToLength: clamps an integer to the range of valid lengths, 0 to 2^53 - 1.
+pub fn to_length(number: f64) -> u64 {
+    to_integer_or_infinity(number).clamp(0.0, 9007199254740991.0) as u64
+}
+
+pub fn to_int32(number: f64) -> i32 {
+    to_uint32(number) as i32
+}
+
+pub fn to_uint32(number: f64) -> u32 {
+    if !number.is_finite() {
+        return 0;
+    }
+
+    number.trunc().rem_euclid(4294967296.0) as u32
+}
+
+pub fn type_of(value: &JSValueVariant) -> &'static str {
+    match value {
+        JSValueVariant::JSString(_) => "string",
+        JSValueVariant::JSNumber(_) => "number",
+        JSValueVariant::JSBoolean(_) => "boolean",
+        JSValueVariant::JSFunction(_) => "function",
+        JSValueVariant::JSObject(_) | JSValueVariant::JSArray(_) | JSValueVariant::Null => "object",
+        JSValueVariant::Undefined => "undefined",
+    }
+}
+
+/// IsStrictlyEqual, the `===` operator.
+pub fn strict_equals(left: &JSValueVariant, right: &JSValueVariant) -> bool {
+    match (left, right) {
+        (JSValueVariant::JSNumber(left), JSValueVariant::JSNumber(right)) => {
+            left.number_value() == right.number_value()
+        }
+        (JSValueVariant::JSString(left), JSValueVariant::JSString(right)) => {
+            left.str_value() == right.str_value()
+        }
+        (JSValueVariant::JSBoolean(left), JSValueVariant::JSBoolean(right)) => {
+            left.bool_value() == right.bool_value()
+        }
+        (JSValueVariant::JSFunction(left), JSValueVariant::JSFunction(right)) => left.ptr_eq(right),
+        (JSValueVariant::JSObject(left), JSValueVariant::JSObject(right)) => left.ptr_eq(right),
+        (JSValueVariant::JSArray(left), JSValueVariant::JSArray(right)) => left.ptr_eq(right),
+        (JSValueVariant::Null, JSValueVariant::Null) => true,
+        (JSValueVariant::Undefined, JSValueVariant::Undefined) => true,
+        _ => false,
+    }
+}
+
+/// SameValue: like `===`, except that NaN equals itself and +0 and -0 differ.
+pub fn same_value(left: &JSValueVariant, right: &JSValueVariant) -> bool {
+    match (left, right) {
+        (JSValueVariant::JSNumber(left), JSValueVariant::JSNumber(right)) => {
+            let (left, right) = (left.number_value(), right.number_value());
+            (left == right && left.is_sign_negative() == right.is_sign_negative())
+                || (left.is_nan() && right.is_nan())
+        }
+        _ => strict_equals(left, right),
+    }
+}
+
+/// SameValueZero: like `===`, except that NaN equals itself.
+pub fn same_value_zero(left: &JSValueVariant, right: &JSValueVariant) -> bool {
+    match (left, right) {
+        (JSValueVariant::JSNumber(left), JSValueVariant::JSNumber(right)) => {
+            let (left, right) = (left.number_value(), right.number_value());
+            left == right || (left.is_nan() && right.is_nan())
+        }
+        _ => strict_equals(left, right),
+    }
+}
+
+/// IsLooselyEqual, the `==` operator. Objects compared with primitives are
+/// converted with the ToPrimitive above, which runs no user code; the
+/// interpretter converts them itself before calling this.
+pub fn loose_equals(left: &JSValueVariant, right: &JSValueVariant) -> bool {
+    match (left, right) {
+        (
+            JSValueVariant::Null | JSValueVariant::Undefined,
+            JSValueVariant::Null | JSValueVariant::Undefined,
+        ) => true,
+        (JSValueVariant::Null | JSValueVariant::Undefined, _)
+        | (_, JSValueVariant::Null | JSValueVariant::Undefined) => false,
+        (JSValueVariant::JSNumber(_), JSValueVariant::JSString(_))
+        | (JSValueVariant::JSString(_), JSValueVariant::JSNumber(_)) => {
+            to_number(left) == to_number(right)
+        }
+        (JSValueVariant::JSBoolean(_), _) => {
+            loose_equals(&JSValueVariant::from(to_number(left)), right)
+        }
+        (_, JSValueVariant::JSBoolean(_)) => {
+            loose_equals(left, &JSValueVariant::from(to_number(right)))
+        }
+        (
+            JSValueVariant::JSObject(_)
+            | JSValueVariant::JSArray(_)
+            | JSValueVariant::JSFunction(_),
+            JSValueVariant::JSObject(_)
+            | JSValueVariant::JSArray(_)
+            | JSValueVariant::JSFunction(_),
+        ) => strict_equals(left, right),
+        (
+            JSValueVariant::JSObject(_)
+            | JSValueVariant::JSArray(_)
+            | JSValueVariant::JSFunction(_),
+            _,
+        ) => loose_equals(&to_primitive(left), right),
+        (
+            _,
+            JSValueVariant::JSObject(_)
+            | JSValueVariant::JSArray(_)
+            | JSValueVariant::JSFunction(_),
+        ) => loose_equals(left, &to_primitive(right)),
+        _ => strict_equals(left, right),
+    }
+}
+
+/// IsLessThan on two primitives: strings compare by UTF-16 code unit, and
+/// everything else as numbers. `None` stands for undefined, the result when
+/// either number is NaN.
+pub fn is_less_than(left: &JSValueVariant, right: &JSValueVariant) -> Option<bool> {
+    if let (JSValueVariant::JSString(left), JSValueVariant::JSString(right)) = (left, right) {
+        return Some(
+            left.str_value()
+                .encode_utf16()
+                .lt(right.str_value().encode_utf16()),
+        );
+    }
+
+    let (left, right) = (to_number(left), to_number(right));
+
+    if left.is_nan() || right.is_nan() {
+        None
+    } else {
+        Some(left < right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_special_numbers_by_name() {
+        assert_eq!(number_to_string(f64::NAN), "NaN");
+        assert_eq!(number_to_string(-0.0), "0");
+        assert_eq!(number_to_string(f64::INFINITY), "Infinity");
+        assert_eq!(number_to_string(f64::NEG_INFINITY), "-Infinity");
+    }
+
+    #[test]
+    fn writes_the_shortest_round_tripping_digits() {
+        assert_eq!(number_to_string(1.0), "1");
+        assert_eq!(number_to_string(-42.0), "-42");
+        assert_eq!(number_to_string(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(number_to_string(123.456), "123.456");
+        assert_eq!(number_to_string(0.000001), "0.000001");
+    }
+
+    #[test]
+    fn switches_to_scientific_notation_at_the_limits() {
+        assert_eq!(number_to_string(1e21), "1e+21");
+        assert_eq!(number_to_string(123e20), "1.23e+22");
+        assert_eq!(number_to_string(1e20), "100000000000000000000");
+        assert_eq!(number_to_string(1e-7), "1e-7");
+        assert_eq!(number_to_string(-1.5e-10), "-1.5e-10");
+        assert_eq!(number_to_string(f64::MAX), "1.7976931348623157e+308");
+        assert_eq!(number_to_string(5e-324), "5e-324");
+    }
+}
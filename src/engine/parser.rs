@@ -0,0 +1,2508 @@
+use crate::engine::ast::*;
+use crate::engine::operations;
+use crate::engine::position::Position;
+use crate::engine::tokens::{PositionedToken, Token};
+use crate::errors::EndiumError;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+#[derive(Clone, Copy, PartialEq)]
+enum InfixOperator {
+    Binary(BinaryOperator),
+    Logical(LogicalOperator),
+}
+
+/// The names declared directly in a block or function body, which are
+/// checked for redeclarations.
+#[derive(Default)]
+struct DeclarationScope {
+    /// `let` and `const` names, and functions declared in a block.
+    lexical: HashSet<String>,
+    /// `var` names declared here or in a nested block, and functions declared
+    /// at the top of a function body.
+    var: HashSet<String>,
+    /// Function and catch parameters, which `var` may redeclare but `let` and
+    /// `const` may not.
+    params: HashSet<String>,
+    is_function: bool,
+}
+
+/// The private names a class body declares, and those used in it, which it
+/// or an enclosing class must declare.
+#[derive(Default)]
+struct PrivateNameScope {
+    /// What each name was declared as. A getter and a setter may share a
+    /// name, which fields and methods may not.
+    declared: HashMap<String, MethodKind>,
+    used: Vec<(String, Position)>,
+}
+
+/// Where `super` may appear: `super.name` in class methods and field
+/// initializers, and `super(...)` in the constructor of a derived class.
+/// Arrow functions keep the context they are in.
+#[derive(Clone, Copy, Default)]
+struct SuperContext {
+    property: bool,
+    call: bool,
+}
+
+pub struct Parser {
+    tokens: Vec<Token>,
+    newline_before: Vec<bool>,
+    positions: Vec<Position>,
+    /// Where the input ends, for errors about a missing token.
+    end_position: Position,
+    position: usize,
+    function_depth: usize,
+    loop_depth: usize,
+    /// How many `switch` statements enclose the current one, which an
+    /// unlabeled `break` may leave just like a loop.
+    switch_depth: usize,
+    /// Labels in scope, and whether each one labels an iteration statement.
+    labels: Vec<(String, bool)>,
+    /// The blocks and function bodies being parsed, innermost last.
+    declarations: Vec<DeclarationScope>,
+    /// The class bodies being parsed, innermost last.
+    private_names: Vec<PrivateNameScope>,
+    super_context: SuperContext,
+    /// Whether `in` is an operator, which it is not in the initializer of a
+    /// for statement outside of brackets.
+    allow_in: bool,
+    /// Where the `{ name = value }` shorthands of the object literals being
+    /// parsed are, which are only valid once they turn out to be patterns.
+    cover_initializers: Vec<Position>,
+}
+
+impl Parser {
+    pub fn parse(&mut self) -> Result<Program, EndiumError> {
+        let mut body: Vec<Statement> = vec![];
+
+        while !self.is_at_end() {
+            body.push(self.parse_statement()?);
+        }
+
+        let var_names = self.pop_declarations().var.into_iter().collect();
+
+        Ok(Program { body, var_names })
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, EndiumError> {
+        match self.peek() {
+            Some(Token::Const) | Some(Token::Let) | Some(Token::Var) => {
+                let declaration = self.parse_variable_declaration()?;
+                self.consume_semicolon()?;
+                Ok(Statement::VariableDeclaration(declaration))
+            }
+            Some(Token::Function) => {
+                self.advance(); // Skip `function`
+                let position = self.current_position();
+                let function = self.parse_function(true)?;
+                self.declare_function(function.name.as_deref().unwrap_or_default(), position)?;
+                Ok(Statement::FunctionDeclaration(function))
+            }
+            Some(Token::Class) => {
+                let position = self.current_position();
+                let class = self.parse_class(true)?;
+                self.declare_lexical(class.name.as_deref().unwrap_or_default(), position)?;
+                Ok(Statement::ClassDeclaration(class))
+            }
+            Some(Token::Return) => self.parse_return(),
+            Some(Token::If) => self.parse_if(),
+            Some(Token::While) => self.parse_while(),
+            Some(Token::Do) => self.parse_do_while(),
+            Some(Token::For) => self.parse_for(),
+            Some(Token::Switch) => self.parse_switch(),
+            Some(Token::Break) | Some(Token::Continue) => self.parse_break_continue(),
+            Some(Token::Throw) => self.parse_throw(),
+            Some(Token::Try) => self.parse_try(),
+            Some(Token::Identifier(_)) if self.peek_at(1) == Some(&Token::Colon) => {
+                self.parse_labeled()
+            }
+            Some(Token::LeftBrace) => Ok(Statement::Block {
+                body: self.parse_block()?,
+            }),
+            Some(Token::Semicolon) => {
+                self.advance();
+                Ok(Statement::Empty)
+            }
+            _ => {
+                let expression = self.parse_expression()?;
+                self.consume_semicolon()?;
+                Ok(Statement::Expression { expression })
+            }
+        }
+    }
+
+    fn parse_variable_declaration(&mut self) -> Result<VariableDeclaration, EndiumError> {
+        let kind = match self.advance() {
+            Some(Token::Const) => VariableKind::Const,
+            Some(Token::Let) => VariableKind::Let,
+            _ => VariableKind::Var,
+        };
+
+        let mut declarations: Vec<VariableDeclarator> = vec![];
+
+        loop {
+            let position = self.current_position();
+            let target = self.parse_pattern()?;
+            self.declare_pattern(kind, &target, position)?;
+
+            let init = if self.eat(&Token::Assign) {
+                Some(self.parse_assignment()?)
+            } else {
+                None
+            };
+
+            if init.is_none() && !matches!(target, Pattern::Identifier(_)) {
+                return Err(self.error("Missing initializer in destructuring declaration"));
+            }
+
+            if init.is_none() && kind == VariableKind::Const {
+                return Err(self.error("Missing initializer in const declaration"));
+            }
+
+            declarations.push(VariableDeclarator { target, init });
+
+            if !self.eat(&Token::Comma) {
+                break;
+            }
+        }
+
+        Ok(VariableDeclaration { kind, declarations })
+    }
+
+    /// Parses the target of a declaration, parameter or catch binding.
+    fn parse_pattern(&mut self) -> Result<Pattern, EndiumError> {
+        self.parse_pattern_target(true)
+    }
+
+    /// Parses an identifier or an object or array pattern. Binding patterns
+    /// only bind identifiers, while those of a destructuring assignment may
+    /// also assign to members.
+    fn parse_pattern_target(&mut self, binding: bool) -> Result<Pattern, EndiumError> {
+        match self.peek() {
+            Some(Token::LeftBracket) => self.parse_array_pattern(binding),
+            Some(Token::LeftBrace) => self.parse_object_pattern(binding),
+            _ if binding => Ok(Pattern::Identifier(self.parse_binding_identifier()?)),
+            _ => match self.parse_call_member()? {
+                Expression::Identifier { name, .. } => Ok(Pattern::Identifier(name)),
+                target @ (Expression::Member { .. } | Expression::SuperMember { .. }) => {
+                    Ok(Pattern::Expression(Box::new(target)))
+                }
+                _ => Err(self.error("Invalid destructuring assignment target")),
+            },
+        }
+    }
+
+    fn parse_array_pattern(&mut self, binding: bool) -> Result<Pattern, EndiumError> {
+        self.advance(); // Skip `[`
+
+        let mut elements: Vec<Option<Pattern>> = vec![];
+
+        while !self.eat(&Token::RightBracket) {
+            // A comma with nothing before it leaves a hole.
+            if self.eat(&Token::Comma) {
+                elements.push(None);
+                continue;
+            }
+
+            if self.eat(&Token::Spread) {
+                let target = self.parse_pattern_target(binding)?;
+                elements.push(Some(Pattern::Rest(Box::new(target))));
+
+                if !self.check(&Token::RightBracket) {
+                    return Err(self.error("Rest element must be last element"));
+                }
+                continue;
+            }
+
+            elements.push(Some(self.parse_pattern_element(binding)?));
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightBracket)?;
+                break;
+            }
+        }
+
+        Ok(Pattern::Array(elements))
+    }
+
+    fn parse_object_pattern(&mut self, binding: bool) -> Result<Pattern, EndiumError> {
+        self.advance(); // Skip `{`
+
+        let mut properties: Vec<PatternProperty> = vec![];
+        let mut rest: Option<Box<Pattern>> = None;
+
+        while !self.eat(&Token::RightBrace) {
+            if self.eat(&Token::Spread) {
+                rest = Some(Box::new(self.parse_pattern_target(binding)?));
+
+                if !self.check(&Token::RightBrace) {
+                    return Err(self.error("Rest element must be last element"));
+                }
+                continue;
+            }
+
+            // Only a plain identifier may stand on its own as `{ name }`.
+            let shorthand = match self.peek() {
+                Some(Token::Identifier(name)) => Some(name.clone()),
+                _ => None,
+            };
+
+            let key = self.parse_property_key()?;
+
+            let value = match shorthand {
+                Some(_) if self.eat(&Token::Colon) => self.parse_pattern_element(binding)?,
+                Some(name) => self.parse_default(Pattern::Identifier(name))?,
+                None => {
+                    self.expect(&Token::Colon)?;
+                    self.parse_pattern_element(binding)?
+                }
+            };
+
+            properties.push(PatternProperty { key, value });
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightBrace)?;
+                break;
+            }
+        }
+
+        Ok(Pattern::Object { properties, rest })
+    }
+
+    /// A pattern with an optional default value, as in a parameter list or
+    /// an array pattern.
+    fn parse_pattern_element(&mut self, binding: bool) -> Result<Pattern, EndiumError> {
+        let target = self.parse_pattern_target(binding)?;
+        self.parse_default(target)
+    }
+
+    /// Gives `target` the default value that follows it, if there is one.
+    fn parse_default(&mut self, target: Pattern) -> Result<Pattern, EndiumError> {
+        if !self.eat(&Token::Assign) {
+            return Ok(target);
+        }
+
+        Ok(Pattern::Default {
+            target: Box::new(target),
+            default: Box::new(self.parse_assignment()?),
+        })
+    }
+
+    fn parse_binding_identifier(&mut self) -> Result<String, EndiumError> {
+        match self.peek() {
+            Some(Token::Identifier(name)) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            // Contextual keywords are still valid binding names.
+            Some(token @ (Token::From | Token::As | Token::Of | Token::Async)) => {
+                let name = token.keyword().unwrap_or_default().to_string();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_function(&mut self, require_name: bool) -> Result<Rc<FunctionNode>, EndiumError> {
+        let name = match self.peek() {
+            Some(Token::LeftParen) if !require_name => None,
+            _ => Some(self.parse_binding_identifier()?),
+        };
+
+        self.with_super_context(SuperContext::default(), |parser| {
+            parser.parse_function_rest(name)
+        })
+    }
+
+    fn with_super_context<T>(
+        &mut self,
+        context: SuperContext,
+        parse: impl FnOnce(&mut Self) -> Result<T, EndiumError>,
+    ) -> Result<T, EndiumError> {
+        let outer_context = std::mem::replace(&mut self.super_context, context);
+        let result = parse(self);
+        self.super_context = outer_context;
+
+        result
+    }
+
+    fn parse_class(&mut self, require_name: bool) -> Result<Rc<ClassNode>, EndiumError> {
+        let position = self.current_position();
+        self.advance(); // Skip `class`
+
+        let name = match self.peek() {
+            Some(Token::LeftBrace) | Some(Token::Extends) if !require_name => None,
+            _ => Some(self.parse_binding_identifier()?),
+        };
+
+        let super_class = if self.eat(&Token::Extends) {
+            Some(self.parse_call_member()?)
+        } else {
+            None
+        };
+
+        self.expect(&Token::LeftBrace)?;
+
+        self.private_names.push(PrivateNameScope::default());
+        let class = self.parse_class_body(name, super_class, position);
+        let private_names = self.private_names.pop().unwrap_or_default();
+
+        let class = class?;
+        self.resolve_private_names(private_names)?;
+
+        Ok(Rc::new(class))
+    }
+
+    /// Parses the members of a class up to its closing brace.
+    fn parse_class_body(
+        &mut self,
+        name: Option<String>,
+        super_class: Option<Expression>,
+        position: Position,
+    ) -> Result<ClassNode, EndiumError> {
+        let mut constructor: Option<Rc<FunctionNode>> = None;
+        let mut members: Vec<ClassMember> = vec![];
+
+        while !self.eat(&Token::RightBrace) {
+            if self.is_at_end() {
+                return Err(self.unexpected());
+            }
+            if self.eat(&Token::Semicolon) {
+                continue;
+            }
+
+            let member_position = self.current_position();
+
+            match self.parse_class_member(super_class.is_some())? {
+                ClassMember::Method {
+                    key: ClassKey::Property(PropertyKey::Static(key)),
+                    kind: MethodKind::Method,
+                    function,
+                    is_static: false,
+                } if key == "constructor" => {
+                    if constructor.is_some() {
+                        return Err(EndiumError::syntax_error(String::from(
+                            "A class may only have one constructor",
+                        ))
+                        .at(member_position));
+                    }
+
+                    // The constructor is the class itself, and goes by its name.
+                    constructor = Some(Rc::new(FunctionNode {
+                        name: name.clone(),
+                        ..(*function).clone()
+                    }));
+                }
+                member => members.push(member),
+            }
+        }
+
+        let constructor = match constructor {
+            Some(constructor) => constructor,
+            None => Self::default_constructor(name.clone(), super_class.is_some(), position),
+        };
+
+        Ok(ClassNode {
+            name,
+            super_class,
+            constructor,
+            members,
+        })
+    }
+
+    /// `constructor() {}`, or `constructor(...args) { super(...args); }` for a
+    /// derived class.
+    fn default_constructor(
+        name: Option<String>,
+        derived: bool,
+        position: Position,
+    ) -> Rc<FunctionNode> {
+        let args = String::from("args");
+
+        let (params, body) = if derived {
+            (
+                vec![Pattern::Rest(Box::new(Pattern::Identifier(args.clone())))],
+                vec![Statement::Expression {
+                    expression: Expression::SuperCall {
+                        arguments: vec![Argument::Spread(Expression::Identifier {
+                            name: args,
+                            position,
+                        })],
+                        position,
+                    },
+                }],
+            )
+        } else {
+            (vec![], vec![])
+        };
+
+        Rc::new(FunctionNode {
+            name,
+            params,
+            body,
+            var_names: vec![],
+        })
+    }
+
+    fn parse_class_member(&mut self, derived: bool) -> Result<ClassMember, EndiumError> {
+        let is_static = self.is_modifier("static");
+
+        if is_static && self.check(&Token::LeftBrace) {
+            let context = SuperContext {
+                property: true,
+                call: false,
+            };
+            let (body, var_names) = self.with_super_context(context, |parser| {
+                parser.parse_function_body(&[], Self::parse_static_block_body)
+            })?;
+
+            return Ok(ClassMember::StaticBlock(Rc::new(FunctionNode {
+                name: None,
+                params: vec![],
+                body,
+                var_names,
+            })));
+        }
+
+        let kind = if self.is_modifier("get") {
+            MethodKind::Getter
+        } else if self.is_modifier("set") {
+            MethodKind::Setter
+        } else {
+            MethodKind::Method
+        };
+
+        let position = self.current_position();
+        let key = match self.peek() {
+            Some(Token::PrivateName(name)) => {
+                let name = name.clone();
+                self.advance();
+                ClassKey::Private(name)
+            }
+            _ => ClassKey::Property(self.parse_property_key()?),
+        };
+
+        let is_constructor_key = !is_static
+            && matches!(&key, ClassKey::Property(PropertyKey::Static(key)) if key == "constructor");
+
+        if self.check(&Token::LeftParen) {
+            if is_constructor_key && kind != MethodKind::Method {
+                return Err(self.error_at("Class constructor may not be an accessor", position));
+            }
+
+            let name = match &key {
+                ClassKey::Property(PropertyKey::Static(name)) => Some(name.clone()),
+                ClassKey::Private(name) => {
+                    self.declare_private_name(name, kind, position)?;
+                    Some(format!("#{}", name))
+                }
+                ClassKey::Property(PropertyKey::Computed(_)) => None,
+            };
+
+            let context = SuperContext {
+                property: true,
+                call: is_constructor_key && derived,
+            };
+            let function =
+                self.with_super_context(context, |parser| parser.parse_function_rest(name))?;
+            self.check_accessor_params(kind, &function, position)?;
+
+            return Ok(ClassMember::Method {
+                key,
+                kind,
+                function,
+                is_static,
+            });
+        }
+
+        if kind != MethodKind::Method {
+            return Err(self.unexpected());
+        }
+
+        match &key {
+            ClassKey::Property(PropertyKey::Static(name)) if name == "constructor" => {
+                return Err(
+                    self.error_at("Classes may not have a field named 'constructor'", position)
+                );
+            }
+            ClassKey::Private(name) => self.declare_private_name(name, kind, position)?,
+            ClassKey::Property(_) => {}
+        }
+
+        let value = if self.eat(&Token::Assign) {
+            let context = SuperContext {
+                property: true,
+                call: false,
+            };
+            Some(self.with_super_context(context, Self::parse_assignment)?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+
+        Ok(ClassMember::Field {
+            key,
+            value,
+            is_static,
+        })
+    }
+
+    /// A static block runs like a method, but `return` cannot leave it.
+    fn parse_static_block_body(&mut self) -> Result<Vec<Statement>, EndiumError> {
+        let function_depth = std::mem::replace(&mut self.function_depth, 0);
+        let body = self.parse_block_body();
+        self.function_depth = function_depth;
+
+        body
+    }
+
+    /// A getter takes no parameters and a setter exactly one, which cannot be
+    /// a rest parameter.
+    fn check_accessor_params(
+        &self,
+        kind: MethodKind,
+        function: &FunctionNode,
+        position: Position,
+    ) -> Result<(), EndiumError> {
+        let message = match (kind, function.params.as_slice()) {
+            (MethodKind::Getter, [_, ..]) => "Getter must not have any formal parameters.",
+            (MethodKind::Setter, [Pattern::Rest(_)]) => {
+                "Setter function argument must not be a rest parameter"
+            }
+            (MethodKind::Setter, [_]) => return Ok(()),
+            (MethodKind::Setter, _) => "Setter must have exactly one formal parameter.",
+            _ => return Ok(()),
+        };
+
+        Err(self.error_at(message, position))
+    }
+
+    /// Skips the contextual keyword `name` when it modifies the class member
+    /// or object literal property after it, rather than naming it.
+    fn is_modifier(&mut self, name: &str) -> bool {
+        let is_modifier = matches!(self.peek(), Some(Token::Identifier(identifier)) if identifier == name)
+            && !matches!(
+                self.peek_at(1),
+                Some(Token::LeftParen)
+                    | Some(Token::Assign)
+                    | Some(Token::Semicolon)
+                    | Some(Token::RightBrace)
+                    | Some(Token::Colon)
+                    | Some(Token::Comma)
+                    | None
+            );
+
+        if is_modifier {
+            self.advance();
+        }
+
+        is_modifier
+    }
+
+    fn declare_private_name(
+        &mut self,
+        name: &str,
+        kind: MethodKind,
+        position: Position,
+    ) -> Result<(), EndiumError> {
+        if name == "constructor" {
+            return Err(self.error_at(
+                "Classes may not have a private field named '#constructor'",
+                position,
+            ));
+        }
+
+        let Some(scope) = self.private_names.last_mut() else {
+            return Ok(());
+        };
+
+        match scope.declared.get(name) {
+            None => {
+                scope.declared.insert(name.to_string(), kind);
+                Ok(())
+            }
+            // A getter and a setter together make up one accessor.
+            Some(MethodKind::Getter) if kind == MethodKind::Setter => {
+                scope.declared.insert(name.to_string(), MethodKind::Method);
+                Ok(())
+            }
+            Some(MethodKind::Setter) if kind == MethodKind::Getter => {
+                scope.declared.insert(name.to_string(), MethodKind::Method);
+                Ok(())
+            }
+            Some(_) => Err(Self::redeclaration(&format!("#{}", name), position)),
+        }
+    }
+
+    /// Records a use of `#name`, which a class around it has to declare.
+    fn use_private_name(&mut self, name: &str, position: Position) -> Result<(), EndiumError> {
+        match self.private_names.last_mut() {
+            Some(scope) => {
+                scope.used.push((name.to_string(), position));
+                Ok(())
+            }
+            None => Err(Self::undeclared_private_name(name, position)),
+        }
+    }
+
+    /// Checks the private names used in a class body once it has been parsed,
+    /// passing those it does not declare on to the enclosing class.
+    fn resolve_private_names(&mut self, scope: PrivateNameScope) -> Result<(), EndiumError> {
+        for (name, position) in scope.used {
+            if !scope.declared.contains_key(&name) {
+                self.use_private_name(&name, position)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn undeclared_private_name(name: &str, position: Position) -> EndiumError {
+        EndiumError::syntax_error(format!(
+            "Private field '#{}' must be declared in an enclosing class",
+            name
+        ))
+        .at(position)
+    }
+
+    /// Parses a function's parameter list and body, starting at the `(`.
+    fn parse_function_rest(
+        &mut self,
+        name: Option<String>,
+    ) -> Result<Rc<FunctionNode>, EndiumError> {
+        let params = self.parse_params()?;
+        let (body, var_names) = self.parse_function_body(&params, Self::parse_block_body)?;
+
+        Ok(Rc::new(FunctionNode {
+            name,
+            params,
+            body,
+            var_names,
+        }))
+    }
+
+    fn parse_params(&mut self) -> Result<Vec<Pattern>, EndiumError> {
+        self.expect(&Token::LeftParen)?;
+
+        let mut params: Vec<Pattern> = vec![];
+
+        while !self.eat(&Token::RightParen) {
+            if self.eat(&Token::Spread) {
+                params.push(Pattern::Rest(Box::new(self.parse_pattern()?)));
+
+                if !self.check(&Token::RightParen) {
+                    return Err(self.error("Rest parameter must be last formal parameter"));
+                }
+                continue;
+            }
+
+            params.push(self.parse_pattern_element(true)?);
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightParen)?;
+                break;
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Parses the body of a function with `params` using `parse_body`, and
+    /// returns it with the `var` names it declares.
+    fn parse_function_body(
+        &mut self,
+        params: &[Pattern],
+        parse_body: fn(&mut Self) -> Result<Vec<Statement>, EndiumError>,
+    ) -> Result<(Vec<Statement>, Vec<String>), EndiumError> {
+        // Labels and loops do not reach into a nested function body.
+        let labels = std::mem::take(&mut self.labels);
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        let switch_depth = std::mem::replace(&mut self.switch_depth, 0);
+        let allow_in = std::mem::replace(&mut self.allow_in, true);
+
+        self.push_declarations(DeclarationScope {
+            params: Self::bound_names(params),
+            is_function: true,
+            ..DeclarationScope::default()
+        });
+
+        self.function_depth += 1;
+        let body = parse_body(self);
+        self.function_depth -= 1;
+
+        let var_names = self.pop_declarations().var.into_iter().collect();
+
+        self.labels = labels;
+        self.loop_depth = loop_depth;
+        self.switch_depth = switch_depth;
+        self.allow_in = allow_in;
+
+        Ok((body?, var_names))
+    }
+
+    /// Whether an arrow function starts here: a single parameter, or a
+    /// parenthesized parameter list, followed by `=>` on the same line.
+    fn is_arrow_function(&self) -> bool {
+        let arrow_offset = match self.peek() {
+            Some(Token::Identifier(_) | Token::From | Token::As | Token::Of | Token::Async) => 1,
+            Some(Token::LeftParen) => {
+                let mut depth = 0;
+                let mut offset = 0;
+
+                loop {
+                    match self.peek_at(offset) {
+                        Some(Token::LeftParen) => depth += 1,
+                        Some(Token::RightParen) => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break offset + 1;
+                            }
+                        }
+                        None => return false,
+                        _ => {}
+                    }
+                    offset += 1;
+                }
+            }
+            _ => return false,
+        };
+
+        self.peek_at(arrow_offset) == Some(&Token::ArrowFunction)
+            && !self.newline_before[self.position + arrow_offset]
+    }
+
+    fn parse_arrow_function(&mut self) -> Result<Expression, EndiumError> {
+        let params = if self.check(&Token::LeftParen) {
+            self.parse_params()?
+        } else {
+            vec![self.parse_pattern()?]
+        };
+        self.advance(); // Skip `=>`
+
+        let (body, var_names) = self.parse_function_body(&params, Self::parse_arrow_body)?;
+
+        Ok(Expression::ArrowFunction(Rc::new(FunctionNode {
+            name: None,
+            params,
+            body,
+            var_names,
+        })))
+    }
+
+    /// An arrow function's body is either a block, or an expression whose
+    /// value the function returns.
+    fn parse_arrow_body(&mut self) -> Result<Vec<Statement>, EndiumError> {
+        if self.check(&Token::LeftBrace) {
+            return self.parse_block_body();
+        }
+
+        let argument = self.parse_assignment()?;
+
+        Ok(vec![Statement::Return {
+            argument: Some(argument),
+        }])
+    }
+
+    fn parse_return(&mut self) -> Result<Statement, EndiumError> {
+        if self.function_depth == 0 {
+            return Err(self.error("Illegal return statement"));
+        }
+
+        self.advance(); // Skip `return`
+
+        let argument = if self.can_insert_semicolon() || self.check(&Token::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+
+        self.consume_semicolon()?;
+
+        Ok(Statement::Return { argument })
+    }
+
+    fn parse_if(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `if`
+
+        self.expect(&Token::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(&Token::RightParen)?;
+
+        let consequent = Box::new(self.parse_statement()?);
+        let alternate = if self.eat(&Token::Else) {
+            Some(Box::new(self.parse_statement()?))
+        } else {
+            None
+        };
+
+        Ok(Statement::If {
+            test,
+            consequent,
+            alternate,
+        })
+    }
+
+    fn parse_while(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `while`
+
+        self.expect(&Token::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(&Token::RightParen)?;
+
+        let body = Box::new(self.parse_loop_body()?);
+
+        Ok(Statement::While { test, body })
+    }
+
+    fn parse_do_while(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `do`
+
+        let body = Box::new(self.parse_loop_body()?);
+
+        self.expect(&Token::While)?;
+        self.expect(&Token::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(&Token::RightParen)?;
+
+        // A semicolon is always inserted after a do-while statement.
+        self.eat(&Token::Semicolon);
+
+        Ok(Statement::DoWhile { body, test })
+    }
+
+    fn parse_for(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `for`
+
+        self.expect(&Token::LeftParen)?;
+
+        // `let` and `const` in the head are scoped to the loop.
+        self.push_declarations(DeclarationScope::default());
+        let statement = self.parse_for_rest();
+        self.pop_declarations();
+
+        statement
+    }
+
+    /// Parses a for statement from its initializer on.
+    fn parse_for_rest(&mut self) -> Result<Statement, EndiumError> {
+        if let Some(statement) = self.parse_for_in_of()? {
+            return Ok(statement);
+        }
+
+        // An `in` there would read as a for-in statement.
+        let allow_in = std::mem::replace(&mut self.allow_in, false);
+        let init = match self.peek() {
+            Some(Token::Semicolon) => Ok(None),
+            Some(Token::Const) | Some(Token::Let) | Some(Token::Var) => self
+                .parse_variable_declaration()
+                .map(|declaration| Some(ForInit::VariableDeclaration(declaration))),
+            _ => self
+                .parse_expression()
+                .map(|expression| Some(ForInit::Expression(expression))),
+        };
+        self.allow_in = allow_in;
+        let init = init?;
+        self.expect(&Token::Semicolon)?;
+
+        let test = if self.check(&Token::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(&Token::Semicolon)?;
+
+        let update = if self.check(&Token::RightParen) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(&Token::RightParen)?;
+
+        let body = Box::new(self.parse_loop_body()?);
+
+        Ok(Statement::For {
+            init,
+            test,
+            update,
+            body,
+        })
+    }
+
+    /// Parses a for-in or for-of statement from its head on. When the head is
+    /// not one, returns `None` and leaves the position where it was.
+    fn parse_for_in_of(&mut self) -> Result<Option<Statement>, EndiumError> {
+        let start = self.position;
+
+        let kind = match self.peek() {
+            Some(Token::Const) => Some(VariableKind::Const),
+            Some(Token::Let) => Some(VariableKind::Let),
+            Some(Token::Var) => Some(VariableKind::Var),
+            _ => None,
+        };
+        if kind.is_some() {
+            self.advance();
+        }
+
+        let position = self.current_position();
+        let target = match kind {
+            Some(_) => self.parse_pattern(),
+            None => self.parse_pattern_target(false),
+        };
+
+        let (target, is_of) = match (target, self.peek()) {
+            (Ok(target), Some(Token::Of)) => (target, true),
+            (Ok(target), Some(Token::In)) => (target, false),
+            _ => {
+                self.position = start;
+                return Ok(None);
+            }
+        };
+        self.advance(); // Skip `of` or `in`
+
+        let left = match kind {
+            Some(kind) => {
+                self.declare_pattern(kind, &target, position)?;
+                ForBinding::Declaration { kind, target }
+            }
+            None => ForBinding::Pattern(target),
+        };
+
+        let right = if is_of {
+            self.parse_assignment()?
+        } else {
+            self.parse_expression()?
+        };
+        self.expect(&Token::RightParen)?;
+
+        let body = Box::new(self.parse_loop_body()?);
+
+        Ok(Some(if is_of {
+            Statement::ForOf { left, right, body }
+        } else {
+            Statement::ForIn { left, right, body }
+        }))
+    }
+
+    fn parse_loop_body(&mut self) -> Result<Statement, EndiumError> {
+        self.loop_depth += 1;
+        let body = self.parse_statement();
+        self.loop_depth -= 1;
+
+        body
+    }
+
+    fn parse_switch(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `switch`
+
+        self.expect(&Token::LeftParen)?;
+        let discriminant = self.parse_expression()?;
+        self.expect(&Token::RightParen)?;
+
+        // All the clauses share one scope.
+        self.push_declarations(DeclarationScope::default());
+        self.switch_depth += 1;
+        let cases = self.parse_switch_cases();
+        self.switch_depth -= 1;
+        self.pop_declarations();
+
+        Ok(Statement::Switch {
+            discriminant,
+            cases: cases?,
+        })
+    }
+
+    fn parse_switch_cases(&mut self) -> Result<Vec<SwitchCase>, EndiumError> {
+        self.expect(&Token::LeftBrace)?;
+
+        let mut cases: Vec<SwitchCase> = vec![];
+        let mut has_default = false;
+
+        while !self.eat(&Token::RightBrace) {
+            let test = match self.peek() {
+                Some(Token::Case) => {
+                    self.advance();
+                    Some(self.parse_expression()?)
+                }
+                Some(Token::Default) if has_default => {
+                    return Err(self.error("More than one default clause in switch statement"));
+                }
+                Some(Token::Default) => {
+                    self.advance();
+                    has_default = true;
+                    None
+                }
+                _ => return Err(self.unexpected()),
+            };
+            self.expect(&Token::Colon)?;
+
+            let mut consequent: Vec<Statement> = vec![];
+
+            while !matches!(
+                self.peek(),
+                Some(Token::Case) | Some(Token::Default) | Some(Token::RightBrace)
+            ) {
+                if self.is_at_end() {
+                    return Err(self.unexpected());
+                }
+                consequent.push(self.parse_statement()?);
+            }
+
+            cases.push(SwitchCase { test, consequent });
+        }
+
+        Ok(cases)
+    }
+
+    fn parse_break_continue(&mut self) -> Result<Statement, EndiumError> {
+        let is_break = self.advance() == Some(Token::Break);
+
+        // A label has to be on the same line as the keyword.
+        let label = match self.peek() {
+            Some(Token::Identifier(label)) if !self.newline_before_current() => {
+                let label = label.clone();
+                self.advance();
+                Some(label)
+            }
+            _ => None,
+        };
+
+        self.consume_semicolon()?;
+
+        match &label {
+            Some(label) => match self.labels.iter().find(|(name, _)| name == label) {
+                None => return Err(self.error(&format!("Undefined label '{}'", label))),
+                Some((_, false)) if !is_break => {
+                    return Err(self.error(&format!(
+                        "Illegal continue statement: '{}' does not denote an iteration statement",
+                        label
+                    )));
+                }
+                _ => {}
+            },
+            None if is_break && self.loop_depth == 0 && self.switch_depth == 0 => {
+                return Err(self.error("Illegal break statement"));
+            }
+            None if !is_break && self.loop_depth == 0 => {
+                return Err(
+                    self.error("Illegal continue statement: no surrounding iteration statement")
+                );
+            }
+            None => {}
+        }
+
+        if is_break {
+            Ok(Statement::Break { label })
+        } else {
+            Ok(Statement::Continue { label })
+        }
+    }
+
+    fn parse_labeled(&mut self) -> Result<Statement, EndiumError> {
+        let label = self.parse_binding_identifier()?;
+        self.advance(); // Skip `:`
+
+        if self.labels.iter().any(|(name, _)| name == &label) {
+            return Err(self.error(&format!("Label '{}' has already been declared", label)));
+        }
+
+        let labels_iteration = matches!(
+            self.peek(),
+            Some(Token::For) | Some(Token::While) | Some(Token::Do)
+        );
+
+        self.labels.push((label.clone(), labels_iteration));
+        let body = self.parse_statement();
+        self.labels.pop();
+
+        Ok(Statement::Labeled {
+            label,
+            body: Box::new(body?),
+        })
+    }
+
+    fn parse_throw(&mut self) -> Result<Statement, EndiumError> {
+        let position = self.current_position();
+        self.advance(); // Skip `throw`
+
+        if self.newline_before_current() {
+            return Err(self.error("Illegal newline after throw"));
+        }
+
+        let argument = self.parse_expression()?;
+        self.consume_semicolon()?;
+
+        Ok(Statement::Throw { argument, position })
+    }
+
+    fn parse_try(&mut self) -> Result<Statement, EndiumError> {
+        self.advance(); // Skip `try`
+
+        let block = self.parse_block()?;
+
+        let handler = if self.eat(&Token::Catch) {
+            let param = if self.eat(&Token::LeftParen) {
+                let param = self.parse_pattern()?;
+                self.expect(&Token::RightParen)?;
+                Some(param)
+            } else {
+                None
+            };
+
+            // The parameter shares a scope with the body of the catch block.
+            self.push_declarations(DeclarationScope {
+                params: Self::bound_names(param.as_slice()),
+                ..DeclarationScope::default()
+            });
+            let body = self.parse_block_body();
+            self.pop_declarations();
+
+            Some(CatchClause { param, body: body? })
+        } else {
+            None
+        };
+
+        let finalizer = if self.eat(&Token::Finally) {
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+
+        if handler.is_none() && finalizer.is_none() {
+            return Err(self.error("Missing catch or finally after try"));
+        }
+
+        Ok(Statement::Try {
+            block,
+            handler,
+            finalizer,
+        })
+    }
+
+    fn parse_block(&mut self) -> Result<Vec<Statement>, EndiumError> {
+        self.push_declarations(DeclarationScope::default());
+        let body = self.parse_block_body();
+        self.pop_declarations();
+
+        body
+    }
+
+    /// Parses the statements between braces, in the declaration scope
+    /// already pushed for them.
+    fn parse_block_body(&mut self) -> Result<Vec<Statement>, EndiumError> {
+        self.expect(&Token::LeftBrace)?;
+
+        let mut body: Vec<Statement> = vec![];
+
+        while !self.eat(&Token::RightBrace) {
+            if self.is_at_end() {
+                return Err(self.unexpected());
+            }
+            body.push(self.parse_statement()?);
+        }
+
+        Ok(body)
+    }
+
+    pub fn parse_expression(&mut self) -> Result<Expression, EndiumError> {
+        let expression = self.parse_assignment()?;
+
+        if !self.check(&Token::Comma) {
+            return Ok(expression);
+        }
+
+        let mut expressions = vec![expression];
+
+        while self.eat(&Token::Comma) {
+            expressions.push(self.parse_assignment()?);
+        }
+
+        Ok(Expression::Sequence(expressions))
+    }
+
+    fn parse_assignment(&mut self) -> Result<Expression, EndiumError> {
+        let pending = self.cover_initializers.len();
+        let expression = self.parse_assignment_cover()?;
+
+        if let Some(&position) = self.cover_initializers.get(pending) {
+            return Err(self.error_at("Invalid shorthand property initializer", position));
+        }
+
+        Ok(expression)
+    }
+
+    /// Parses an assignment expression that may still turn out to be part of
+    /// a pattern, as the elements of an array or object literal may when it is
+    /// assigned to.
+    fn parse_assignment_cover(&mut self) -> Result<Expression, EndiumError> {
+        if self.is_arrow_function() {
+            return self.parse_arrow_function();
+        }
+
+        let position = self.current_position();
+        let pending = self.cover_initializers.len();
+        let is_literal = matches!(self.peek(), Some(Token::LeftBracket | Token::LeftBrace));
+
+        let target = self.parse_binary(0)?;
+
+        // An array or object literal followed by `=` is the pattern of a
+        // destructuring assignment.
+        if is_literal
+            && matches!(target, Expression::Array(_) | Expression::Object(_))
+            && self.eat(&Token::Assign)
+        {
+            let target = self.to_pattern(target, position)?;
+            self.cover_initializers.truncate(pending);
+            let value = self.parse_assignment()?;
+
+            return Ok(Expression::DestructuringAssignment {
+                target,
+                value: Box::new(value),
+                position,
+            });
+        }
+
+        if self.eat(&Token::Ternary) {
+            let consequent = self.with_in_allowed(Self::parse_assignment)?;
+            self.expect(&Token::Colon)?;
+            let alternate = self.parse_assignment()?;
+
+            return Ok(Expression::Conditional {
+                test: Box::new(target),
+                consequent: Box::new(consequent),
+                alternate: Box::new(alternate),
+            });
+        }
+
+        let operator = match self.peek() {
+            Some(Token::Assign) => AssignmentOperator::Assign,
+            Some(Token::PlusAssign) => AssignmentOperator::Addition,
+            Some(Token::MinusAssign) => AssignmentOperator::Subtraction,
+            Some(Token::MultiplyAssign) => AssignmentOperator::Multiplication,
+            Some(Token::DivideAssign) => AssignmentOperator::Division,
+            Some(Token::ModuloAssign) => AssignmentOperator::Modulo,
+            Some(Token::ExponentAssign) => AssignmentOperator::Exponent,
+            Some(Token::BitwiseAndAssign) => AssignmentOperator::BitwiseAnd,
+            Some(Token::BitwiseOrAssign) => AssignmentOperator::BitwiseOr,
+            Some(Token::BitwiseXorAssign) => AssignmentOperator::BitwiseXor,
+            Some(Token::LeftShiftAssign) => AssignmentOperator::LeftShift,
+            Some(Token::RightShiftAssign) => AssignmentOperator::RightShift,
+            Some(Token::UnsignedRightShiftAssign) => AssignmentOperator::UnsignedRightShift,
+            Some(Token::LogicalAndAssign) => AssignmentOperator::LogicalAnd,
+            Some(Token::LogicalOrAssign) => AssignmentOperator::LogicalOr,
+            Some(Token::NullishAssign) => AssignmentOperator::Nullish,
+            _ => return Ok(target),
+        };
+
+        if !Self::is_assignment_target(&target) {
+            return Err(self.error("Invalid left-hand side in assignment"));
+        }
+
+        self.advance(); // Skip the assignment operator
+
+        let value = self.parse_assignment()?;
+
+        Ok(Expression::Assignment {
+            operator,
+            target: Box::new(target),
+            value: Box::new(value),
+            position,
+        })
+    }
+
+    /// Reinterprets an expression parsed as an array or object literal as the
+    /// pattern of a destructuring assignment.
+    fn to_pattern(
+        &self,
+        expression: Expression,
+        position: Position,
+    ) -> Result<Pattern, EndiumError> {
+        let position = expression.position().unwrap_or(position);
+
+        match expression {
+            Expression::Identifier { name, .. } => Ok(Pattern::Identifier(name)),
+            target @ (Expression::Member { .. } | Expression::SuperMember { .. }) => {
+                Ok(Pattern::Expression(Box::new(target)))
+            }
+            Expression::Array(elements) => {
+                let count = elements.len();
+                let mut patterns: Vec<Option<Pattern>> = vec![];
+
+                for (index, element) in elements.into_iter().enumerate() {
+                    patterns.push(match element {
+                        ArrayElement::Hole => None,
+                        ArrayElement::Expression(element) => {
+                            Some(self.to_pattern_element(element, position)?)
+                        }
+                        ArrayElement::Spread(_) if index + 1 < count => {
+                            return Err(
+                                self.error_at("Rest element must be last element", position)
+                            );
+                        }
+                        ArrayElement::Spread(target) => {
+                            Some(Pattern::Rest(Box::new(self.to_pattern(target, position)?)))
+                        }
+                    });
+                }
+
+                Ok(Pattern::Array(patterns))
+            }
+            Expression::Object(properties) => {
+                let count = properties.len();
+                let mut patterns: Vec<PatternProperty> = vec![];
+                let mut rest: Option<Box<Pattern>> = None;
+
+                for (index, property) in properties.into_iter().enumerate() {
+                    match property {
+                        ObjectProperty::Property { key, value } => patterns.push(PatternProperty {
+                            key,
+                            value: self.to_pattern_element(value, position)?,
+                        }),
+                        ObjectProperty::Prototype(value) => patterns.push(PatternProperty {
+                            key: PropertyKey::Static("__proto__".to_string()),
+                            value: self.to_pattern_element(value, position)?,
+                        }),
+                        ObjectProperty::Spread(_) if index + 1 < count => {
+                            return Err(
+                                self.error_at("Rest element must be last element", position)
+                            );
+                        }
+                        // The rest of an object is a single target, not a pattern.
+                        ObjectProperty::Spread(
+                            target @ (Expression::Identifier { .. }
+                            | Expression::Member { .. }
+                            | Expression::SuperMember { .. }),
+                        ) => rest = Some(Box::new(self.to_pattern(target, position)?)),
+                        ObjectProperty::Spread(_) | ObjectProperty::Method { .. } => {
+                            return Err(
+                                self.error_at("Invalid destructuring assignment target", position)
+                            );
+                        }
+                    }
+                }
+
+                Ok(Pattern::Object {
+                    properties: patterns,
+                    rest,
+                })
+            }
+            _ => Err(self.error_at("Invalid destructuring assignment target", position)),
+        }
+    }
+
+    /// Reinterprets an element of an array or object literal, where `a = 1`
+    /// gives the target `a` a default value.
+    fn to_pattern_element(
+        &self,
+        expression: Expression,
+        position: Position,
+    ) -> Result<Pattern, EndiumError> {
+        match expression {
+            Expression::Assignment {
+                operator: AssignmentOperator::Assign,
+                target,
+                value,
+                ..
+            } => Ok(Pattern::Default {
+                target: Box::new(self.to_pattern(*target, position)?),
+                default: value,
+            }),
+            Expression::DestructuringAssignment { target, value, .. } => Ok(Pattern::Default {
+                target: Box::new(target),
+                default: value,
+            }),
+            expression => self.to_pattern(expression, position),
+        }
+    }
+
+    /// Precedence climbing over the binary operators, lowest precedence first.
+    fn parse_binary(&mut self, min_precedence: u8) -> Result<Expression, EndiumError> {
+        let starts_with_unary = self.peek().is_some_and(Self::is_unary_operator);
+        let mut left = match self.peek() {
+            Some(Token::PrivateName(_)) if self.peek_at(1) == Some(&Token::In) => {
+                self.parse_private_in()?
+            }
+            _ => self.parse_unary()?,
+        };
+        // The logical operator `left` was last built with here, as opposed to
+        // one inside parentheses.
+        let mut left_logical: Option<LogicalOperator> = None;
+
+        while let Some((operator, precedence)) = self.peek().and_then(Self::infix_operator) {
+            if precedence < min_precedence
+                || (operator == InfixOperator::Binary(BinaryOperator::In) && !self.allow_in)
+            {
+                break;
+            }
+
+            if operator == InfixOperator::Binary(BinaryOperator::Exponent)
+                && starts_with_unary
+                && matches!(left, Expression::Unary { .. })
+            {
+                return Err(self.error(
+                    "Unary operator used immediately before exponentiation expression. Parenthesis must be used to disambiguate operator precedence",
+                ));
+            }
+
+            // `??` does not mix with `&&` or `||` without parentheses.
+            if let InfixOperator::Logical(operator) = operator
+                && let Some(left_operator) = left_logical
+                && (operator == LogicalOperator::Nullish)
+                    != (left_operator == LogicalOperator::Nullish)
+            {
+                return Err(self.unexpected());
+            }
+
+            self.advance(); // Skip the operator
+
+            // `**` is the only right-associative binary operator.
+            let right = if operator == InfixOperator::Binary(BinaryOperator::Exponent) {
+                self.parse_binary(precedence)?
+            } else if operator == InfixOperator::Logical(LogicalOperator::Nullish) {
+                // Stops before `&&`, leaving it to the check above.
+                self.parse_binary(3)?
+            } else {
+                self.parse_binary(precedence + 1)?
+            };
+
+            left_logical = match operator {
+                InfixOperator::Logical(operator) => Some(operator),
+                InfixOperator::Binary(_) => None,
+            };
+
+            left = match operator {
+                InfixOperator::Binary(operator) => Expression::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                InfixOperator::Logical(operator) => Expression::Logical {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// Parses `#name in object`, the only place a private name stands on its
+    /// own.
+    fn parse_private_in(&mut self) -> Result<Expression, EndiumError> {
+        let position = self.current_position();
+        let Some(Token::PrivateName(name)) = self.advance() else {
+            return Err(self.unexpected());
+        };
+        self.use_private_name(&name, position)?;
+        self.advance(); // Skip `in`
+
+        let object = self.parse_binary(8)?;
+
+        Ok(Expression::PrivateIn {
+            name,
+            object: Box::new(object),
+            position,
+        })
+    }
+
+    /// Runs `parse` with `in` allowed as an operator, as it is again inside
+    /// brackets and function bodies in the head of a for statement.
+    fn with_in_allowed<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, EndiumError>,
+    ) -> Result<T, EndiumError> {
+        let allow_in = std::mem::replace(&mut self.allow_in, true);
+        let result = parse(self);
+        self.allow_in = allow_in;
+
+        result
+    }
+
+    fn infix_operator(token: &Token) -> Option<(InfixOperator, u8)> {
+        let operator = match token {
+            Token::LogicalOr => (InfixOperator::Logical(LogicalOperator::Or), 1),
+            Token::NullishCoalescing => (InfixOperator::Logical(LogicalOperator::Nullish), 1),
+            Token::LogicalAnd => (InfixOperator::Logical(LogicalOperator::And), 2),
+            Token::BitwiseOr => (InfixOperator::Binary(BinaryOperator::BitwiseOr), 3),
+            Token::BitwiseXor => (InfixOperator::Binary(BinaryOperator::BitwiseXor), 4),
+            Token::BitwiseAnd => (InfixOperator::Binary(BinaryOperator::BitwiseAnd), 5),
+            Token::Equal => (InfixOperator::Binary(BinaryOperator::Equal), 6),
+            Token::NotEqual => (InfixOperator::Binary(BinaryOperator::NotEqual), 6),
+            Token::StrictEqual => (InfixOperator::Binary(BinaryOperator::StrictEqual), 6),
+            Token::StrictNotEqual => (InfixOperator::Binary(BinaryOperator::StrictNotEqual), 6),
+            Token::LessThan => (InfixOperator::Binary(BinaryOperator::LessThan), 7),
+            Token::LessThanOrEqual => (InfixOperator::Binary(BinaryOperator::LessThanOrEqual), 7),
+            Token::GreaterThan => (InfixOperator::Binary(BinaryOperator::GreaterThan), 7),
+            Token::GreaterThanOrEqual => {
+                (InfixOperator::Binary(BinaryOperator::GreaterThanOrEqual), 7)
+            }
+            Token::InstanceOf => (InfixOperator::Binary(BinaryOperator::InstanceOf), 7),
+            Token::In => (InfixOperator::Binary(BinaryOperator::In), 7),
+            Token::LeftShift => (InfixOperator::Binary(BinaryOperator::LeftShift), 8),
+            Token::RightShift => (InfixOperator::Binary(BinaryOperator::RightShift), 8),
+            Token::UnsignedRightShift => {
+                (InfixOperator::Binary(BinaryOperator::UnsignedRightShift), 8)
+            }
+            Token::Plus => (InfixOperator::Binary(BinaryOperator::Addition), 9),
+            Token::Minus => (InfixOperator::Binary(BinaryOperator::Subtraction), 9),
+            Token::Multiply => (InfixOperator::Binary(BinaryOperator::Multiplication), 10),
+            Token::Divide => (InfixOperator::Binary(BinaryOperator::Division), 10),
+            Token::Modulo => (InfixOperator::Binary(BinaryOperator::Modulo), 10),
+            Token::Exponent => (InfixOperator::Binary(BinaryOperator::Exponent), 11),
+            _ => return None,
+        };
+
+        Some(operator)
+    }
+
+    fn is_unary_operator(token: &Token) -> bool {
+        matches!(
+            token,
+            Token::Minus
+                | Token::Plus
+                | Token::LogicalNot
+                | Token::BitwiseNot
+                | Token::Typeof
+                | Token::Void
+                | Token::Delete
+        )
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, EndiumError> {
+        let unary_operator = match self.peek() {
+            Some(Token::Minus) => Some(UnaryOperator::Minus),
+            Some(Token::Plus) => Some(UnaryOperator::Plus),
+            Some(Token::LogicalNot) => Some(UnaryOperator::Not),
+            Some(Token::BitwiseNot) => Some(UnaryOperator::BitwiseNot),
+            Some(Token::Typeof) => Some(UnaryOperator::Typeof),
+            Some(Token::Void) => Some(UnaryOperator::Void),
+            Some(Token::Delete) => Some(UnaryOperator::Delete),
+            _ => None,
+        };
+
+        if let Some(operator) = unary_operator {
+            self.advance();
+            let position = self.current_position();
+            let argument = self.parse_unary()?;
+
+            if operator == UnaryOperator::Delete {
+                self.check_delete(&argument, position)?;
+            }
+
+            return Ok(Expression::Unary {
+                operator,
+                argument: Box::new(argument),
+            });
+        }
+
+        let position = self.current_position();
+        let operator = match self.peek() {
+            Some(Token::Increment) => UpdateOperator::Increment,
+            Some(Token::Decrement) => UpdateOperator::Decrement,
+            _ => return self.parse_postfix(),
+        };
+
+        self.advance();
+
+        let target = self.parse_unary()?;
+
+        if !Self::is_assignment_target(&target) {
+            return Err(self.error("Invalid left-hand side expression in prefix operation"));
+        }
+
+        Ok(Expression::Update {
+            operator,
+            prefix: true,
+            target: Box::new(target),
+            position,
+        })
+    }
+
+    /// Scripts run in strict mode, where `delete` can not remove a variable,
+    /// and no code can remove a private name.
+    fn check_delete(&self, argument: &Expression, position: Position) -> Result<(), EndiumError> {
+        let member = match argument {
+            Expression::OptionalChain(chain) => chain.as_ref(),
+            argument => argument,
+        };
+
+        match member {
+            Expression::Identifier { .. } => Err(self.error_at(
+                "Delete of an unqualified identifier in strict mode.",
+                position,
+            )),
+            Expression::Member {
+                property: MemberProperty::Private(_),
+                position,
+                ..
+            } => Err(self.error_at("Private fields can not be deleted", *position)),
+            _ => Ok(()),
+        }
+    }
+
+    fn parse_postfix(&mut self) -> Result<Expression, EndiumError> {
+        let position = self.current_position();
+        let expression = self.parse_call_member()?;
+
+        // A line break before `++`/`--` ends the expression instead.
+        if self.newline_before_current() {
+            return Ok(expression);
+        }
+
+        let operator = match self.peek() {
+            Some(Token::Increment) => UpdateOperator::Increment,
+            Some(Token::Decrement) => UpdateOperator::Decrement,
+            _ => return Ok(expression),
+        };
+
+        if !Self::is_assignment_target(&expression) {
+            return Err(self.error("Invalid left-hand side expression in postfix operation"));
+        }
+
+        self.advance();
+
+        Ok(Expression::Update {
+            operator,
+            prefix: false,
+            target: Box::new(expression),
+            position,
+        })
+    }
+
+    fn parse_call_member(&mut self) -> Result<Expression, EndiumError> {
+        let start = self.current_position();
+        let mut expression = match self.peek() {
+            Some(Token::New) => self.parse_new()?,
+            _ => self.parse_primary()?,
+        };
+
+        let mut is_optional_chain = false;
+
+        loop {
+            match self.peek() {
+                Some(Token::Dot) | Some(Token::LeftBracket) => {
+                    expression = self.parse_member_access(expression, false)?;
+                }
+                Some(Token::OptionalChaining) => {
+                    self.advance();
+                    is_optional_chain = true;
+
+                    expression = match self.peek() {
+                        Some(Token::LeftParen) => self.parse_call(expression, true, start)?,
+                        Some(Token::LeftBracket) => self.parse_member_access(expression, true)?,
+                        _ => {
+                            let (property, position) = self.parse_member_name()?;
+                            Expression::Member {
+                                object: Box::new(expression),
+                                property,
+                                optional: true,
+                                position,
+                            }
+                        }
+                    };
+                }
+                Some(Token::LeftParen) => {
+                    expression = self.parse_call(expression, false, start)?;
+                }
+                _ => break,
+            }
+        }
+
+        if is_optional_chain {
+            expression = Expression::OptionalChain(Box::new(expression));
+        }
+
+        Ok(expression)
+    }
+
+    /// Parses the arguments of a call to `callee`, which started at `start`.
+    fn parse_call(
+        &mut self,
+        callee: Expression,
+        optional: bool,
+        start: Position,
+    ) -> Result<Expression, EndiumError> {
+        // A failed call is reported at the name of what was called.
+        let position = callee.position().unwrap_or(start);
+        let arguments = self.parse_arguments()?;
+
+        Ok(Expression::Call {
+            callee: Box::new(callee),
+            arguments,
+            optional,
+            position,
+        })
+    }
+
+    /// Parses a `.name`, `.#name` or `[key]` access on `object`.
+    fn parse_member_access(
+        &mut self,
+        object: Expression,
+        optional: bool,
+    ) -> Result<Expression, EndiumError> {
+        let (property, position) = self.parse_member_property()?;
+
+        Ok(Expression::Member {
+            object: Box::new(object),
+            property,
+            optional,
+            position,
+        })
+    }
+
+    fn parse_member_property(&mut self) -> Result<(MemberProperty, Position), EndiumError> {
+        if self.eat(&Token::Dot) {
+            return self.parse_member_name();
+        }
+
+        let position = self.current_position();
+        self.expect(&Token::LeftBracket)?;
+        let property = self.with_in_allowed(Self::parse_expression)?;
+        self.expect(&Token::RightBracket)?;
+
+        Ok((MemberProperty::Computed(Box::new(property)), position))
+    }
+
+    /// Parses the name after `.` or `?.`, which may be a private name.
+    fn parse_member_name(&mut self) -> Result<(MemberProperty, Position), EndiumError> {
+        let position = self.current_position();
+
+        if let Some(Token::PrivateName(name)) = self.peek() {
+            let name = name.clone();
+            self.advance();
+            self.use_private_name(&name, position)?;
+            return Ok((MemberProperty::Private(name), position));
+        }
+
+        Ok((
+            MemberProperty::Identifier(self.parse_property_name()?),
+            position,
+        ))
+    }
+
+    /// Parses `new` with its callee, which may be a member access but not a
+    /// call, and its arguments, which may be left out. Also `new.target`.
+    fn parse_new(&mut self) -> Result<Expression, EndiumError> {
+        let position = self.current_position();
+        self.advance(); // Skip `new`
+
+        if self.eat(&Token::Dot) {
+            if !matches!(self.peek(), Some(Token::Identifier(name)) if name == "target") {
+                return Err(self.unexpected());
+            }
+            if self.function_depth == 0 {
+                return Err(self.error_at("new.target expression is not allowed here", position));
+            }
+
+            self.advance();
+            return Ok(Expression::NewTarget);
+        }
+
+        let mut callee = match self.peek() {
+            Some(Token::New) => self.parse_new()?,
+            _ => self.parse_primary()?,
+        };
+
+        while matches!(self.peek(), Some(Token::Dot) | Some(Token::LeftBracket)) {
+            callee = self.parse_member_access(callee, false)?;
+        }
+
+        if self.check(&Token::OptionalChaining) {
+            return Err(self.error("Invalid optional chain from new expression"));
+        }
+
+        let arguments = if self.check(&Token::LeftParen) {
+            self.parse_arguments()?
+        } else {
+            vec![]
+        };
+
+        Ok(Expression::New {
+            callee: Box::new(callee),
+            arguments,
+            position,
+        })
+    }
+
+    /// Parses `super(...)`, `super.name` or `super[key]` where the context
+    /// allows it.
+    fn parse_super(&mut self) -> Result<Expression, EndiumError> {
+        let position = self.current_position();
+        self.advance(); // Skip `super`
+
+        match self.peek() {
+            Some(Token::LeftParen) if self.super_context.call => Ok(Expression::SuperCall {
+                arguments: self.parse_arguments()?,
+                position,
+            }),
+            Some(Token::Dot) | Some(Token::LeftBracket) if self.super_context.property => {
+                match self.parse_member_property()? {
+                    (MemberProperty::Private(_), position) => {
+                        Err(self.error_at("Unexpected private field", position))
+                    }
+                    (property, position) => Ok(Expression::SuperMember { property, position }),
+                }
+            }
+            _ => Err(self.error_at("'super' keyword unexpected here", position)),
+        }
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Argument>, EndiumError> {
+        self.with_in_allowed(Self::parse_argument_list)
+    }
+
+    fn parse_argument_list(&mut self) -> Result<Vec<Argument>, EndiumError> {
+        self.expect(&Token::LeftParen)?;
+
+        let mut arguments: Vec<Argument> = vec![];
+
+        while !self.eat(&Token::RightParen) {
+            if self.eat(&Token::Spread) {
+                arguments.push(Argument::Spread(self.parse_assignment()?));
+            } else {
+                arguments.push(Argument::Expression(self.parse_assignment()?));
+            }
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightParen)?;
+                break;
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    fn parse_property_name(&mut self) -> Result<String, EndiumError> {
+        let name = match self.peek() {
+            Some(Token::Identifier(name)) => name.clone(),
+            Some(token) => match token.keyword() {
+                Some(keyword) => keyword.to_string(),
+                None => return Err(self.unexpected()),
+            },
+            None => return Err(self.unexpected()),
+        };
+
+        self.advance();
+        Ok(name)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, EndiumError> {
+        // Brackets, function bodies and templates start over with `in`.
+        self.with_in_allowed(Self::parse_primary_expression)
+    }
+
+    fn parse_primary_expression(&mut self) -> Result<Expression, EndiumError> {
+        let token = match self.peek() {
+            Some(token) => token.clone(),
+            None => return Err(self.unexpected()),
+        };
+
+        let expression = match token {
+            Token::Number(number) => {
+                self.advance();
+                Expression::Number(operations::string_to_number(&number))
+            }
+            Token::BigNumber(_) => {
+                return Err(self.error("BigInt literals are not supported"));
+            }
+            Token::String(string) => {
+                self.advance();
+                Expression::String(string)
+            }
+            Token::TemplateString(template_tokens) => {
+                self.advance();
+                self.parse_template(template_tokens)?
+            }
+            Token::True => {
+                self.advance();
+                Expression::Boolean(true)
+            }
+            Token::False => {
+                self.advance();
+                Expression::Boolean(false)
+            }
+            Token::Null => {
+                self.advance();
+                Expression::Null
+            }
+            Token::Undefined => {
+                self.advance();
+                Expression::Undefined
+            }
+            Token::Function => {
+                self.advance();
+                Expression::Function(self.parse_function(false)?)
+            }
+            Token::Class => Expression::Class(self.parse_class(false)?),
+            Token::Super => self.parse_super()?,
+            Token::This => {
+                self.advance();
+                Expression::This
+            }
+            Token::LeftBrace => {
+                self.advance();
+                self.parse_object_literal()?
+            }
+            Token::LeftBracket => {
+                self.advance();
+                self.parse_array_literal()?
+            }
+            Token::LeftParen => {
+                self.advance();
+                let expression = self.parse_expression()?;
+                self.expect(&Token::RightParen)?;
+                expression
+            }
+            _ => Expression::Identifier {
+                position: self.current_position(),
+                name: self.parse_binding_identifier()?,
+            },
+        };
+
+        Ok(expression)
+    }
+
+    fn parse_array_literal(&mut self) -> Result<Expression, EndiumError> {
+        let mut elements: Vec<ArrayElement> = vec![];
+
+        while !self.eat(&Token::RightBracket) {
+            // A comma with nothing before it leaves a hole.
+            if self.eat(&Token::Comma) {
+                elements.push(ArrayElement::Hole);
+                continue;
+            }
+
+            if self.eat(&Token::Spread) {
+                elements.push(ArrayElement::Spread(self.parse_assignment_cover()?));
+            } else {
+                elements.push(ArrayElement::Expression(self.parse_assignment_cover()?));
+            }
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightBracket)?;
+                break;
+            }
+        }
+
+        Ok(Expression::Array(elements))
+    }
+
+    fn parse_object_literal(&mut self) -> Result<Expression, EndiumError> {
+        let mut properties: Vec<ObjectProperty> = vec![];
+
+        while !self.eat(&Token::RightBrace) {
+            let property = self.parse_object_property()?;
+
+            if matches!(property, ObjectProperty::Prototype(_))
+                && properties
+                    .iter()
+                    .any(|property| matches!(property, ObjectProperty::Prototype(_)))
+            {
+                return Err(
+                    self.error("Duplicate __proto__ fields are not allowed in object literals")
+                );
+            }
+
+            properties.push(property);
+
+            if !self.eat(&Token::Comma) {
+                self.expect(&Token::RightBrace)?;
+                break;
+            }
+        }
+
+        Ok(Expression::Object(properties))
+    }
+
+    fn parse_object_property(&mut self) -> Result<ObjectProperty, EndiumError> {
+        if self.eat(&Token::Spread) {
+            return Ok(ObjectProperty::Spread(self.parse_assignment_cover()?));
+        }
+
+        // Only a plain identifier may stand on its own as `{ name }`.
+        let shorthand = match self.peek() {
+            Some(Token::Identifier(name)) => Some(name.clone()),
+            _ => None,
+        };
+
+        let kind = if self.is_modifier("get") {
+            MethodKind::Getter
+        } else if self.is_modifier("set") {
+            MethodKind::Setter
+        } else {
+            MethodKind::Method
+        };
+
+        let key_position = self.current_position();
+        let key = self.parse_property_key()?;
+
+        if kind != MethodKind::Method && !self.check(&Token::LeftParen) {
+            return Err(self.unexpected());
+        }
+
+        match self.peek() {
+            Some(Token::Colon) => {
+                self.advance();
+                let value = self.parse_assignment_cover()?;
+
+                match key {
+                    PropertyKey::Static(name) if name == "__proto__" => {
+                        Ok(ObjectProperty::Prototype(value))
+                    }
+                    key => Ok(ObjectProperty::Property { key, value }),
+                }
+            }
+            Some(Token::LeftParen) => {
+                let name = match &key {
+                    PropertyKey::Static(name) => Some(name.clone()),
+                    PropertyKey::Computed(_) => None,
+                };
+
+                let function = self.with_super_context(SuperContext::default(), |parser| {
+                    parser.parse_function_rest(name)
+                })?;
+                self.check_accessor_params(kind, &function, key_position)?;
+
+                Ok(ObjectProperty::Method {
+                    key,
+                    kind,
+                    function,
+                })
+            }
+            // `{ name = value }` is only valid as a pattern, which is not known
+            // until the literal is followed by `=`.
+            Some(Token::Assign) if shorthand.is_some() => {
+                self.cover_initializers.push(self.current_position());
+                self.advance();
+                let name = Expression::Identifier {
+                    name: shorthand.unwrap_or_default(),
+                    position: key_position,
+                };
+
+                Ok(ObjectProperty::Property {
+                    key,
+                    value: Expression::Assignment {
+                        operator: AssignmentOperator::Assign,
+                        target: Box::new(name),
+                        value: Box::new(self.parse_assignment()?),
+                        position: key_position,
+                    },
+                })
+            }
+            Some(Token::Comma) | Some(Token::RightBrace) if shorthand.is_some() => {
+                Ok(ObjectProperty::Property {
+                    key,
+                    value: Expression::Identifier {
+                        name: shorthand.unwrap_or_default(),
+                        position: key_position,
+                    },
+                })
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_property_key(&mut self) -> Result<PropertyKey, EndiumError> {
+        let key = match self.peek() {
+            Some(Token::String(string)) => string.clone(),
+            Some(Token::Number(number)) => {
+                operations::number_to_string(operations::string_to_number(number))
+            }
+            Some(Token::LeftBracket) => {
+                self.advance();
+                let key = self.parse_assignment()?;
+                self.expect(&Token::RightBracket)?;
+                return Ok(PropertyKey::Computed(Box::new(key)));
+            }
+            _ => return Ok(PropertyKey::Static(self.parse_property_name()?)),
+        };
+
+        self.advance();
+        Ok(PropertyKey::Static(key))
+    }
+
+    fn parse_template(&mut self, template_tokens: Vec<Token>) -> Result<Expression, EndiumError> {
+        let mut parts: Vec<TemplatePart> = vec![];
+
+        for template_token in template_tokens {
+            match template_token {
+                Token::String(string) => parts.push(TemplatePart::String(string)),
+                Token::TemplateExpr(expression_tokens) => {
+                    let mut expression_parser = Parser::new(expression_tokens);
+                    let expression = expression_parser.parse_expression()?;
+
+                    if !expression_parser.is_at_end() {
+                        return Err(expression_parser.unexpected());
+                    }
+
+                    parts.push(TemplatePart::Expression(expression));
+                }
+                _ => return Err(self.error("Invalid template literal")),
+            }
+        }
+
+        Ok(Expression::TemplateLiteral(parts))
+    }
+
+    fn is_assignment_target(expression: &Expression) -> bool {
+        matches!(
+            expression,
+            Expression::Identifier { .. }
+                | Expression::Member { .. }
+                | Expression::SuperMember { .. }
+        )
+    }
+
+    fn consume_semicolon(&mut self) -> Result<(), EndiumError> {
+        if self.eat(&Token::Semicolon) || self.can_insert_semicolon() {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    /// Automatic semicolon insertion applies before `}`, at the end of input
+    /// and after a line break.
+    fn can_insert_semicolon(&self) -> bool {
+        self.is_at_end() || self.check(&Token::RightBrace) || self.newline_before_current()
+    }
+
+    fn newline_before_current(&self) -> bool {
+        self.newline_before
+            .get(self.position)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    fn current_position(&self) -> Position {
+        self.positions
+            .get(self.position)
+            .copied()
+            .unwrap_or(self.end_position)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.position + offset)
+    }
+
+    fn check(&self, token: &Token) -> bool {
+        self.peek() == Some(token)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.check(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), EndiumError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn push_declarations(&mut self, scope: DeclarationScope) {
+        self.declarations.push(scope);
+    }
+
+    fn pop_declarations(&mut self) -> DeclarationScope {
+        self.declarations.pop().unwrap_or_default()
+    }
+
+    fn bound_names(patterns: &[Pattern]) -> HashSet<String> {
+        patterns
+            .iter()
+            .flat_map(Pattern::bound_names)
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn declare_pattern(
+        &mut self,
+        kind: VariableKind,
+        pattern: &Pattern,
+        position: Position,
+    ) -> Result<(), EndiumError> {
+        for name in pattern.bound_names() {
+            match kind {
+                VariableKind::Var => self.declare_var(name, position)?,
+                VariableKind::Let | VariableKind::Const => self.declare_lexical(name, position)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Declares a `let` or `const` name, or a function in a block, which no
+    /// other declaration in the same block may share.
+    fn declare_lexical(&mut self, name: &str, position: Position) -> Result<(), EndiumError> {
+        let scope = self.declarations.last_mut();
+
+        match scope {
+            Some(scope)
+                if scope.lexical.contains(name)
+                    || scope.var.contains(name)
+                    || scope.params.contains(name) =>
+            {
+                Err(Self::redeclaration(name, position))
+            }
+            Some(scope) => {
+                scope.lexical.insert(name.to_string());
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Declares a `var` name in every block up to the enclosing function body,
+    /// none of which may declare it lexically.
+    fn declare_var(&mut self, name: &str, position: Position) -> Result<(), EndiumError> {
+        for scope in self.declarations.iter_mut().rev() {
+            if scope.lexical.contains(name) {
+                return Err(Self::redeclaration(name, position));
+            }
+
+            scope.var.insert(name.to_string());
+
+            if scope.is_function {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Functions declared at the top of a function body or script behave like
+    /// `var`, and those declared in a block like `let`.
+    fn declare_function(&mut self, name: &str, position: Position) -> Result<(), EndiumError> {
+        match self.declarations.last_mut() {
+            Some(scope) if scope.is_function => {
+                if scope.lexical.contains(name) {
+                    return Err(Self::redeclaration(name, position));
+                }
+
+                scope.var.insert(name.to_string());
+                Ok(())
+            }
+            _ => self.declare_lexical(name, position),
+        }
+    }
+
+    fn redeclaration(name: &str, position: Position) -> EndiumError {
+        EndiumError::syntax_error(format!("Identifier '{}' has already been declared", name))
+            .at(position)
+    }
+
+    fn unexpected(&self) -> EndiumError {
+        match self.peek() {
+            None | Some(Token::Eof) => self.error("Unexpected end of input"),
+            Some(Token::Identifier(name)) => {
+                self.error(&format!("Unexpected identifier '{}'", name))
+            }
+            Some(Token::Number(_) | Token::BigNumber(_)) => self.error("Unexpected number"),
+            Some(Token::String(_)) => self.error("Unexpected string"),
+            Some(Token::TemplateString(_)) => self.error("Unexpected template string"),
+            Some(token) => self.error(&format!("Unexpected token '{}'", token)),
+        }
+    }
+
+    fn error(&self, message: &str) -> EndiumError {
+        self.error_at(message, self.current_position())
+    }
+
+    fn error_at(&self, message: &str, position: Position) -> EndiumError {
+        EndiumError::syntax_error(message.to_string()).at(position)
+    }
+
+    pub fn new(tokens: Vec<PositionedToken>) -> Self {
+        let mut significant_tokens: Vec<Token> = vec![];
+        let mut newline_before: Vec<bool> = vec![];
+        let mut positions: Vec<Position> = vec![];
+        let mut end_position = Position::default();
+        let mut pending_newline = false;
+
+        for PositionedToken { token, position } in tokens {
+            end_position = position;
+
+            match token {
+                Token::Newline => pending_newline = true,
+                Token::BlockComment(comment) => {
+                    pending_newline = pending_newline || comment.contains('\n');
+                }
+                Token::Comment(_) | Token::NoToken | Token::Eof => {}
+                _ => {
+                    significant_tokens.push(token);
+                    newline_before.push(pending_newline);
+                    positions.push(position);
+                    pending_newline = false;
+                }
+            }
+        }
+
+        Self {
+            tokens: significant_tokens,
+            newline_before,
+            positions,
+            end_position,
+            position: 0,
+            function_depth: 0,
+            loop_depth: 0,
+            switch_depth: 0,
+            labels: vec![],
+            declarations: vec![DeclarationScope {
+                is_function: true,
+                ..DeclarationScope::default()
+            }],
+            private_names: vec![],
+            super_context: SuperContext::default(),
+            allow_in: true,
+            cover_initializers: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::lexer::Lexer;
+
+    fn parse(source: &str) -> Result<Program, EndiumError> {
+        let tokens = Lexer::new(source).tokenize()?;
+        Parser::new(tokens).parse()
+    }
+
+    fn expression(source: &str) -> Expression {
+        match parse(source).map(|program| program.body.into_iter().next()) {
+            Ok(Some(Statement::Expression { expression })) => expression,
+            other => panic!("expected an expression statement, got {:?}", other),
+        }
+    }
+
+    fn error(source: &str) -> String {
+        match parse(source) {
+            Ok(_) => panic!("expected {:?} to be a syntax error", source),
+            Err(error) => error.message().to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_binary_operators_by_precedence() {
+        let Expression::Binary {
+            operator: BinaryOperator::Addition,
+            right,
+            ..
+        } = expression("1 + 2 * 3")
+        else {
+            panic!("expected an addition");
+        };
+
+        assert!(matches!(
+            *right,
+            Expression::Binary {
+                operator: BinaryOperator::Multiplication,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn hoists_var_names() {
+        let mut var_names = parse("var a = 1; function f() { var b; } { var c; }")
+            .unwrap()
+            .var_names;
+        var_names.sort();
+
+        assert_eq!(var_names, ["a", "c", "f"]);
+    }
+
+    #[test]
+    fn reinterprets_literals_as_destructuring_patterns() {
+        let Expression::DestructuringAssignment { target, .. } =
+            expression("[a, [b] = [1], ...c] = list")
+        else {
+            panic!("expected a destructuring assignment");
+        };
+        let Pattern::Array(elements) = target else {
+            panic!("expected an array pattern");
+        };
+
+        assert!(matches!(elements[0], Some(Pattern::Identifier(_))));
+        assert!(matches!(elements[1], Some(Pattern::Default { .. })));
+        assert!(matches!(elements[2], Some(Pattern::Rest(_))));
+    }
+
+    #[test]
+    fn accepts_shorthand_initializers_only_in_patterns() {
+        assert!(matches!(
+            expression("({ a = 1, b: { c = 2 } } = object)"),
+            Expression::DestructuringAssignment { .. }
+        ));
+        assert_eq!(
+            error("({ a = 1 })"),
+            "Invalid shorthand property initializer"
+        );
+        assert_eq!(
+            error("[{ a = 1 }]"),
+            "Invalid shorthand property initializer"
+        );
+    }
+
+    #[test]
+    fn accepts_super_properties_as_assignment_targets() {
+        assert!(
+            parse("class A extends Object { m() { super.x = 1; super.x++; [super.y] = [2]; } }")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_assignment_targets() {
+        assert_eq!(error("a + 1 = 2"), "Invalid left-hand side in assignment");
+        assert_eq!(
+            error("[a + 1] = list"),
+            "Invalid destructuring assignment target"
+        );
+        assert_eq!(
+            error("[...a, b] = list"),
+            "Rest element must be last element"
+        );
+    }
+
+    #[test]
+    fn describes_unexpected_tokens() {
+        assert_eq!(error("let x = ;"), "Unexpected token ';'");
+        assert_eq!(error("let x = 1 2"), "Unexpected number");
+        assert_eq!(error("(1 + "), "Unexpected end of input");
+    }
+
+    #[test]
+    fn reports_the_position_of_errors() {
+        let error = parse("let a = 1;\nlet b = );").unwrap_err();
+        let position = error.position().unwrap();
+
+        assert_eq!((position.line, position.column), (2, 9));
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// A place in the source: its byte offset, and the line and column it falls
+/// on. Lines and columns count from 1, and columns count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn new(offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// The position just past `ch`, when `ch` is the character at this one.
+    pub fn advance(&self, ch: char) -> Self {
+        if ch == '\n' {
+            Self::new(self.offset + ch.len_utf8(), self.line + 1, 1)
+        } else {
+            Self::new(self.offset + ch.len_utf8(), self.line, self.column + 1)
+        }
+    }
+}
+
+/// The start of the source.
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(0, 1, 1)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
@@ -1,101 +1,103 @@
-use crate::apis::type_variants::js_string::JSString;
 use crate::engine::state::State;
-use crate::engine::tokens::Token;
 use crate::engine::value_variant::JSValueVariant;
+use crate::errors::EndiumError;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-#[derive(Clone, Debug)]
+/// An environment record. Scopes form a chain through `parent`, and closures
+/// keep the scope they were created in alive by holding an `Rc` to it, so
+/// writes through any of them are seen by all.
+#[derive(Debug)]
 pub struct Scope {
     state: HashMap<String, State>,
-    parent_state: HashMap<String, State>,
+    parent: Option<Rc<RefCell<Scope>>>,
     depth: usize,
-    instructions: Vec<Token>,
-    children: Vec<Rc<RefCell<Scope>>>,
-
-    intialized_parent_state: bool,
 }
 
 impl Scope {
-    pub fn initialize_parent_state(&mut self) {
-        if self.intialized_parent_state == true {
-            return;
-        }
-
-        for (key, state) in self.parent_state.iter() {
-            match state.value() {
-                JSValueVariant::JSString(js_string) => {
-                    // self.state.insert(
-                    //     key.to_string(),
-                    //     ValueVariant::Pointer(js_string.heap_ptr().clone()),
-                    // );
+    pub fn insert_state(&mut self, key: String, state: State) {
+        self.state.insert(key, state);
+    }
 
-                    self.state.insert(key.to_string(), state.clone());
-                }
-                JSValueVariant::JSPointer(js_ptr) => {
-                    // self.state
-                    //     .insert(key.to_string(), JSValueVariant::JSPointer(js_ptr.clone()));
-                }
-                JSValueVariant::JSFunction(js_function) => {}
-                JSValueVariant::JSBoolean(js_bool) => {}
-                JSValueVariant::JSNumber(js_number) => {}
-                JSValueVariant::Null => {}
-                JSValueVariant::Undefined => {}
-            }
-        }
+    pub fn get_state(&self, key: &str) -> Option<&State> {
+        self.state.get(key)
     }
 
-    pub fn add_child(&mut self, scope: Scope) {
-        self.children.push(Rc::new(RefCell::new(scope)));
+    pub fn state(&self) -> &HashMap<String, State> {
+        &self.state
     }
 
-    pub fn instructions(&self) -> &Vec<Token> {
-        &self.instructions
+    /// Takes every binding out, for a scope nothing can reach anymore. They
+    /// are handed back to be dropped once the scope is no longer borrowed.
+    pub fn clear(&mut self) -> HashMap<String, State> {
+        std::mem::take(&mut self.state)
     }
 
-    pub fn insert_state(&mut self, key: String, state: State) {
-        self.state.insert(key, state);
+    pub fn parent(&self) -> Option<&Rc<RefCell<Scope>>> {
+        self.parent.as_ref()
     }
 
-    pub fn get_state(&self, key: &String) -> Option<&State> {
-        self.state.get(key)
+    /// Resolves `key` in this scope or the nearest enclosing one that declares it.
+    pub fn lookup(&self, key: &str) -> Option<State> {
+        match self.state.get(key) {
+            Some(state) => Some(state.clone()),
+            None => self.parent.as_ref()?.borrow().lookup(key),
+        }
     }
 
-    pub fn get_state_mut(&mut self, key: &String) -> Option<&mut State> {
-        self.state.get_mut(key)
+    pub fn has_binding(&self, key: &str) -> bool {
+        self.state.contains_key(key)
+            || self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.borrow().has_binding(key))
     }
 
-    pub fn state(&self) -> &HashMap<String, State> {
-        &self.state
+    /// Writes to the nearest scope that declares `key`, failing when no scope
+    /// declares it, the binding is not initialized yet or it is immutable.
+    pub fn assign(&mut self, key: &str, value: JSValueVariant) -> Result<(), EndiumError> {
+        match self.state.get_mut(key) {
+            Some(state) if !state.is_initialized() => Err(EndiumError::uninitialized(key)),
+            Some(state) if !state.is_mutable() => Err(EndiumError::assignment_to_constant()),
+            Some(state) => {
+                *state.value_mut() = value;
+                Ok(())
+            }
+            None => match &self.parent {
+                Some(parent) => parent.borrow_mut().assign(key, value),
+                None => Err(EndiumError::not_defined(key)),
+            },
+        }
     }
 
-    pub fn clear_state(&mut self) {
-        self.state.clear();
+    /// Initializes the nearest binding of `key`, which its declaration does
+    /// for `let`, `const` and classes. Returns false when that binding is
+    /// already initialized, or there is none.
+    pub fn initialize(&mut self, key: &str, value: JSValueVariant) -> bool {
+        match self.state.get_mut(key) {
+            Some(state) if state.is_initialized() => false,
+            Some(state) => {
+                *state = State::new(value, state.is_mutable());
+                true
+            }
+            None => match &self.parent {
+                Some(parent) => parent.borrow_mut().initialize(key, value),
+                None => false,
+            },
+        }
     }
 
-    pub fn new(parent: Option<Scope>, instructions: Vec<Token>) -> Self {
-        let parent_depth = parent.as_ref().map(|p| p.depth).unwrap_or(0);
-        let parent_state = match &parent {
-            Some(p) => p.state().clone(),
-            None => HashMap::new(),
+    pub fn new(parent: Option<Rc<RefCell<Scope>>>) -> Self {
+        let depth = match &parent {
+            Some(parent) => parent.borrow().depth + 1,
+            None => 0,
         };
 
-        let mut scope_self = Self {
-            parent_state,
-            intialized_parent_state: false,
+        Self {
             state: HashMap::new(),
-            depth: parent_depth,
-            children: vec![],
-            instructions,
-        };
-
-        scope_self.initialize_parent_state();
-
-        if let Some(mut scope_parent) = parent {
-            scope_parent.add_child(scope_self.clone());
+            parent,
+            depth,
         }
-
-        scope_self
     }
 }
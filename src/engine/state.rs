@@ -4,6 +4,9 @@ use crate::engine::value_variant::JSValueVariant;
 pub struct State {
     value: JSValueVariant,
     mutable: bool,
+    /// Whether the declaration has run. `let` and `const` bindings exist from
+    /// the start of their block but cannot be used until then.
+    initialized: bool,
 }
 
 impl State {
@@ -19,7 +22,24 @@ impl State {
         self.mutable
     }
 
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
     pub fn new(value: JSValueVariant, mutable: bool) -> Self {
-        Self { value, mutable }
+        Self {
+            value,
+            mutable,
+            initialized: true,
+        }
+    }
+
+    /// A binding in its temporal dead zone, before its declaration has run.
+    pub fn uninitialized(mutable: bool) -> Self {
+        Self {
+            value: JSValueVariant::Undefined,
+            mutable,
+            initialized: false,
+        }
     }
 }
@@ -1,3 +1,13 @@
+use crate::engine::position::Position;
+use std::fmt;
+
+/// A token and the position of its first character.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedToken {
+    pub token: Token,
+    pub position: Position,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     // Keywords
@@ -49,8 +59,10 @@ pub enum Token {
     BigNumber(String),
     String(String),
     Identifier(String),
+    /// A private class member name such as `#count`, without the `#`.
+    PrivateName(String),
     TemplateString(Vec<Token>),
-    TemplateExpr(Vec<Token>),
+    TemplateExpr(Vec<PositionedToken>),
 
     // Operators
     Plus,
@@ -68,6 +80,15 @@ pub enum Token {
     DivideAssign,
     ModuloAssign,
     ExponentAssign,
+    BitwiseAndAssign,
+    BitwiseOrAssign,
+    BitwiseXorAssign,
+    LeftShiftAssign,
+    RightShiftAssign,
+    UnsignedRightShiftAssign,
+    LogicalAndAssign,
+    LogicalOrAssign,
+    NullishAssign,
 
     // Increment/Decrement
     Increment,
@@ -125,5 +146,138 @@ pub enum Token {
 
     // End of file
     Eof,
+    #[allow(clippy::enum_variant_names)]
     NoToken,
 }
+
+impl Token {
+    pub fn keyword(&self) -> Option<&'static str> {
+        let keyword = match self {
+            Token::Const => "const",
+            Token::Let => "let",
+            Token::Var => "var",
+            Token::Function => "function",
+            Token::Return => "return",
+            Token::Yield => "yield",
+            Token::If => "if",
+            Token::Else => "else",
+            Token::Switch => "switch",
+            Token::Case => "case",
+            Token::Break => "break",
+            Token::Continue => "continue",
+            Token::Default => "default",
+            Token::For => "for",
+            Token::While => "while",
+            Token::Do => "do",
+            Token::Try => "try",
+            Token::Catch => "catch",
+            Token::Finally => "finally",
+            Token::Throw => "throw",
+            Token::Class => "class",
+            Token::Extends => "extends",
+            Token::Super => "super",
+            Token::This => "this",
+            Token::New => "new",
+            Token::Import => "import",
+            Token::Export => "export",
+            Token::From => "from",
+            Token::As => "as",
+            Token::Async => "async",
+            Token::Await => "await",
+            Token::With => "with",
+            Token::In => "in",
+            Token::Of => "of",
+            Token::InstanceOf => "instanceof",
+            Token::Typeof => "typeof",
+            Token::Delete => "delete",
+            Token::Void => "void",
+            Token::True => "true",
+            Token::False => "false",
+            Token::Null => "null",
+            Token::Undefined => "undefined",
+            _ => return None,
+        };
+
+        Some(keyword)
+    }
+}
+
+/// The token as it is written in source, as error messages quote it.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(keyword) = self.keyword() {
+            return write!(f, "{}", keyword);
+        }
+
+        let text = match self {
+            Token::Number(number) | Token::BigNumber(number) => number,
+            Token::String(string) => return write!(f, "\"{}\"", string),
+            Token::Identifier(name) => name,
+            Token::PrivateName(name) => return write!(f, "#{}", name),
+            Token::TemplateString(_) | Token::TemplateExpr(_) => "`",
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Multiply => "*",
+            Token::Divide => "/",
+            Token::Modulo => "%",
+            Token::Exponent => "**",
+            Token::Assign => "=",
+            Token::PlusAssign => "+=",
+            Token::MinusAssign => "-=",
+            Token::MultiplyAssign => "*=",
+            Token::DivideAssign => "/=",
+            Token::ModuloAssign => "%=",
+            Token::ExponentAssign => "**=",
+            Token::BitwiseAndAssign => "&=",
+            Token::BitwiseOrAssign => "|=",
+            Token::BitwiseXorAssign => "^=",
+            Token::LeftShiftAssign => "<<=",
+            Token::RightShiftAssign => ">>=",
+            Token::UnsignedRightShiftAssign => ">>>=",
+            Token::LogicalAndAssign => "&&=",
+            Token::LogicalOrAssign => "||=",
+            Token::NullishAssign => "??=",
+            Token::Increment => "++",
+            Token::Decrement => "--",
+            Token::Equal => "==",
+            Token::NotEqual => "!=",
+            Token::StrictEqual => "===",
+            Token::StrictNotEqual => "!==",
+            Token::LessThan => "<",
+            Token::LessThanOrEqual => "<=",
+            Token::GreaterThan => ">",
+            Token::GreaterThanOrEqual => ">=",
+            Token::LogicalAnd => "&&",
+            Token::LogicalOr => "||",
+            Token::LogicalNot => "!",
+            Token::BitwiseAnd => "&",
+            Token::BitwiseOr => "|",
+            Token::BitwiseXor => "^",
+            Token::BitwiseNot => "~",
+            Token::LeftShift => "<<",
+            Token::RightShift => ">>",
+            Token::UnsignedRightShift => ">>>",
+            Token::ArrowFunction => "=>",
+            Token::Ternary => "?",
+            Token::NullishCoalescing => "??",
+            Token::OptionalChaining => "?.",
+            Token::Spread => "...",
+            Token::Semicolon => ";",
+            Token::Comma => ",",
+            Token::Dot => ".",
+            Token::Colon => ":",
+            Token::LeftParen => "(",
+            Token::RightParen => ")",
+            Token::LeftBrace => "{",
+            Token::RightBrace => "}",
+            Token::LeftBracket => "[",
+            Token::RightBracket => "]",
+            Token::Newline => "\n",
+            Token::Comment(comment) => return write!(f, "//{}", comment),
+            Token::BlockComment(comment) => return write!(f, "/*{}*/", comment),
+            _ => "",
+        };
+
+        write!(f, "{}", text)
+    }
+}
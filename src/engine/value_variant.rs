@@ -1,14 +1,39 @@
-use crate::apis::type_variants::{js_bool::*, js_number::*, js_pointer::*, js_string::*, js_function::*};
+use crate::apis::type_variants::{
+    js_array::*, js_bool::*, js_function::*, js_number::*, js_object::*, js_string::*,
+};
 
 #[derive(Clone, Debug)]
 pub enum JSValueVariant {
     JSString(JSString),
     JSNumber(JSNumber),
     JSBoolean(JSBool),
-    // Array(JSArray),
-    // Object(JSObject),
+    JSArray(JSArray),
+    JSObject(JSObject),
     JSFunction(JSFunction),
-    JSPointer(JSPointer),
     Null,
     Undefined,
 }
+
+impl From<f64> for JSValueVariant {
+    fn from(number_value: f64) -> Self {
+        JSValueVariant::JSNumber(JSNumber::new(number_value))
+    }
+}
+
+impl From<bool> for JSValueVariant {
+    fn from(bool_value: bool) -> Self {
+        JSValueVariant::JSBoolean(JSBool::new(bool_value))
+    }
+}
+
+impl From<String> for JSValueVariant {
+    fn from(str_value: String) -> Self {
+        JSValueVariant::JSString(JSString::from(str_value))
+    }
+}
+
+impl From<&str> for JSValueVariant {
+    fn from(str_value: &str) -> Self {
+        JSValueVariant::JSString(JSString::from(str_value.to_string()))
+    }
+}
@@ -1,44 +1,199 @@
-use std::fmt::Debug;
+use std::fmt;
+use std::io;
 
-use crate::engine::tokens::Token;
+use crate::apis::stdlib;
+use crate::engine::position::Position;
+use crate::engine::value_variant::JSValueVariant;
 
-pub enum EndiumError {
-    // Critical
-    CriticalFileNotFound(String),
-    AssignmentToConstantVariable(String),
-    NotDefinedError(String),
-
-    // Error
-    UndefinedError(String),
-
-    // Warnings
-    FileNotFound(String),
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    SyntaxError,
+    TypeError,
+    RangeError,
+    ReferenceError,
+    /// A value thrown by script, which the error carries.
+    Exception,
+    FileNotFound,
+    /// A script file that exists but cannot be read as UTF-8 text.
+    UnreadableFile,
 }
 
-impl std::fmt::Display for EndiumError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ErrorKind {
+    /// The name the error is shown under, which for the built-in JS errors
+    /// is also the name of their constructor.
+    pub fn name(&self) -> &'static str {
         match self {
-            EndiumError::CriticalFileNotFound(file) => {
-                self.panic(format!("File {} not found", file))
-            }
-            EndiumError::FileNotFound(file) => self.error(format!("File {} not found", file)),
-            _ => {}
+            ErrorKind::SyntaxError => "SyntaxError",
+            ErrorKind::TypeError => "TypeError",
+            ErrorKind::RangeError => "RangeError",
+            ErrorKind::ReferenceError => "ReferenceError",
+            ErrorKind::Exception => "Uncaught",
+            ErrorKind::FileNotFound | ErrorKind::UnreadableFile => "Error",
         }
+    }
 
-        Ok(())
+    /// Whether script can catch the error, as an instance of the error
+    /// constructor of the same name.
+    pub fn is_catchable(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::SyntaxError
+                | ErrorKind::TypeError
+                | ErrorKind::RangeError
+                | ErrorKind::ReferenceError
+        )
     }
 }
 
+/// An error raised while lexing, parsing or running a script: what kind it
+/// is, its message, where in the source it was raised when that is known
+/// and, for exceptions, the value that was thrown and its stack.
+#[derive(Debug, Clone)]
+pub struct EndiumError {
+    kind: ErrorKind,
+    message: String,
+    position: Option<Position>,
+    // Both boxed to keep the `Err` side of every engine `Result` small.
+    value: Option<Box<JSValueVariant>>,
+    stack: Option<Box<str>>,
+}
+
 impl EndiumError {
-    pub fn panic<T: Debug>(&self, message: T) {
-        panic!("[Endium Critical Error]: {:#?}", message);
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
     }
 
-    pub fn error<T: Debug>(&self, message: T) {
-        eprintln!("[Endium Error]: {:#?}", message);
+    pub fn position(&self) -> Option<Position> {
+        self.position
     }
 
-    pub fn warning<T: Debug>(&self, message: T) {
-        println!("[Endium Warning]: {:#?}", message);
+    /// The value a `catch` block receives, if the error is a JS exception.
+    pub fn value(&self) -> Option<&JSValueVariant> {
+        self.value.as_deref()
+    }
+
+    /// The stack trace of a thrown error object.
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+
+    /// Records that the error was raised at `position`, unless it already
+    /// carries a position of its own.
+    pub fn at(mut self, position: Position) -> Self {
+        self.position.get_or_insert(position);
+        self
+    }
+
+    pub fn new(kind: ErrorKind, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            position: None,
+            value: None,
+            stack: None,
+        }
+    }
+
+    pub fn syntax_error(message: String) -> Self {
+        Self::new(ErrorKind::SyntaxError, message)
+    }
+
+    pub fn type_error(message: String) -> Self {
+        Self::new(ErrorKind::TypeError, message)
+    }
+
+    pub fn range_error(message: String) -> Self {
+        Self::new(ErrorKind::RangeError, message)
+    }
+
+    pub fn reference_error(message: String) -> Self {
+        Self::new(ErrorKind::ReferenceError, message)
+    }
+
+    pub fn not_defined(identifier: &str) -> Self {
+        Self::reference_error(format!("{} is not defined", identifier))
+    }
+
+    /// A `let`, `const` or class binding used before its declaration ran.
+    pub fn uninitialized(identifier: &str) -> Self {
+        Self::reference_error(format!(
+            "Cannot access '{}' before initialization",
+            identifier
+        ))
+    }
+
+    /// A length that is not a whole number from 0 to 2^32 - 1, or an array
+    /// that would grow past that.
+    pub fn invalid_array_length() -> Self {
+        Self::range_error(String::from("Invalid array length"))
+    }
+
+    /// A string that would be longer than `MAX_STRING_LENGTH`.
+    pub fn invalid_string_length() -> Self {
+        Self::range_error(String::from("Invalid string length"))
+    }
+
+    pub fn assignment_to_constant() -> Self {
+        Self::type_error(String::from("Assignment to constant variable."))
+    }
+
+    /// An exception thrown by script. Its message describes the thrown
+    /// value, and an error object brings its stack.
+    pub fn thrown(value: JSValueVariant) -> Self {
+        let message = stdlib::error::describe(&value);
+        let stack = stdlib::error::stack(&value).map(String::into_boxed_str);
+
+        Self {
+            value: Some(Box::new(value)),
+            stack,
+            ..Self::new(ErrorKind::Exception, message)
+        }
+    }
+
+    /// A failure raised by the engine once it is thrown as `error_object`.
+    /// It keeps its kind and message.
+    pub fn raised(self, error_object: JSValueVariant) -> Self {
+        Self {
+            kind: self.kind,
+            message: self.message,
+            ..Self::thrown(error_object)
+        }
+    }
+
+    pub fn file_not_found(file_path: &str) -> Self {
+        Self::new(
+            ErrorKind::FileNotFound,
+            format!("File {} not found", file_path),
+        )
+    }
+
+    pub fn unreadable_file(file_path: &str, error: &io::Error) -> Self {
+        let cause = match error.kind() {
+            io::ErrorKind::InvalidData => String::from("is not valid UTF-8"),
+            io::ErrorKind::IsADirectory => String::from("is a directory"),
+            io::ErrorKind::PermissionDenied => String::from("cannot be read: permission denied"),
+            _ => format!("cannot be read: {}", error),
+        };
+
+        Self::new(
+            ErrorKind::UnreadableFile,
+            format!("File {} {}", file_path, cause),
+        )
+    }
+}
+
+impl fmt::Display for EndiumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.value, &self.stack) {
+            (Some(_), Some(stack)) => write!(f, "Uncaught {}", stack),
+            (Some(_), None) => write!(f, "Uncaught {}", self.message),
+            (None, _) => write!(f, "{}: {}", self.kind.name(), self.message),
+        }
     }
 }
+
+impl std::error::Error for EndiumError {}
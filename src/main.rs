@@ -1,34 +1,87 @@
 mod apis;
 mod engine;
 mod errors;
-mod utils;
+mod reporter;
+#[cfg(test)]
+mod tests;
 
 use engine::*;
 
 use std::env;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::thread;
 
 use crate::errors::EndiumError;
+use crate::reporter::{ReportFormat, Reporter};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Native stack reserved for the interpretter, which recurses for every JS call.
+const INTERPRETTER_STACK_SIZE: usize = 1024 * 1024 * 1024;
 
-    let file_path = match args.get(1) {
+fn main() -> ExitCode {
+    let mut format = ReportFormat::Human;
+    let mut file_path = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => format = ReportFormat::Json,
+            _ => file_path = Some(arg),
+        }
+    }
+
+    let file_path = match file_path {
         Some(file_path) => file_path,
-        None => panic!("No file path provided"),
+        None => {
+            eprintln!("Usage: endium [--json] <file>");
+            return ExitCode::FAILURE;
+        }
     };
 
-    if let Ok(file_contents) = std::fs::read_to_string(&file_path) {
-        let mut lexer = lexer::Lexer::new(&file_contents);
-
-        let tokens = lexer.tokenize().unwrap();
+    let file_contents = match read_script(&file_path) {
+        Ok(file_contents) => file_contents,
+        Err(error) => {
+            let reporter = Reporter::new(format, &file_path, "");
+            eprintln!("{}", reporter.render(&error));
+            return ExitCode::FAILURE;
+        }
+    };
 
-        println!("{:#?}", tokens);
+    let reporter = Reporter::new(format, &file_path, &file_contents);
 
-        let mut interpretter = interpretter::Interpretter::new(Some(tokens), None);
-        let _ = interpretter.execute();
+    let interpretter_thread = thread::Builder::new()
+        .stack_size(INTERPRETTER_STACK_SIZE)
+        .spawn(move || match run(&file_contents) {
+            Ok(()) => true,
+            Err(error) => {
+                eprintln!("{}", reporter.render(&error));
+                false
+            }
+        });
 
-        // println!("{:#?}", interpretter.scope());
-    } else {
-        EndiumError::FileNotFound(file_path.to_string());
+    match interpretter_thread.map(|interpretter_thread| interpretter_thread.join()) {
+        Ok(Ok(true)) => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
     }
 }
+
+/// Reads a script file, telling a missing file apart from one that cannot
+/// be read.
+fn read_script(file_path: &str) -> Result<String, EndiumError> {
+    fs::read_to_string(file_path).map_err(|error| match error.kind() {
+        io::ErrorKind::NotFound => EndiumError::file_not_found(file_path),
+        _ => EndiumError::unreadable_file(file_path, &error),
+    })
+}
+
+/// Lexes, parses and runs a script.
+fn run(file_contents: &str) -> Result<(), EndiumError> {
+    let mut lexer = lexer::Lexer::new(file_contents);
+    let tokens = lexer.tokenize()?;
+
+    let mut parser = parser::Parser::new(tokens);
+    let program = parser.parse()?;
+
+    let mut interpretter = interpretter::Interpretter::new(None);
+    interpretter.execute(&program)
+}
@@ -0,0 +1,106 @@
+use crate::errors::EndiumError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Human,
+    Json,
+}
+
+/// Renders the errors raised by a script for whoever ran it.
+pub struct Reporter {
+    format: ReportFormat,
+    file_path: String,
+    source: String,
+}
+
+impl Reporter {
+    pub fn render(&self, error: &EndiumError) -> String {
+        match self.format {
+            ReportFormat::Human => self.render_human(error),
+            ReportFormat::Json => self.render_json(error),
+        }
+    }
+
+    /// Where the error was raised, the offending line with a caret under
+    /// that position, then the message.
+    fn render_human(&self, error: &EndiumError) -> String {
+        let position = match error.position() {
+            Some(position) => position,
+            None => return error.to_string(),
+        };
+
+        let line = self
+            .source
+            .lines()
+            .nth(position.line - 1)
+            .unwrap_or_default();
+        let line_number = position.line.to_string();
+
+        // Tabs are kept so that the caret lines up with the character above it.
+        let indent: String = line
+            .chars()
+            .take(position.column - 1)
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        format!(
+            "{}:{}\n{} | {}\n{} | {}^\n\n{}",
+            self.file_path,
+            position,
+            line_number,
+            line,
+            " ".repeat(line_number.len()),
+            indent,
+            error
+        )
+    }
+
+    /// The error as a single JSON object, with its position and stack when
+    /// known.
+    fn render_json(&self, error: &EndiumError) -> String {
+        let mut fields = vec![
+            format!("\"kind\":{}", json_string(&format!("{:?}", error.kind()))),
+            format!("\"message\":{}", json_string(error.message())),
+            format!("\"file\":{}", json_string(&self.file_path)),
+        ];
+
+        if let Some(position) = error.position() {
+            fields.push(format!("\"line\":{}", position.line));
+            fields.push(format!("\"column\":{}", position.column));
+            fields.push(format!("\"offset\":{}", position.offset));
+        }
+
+        if let Some(stack) = error.stack() {
+            fields.push(format!("\"stack\":{}", json_string(stack)));
+        }
+
+        format!("{{{}}}", fields.join(","))
+    }
+
+    pub fn new(format: ReportFormat, file_path: &str, source: &str) -> Self {
+        Self {
+            format,
+            file_path: file_path.to_string(),
+            source: source.to_string(),
+        }
+    }
+}
+
+fn json_string(string: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for ch in string.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,91 @@
+use super::{error, run};
+
+#[test]
+fn transforms_and_searches_arrays() {
+    let source = "
+        var numbers = [3, 1, 2];
+        var result = [
+            numbers.map(function (n) { return n * 2; }).join(),
+            numbers.filter(function (n) { return n > 1; }).join(),
+            numbers.reduce(function (sum, n) { return sum + n; }, 0),
+            numbers.indexOf(2),
+            numbers.includes(4),
+            numbers.find(function (n) { return n < 3; }),
+            numbers.slice(-2).join(),
+        ].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "6,2,4 | 3,2 | 6 | 2 | false | 1 | 1,2"
+    );
+}
+
+#[test]
+fn mutates_arrays_in_place() {
+    let source = "
+        var a = [1, 2, 3, 4];
+        var removed = a.splice(1, 2, 'x');
+        a.unshift(0);
+        a.push(5);
+        a.reverse();
+        var result = a.join() + ' / ' + removed.join() + ' / ' + [10, 9, 1].sort().join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "5,4,x,1,0 / 2,3 / 1,10,9");
+}
+
+#[test]
+fn works_on_array_likes() {
+    let source = "
+        function collect() {
+            Array.prototype.push.call(arguments, 'c');
+            return Array.prototype.join.call(arguments, '-') + ' ' + arguments.length;
+        }
+        var result = collect('a', 'b');
+    ";
+
+    assert_eq!(run(source).unwrap(), "a-b-c 3");
+}
+
+#[test]
+fn skips_holes() {
+    let source = "
+        var calls = 0;
+        [1, , 3].forEach(function () { calls++; });
+        var result = calls + ' ' + [1, , 3].join('-');
+    ";
+
+    assert_eq!(run(source).unwrap(), "2 1--3");
+}
+
+#[test]
+fn flattens_nested_arrays() {
+    let source = "
+        var result = [1, [2, [3, [4]]]].flat(Infinity).join() + ' ' +
+            [1, 2].flatMap(function (n) { return [n, [n]]; }).length;
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,2,3,4 4");
+}
+
+#[test]
+fn throws_on_flattening_an_array_nested_in_itself() {
+    let source = "
+        var a = [];
+        a[0] = a;
+        var result;
+        try { a.flat(Infinity); } catch (e) { result = e instanceof RangeError; }
+    ";
+
+    assert_eq!(run(source).unwrap(), "true");
+    assert_eq!(
+        error("var a = [1]; a.push(a); a.flat(Infinity);"),
+        "RangeError: Maximum call stack size exceeded"
+    );
+}
+
+#[test]
+fn rejects_non_callable_callbacks() {
+    assert_eq!(error("[1].map(5);"), "TypeError: 5 is not a function");
+}
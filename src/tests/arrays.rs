@@ -0,0 +1,53 @@
+use super::{error, run};
+
+#[test]
+fn indexes_arrays() {
+    let source = "
+        var a = [1, 'two', [3]];
+        a[1] = 2;
+        a['2'] = 3;
+        var result = [a.length, a[0], a[1], a[2], a[3], a[-1], a['1']].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "3,1,2,3,,,2");
+}
+
+#[test]
+fn follows_length_semantics() {
+    let source = "
+        var a = [1, 2, 3];
+        a[5] = 6;
+        var grown = a.length;
+        a.length = 2;
+        var truncated = a.join();
+        a.length = 4;
+        var holes = [, 1, , ];
+        var result = [grown, truncated, a.length, a[3], holes.length, 0 in holes, 1 in holes].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "6,1,2,4,,3,false,true");
+}
+
+#[test]
+fn keeps_named_properties_apart_from_elements() {
+    let source = "
+        var a = [1, 2];
+        a.name = 'pair';
+        a['01'] = 'not an index';
+        var keys = [];
+        for (var key in a) keys.push(key);
+        var result = [a.length, a.name, keys.join(), String([1, [2, [3]]]), [null, undefined].join('-')].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "2 | pair | 0,1,name,01 | 1,2,3 | -");
+}
+
+#[test]
+fn rejects_invalid_lengths() {
+    assert_eq!(error("[].length = -1;"), "RangeError: Invalid array length");
+    assert_eq!(error("new Array(1.5);"), "RangeError: Invalid array length");
+    assert_eq!(
+        error("var a = []; a[4294967294] = 1; a.push(2);"),
+        "RangeError: Invalid array length"
+    );
+}
@@ -0,0 +1,47 @@
+use super::{error, run};
+
+#[test]
+fn takes_this_from_the_enclosing_function() {
+    let source = "
+        var counter = {
+            count: 0,
+            start: function () {
+                [1, 2, 3].forEach(() => { this.count++; });
+                return (() => () => this.count)()();
+            },
+        };
+        var plain = {
+            value: 'plain',
+            read: function () { return [1].map(function () { return this; })[0]; },
+        };
+        var result = [counter.start(), plain.read()].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "3,");
+}
+
+#[test]
+fn returns_concise_bodies() {
+    let source = "
+        var square = x => x * x;
+        var add = (a, b) => a + b;
+        var make = () => ({ made: true });
+        var nothing = () => {};
+        var curried = a => b => c => a + b + c;
+        var result = [square(4), add(1, 2), make().made, nothing(), curried(1)(2)(3)].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "16,3,true,,6");
+}
+
+#[test]
+fn has_no_arguments_or_construct() {
+    let source = "
+        function outer() { return (() => arguments[0])(); }
+        var result = outer('from outer');
+    ";
+
+    assert_eq!(run(source).unwrap(), "from outer");
+    let error = error("var a = () => 1; new a();");
+    assert!(error.starts_with("TypeError: ") && error.ends_with(" is not a constructor"));
+}
@@ -0,0 +1,83 @@
+use super::{error, run};
+
+#[test]
+fn constructs_instances_with_methods() {
+    let source = "
+        class Point {
+            constructor(x, y) { this.x = x; this.y = y; }
+            sum() { return this.x + this.y; }
+            get norm() { return this.x * this.x + this.y * this.y; }
+            set both(value) { this.x = this.y = value; }
+            static origin() { return new Point(0, 0); }
+        }
+        var p = new Point(1, -2);
+        var sum = p.sum();
+        var norm = p.norm;
+        p.both = 4;
+        var keys = [];
+        for (var key in p) keys.push(key);
+        var result = [sum, norm, p.sum(), Point.origin().sum(), typeof Point, keys.join()].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "-1 | 5 | 8 | 0 | function | x,y");
+}
+
+#[test]
+fn inherits_and_calls_super() {
+    let source = "
+        class Animal {
+            constructor(name) { this.name = name; }
+            speak() { return this.name + ' makes a sound'; }
+            static create(name) { return new this(name); }
+        }
+        class Dog extends Animal {
+            constructor(name) { super(name + ' the dog'); }
+            speak() { return super.speak() + ', woof'; }
+        }
+        class Puppy extends Dog {}
+        var puppy = Puppy.create('Rex');
+        var result = [puppy.speak(), puppy instanceof Dog, puppy instanceof Animal, puppy.constructor === Puppy].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "Rex the dog makes a sound, woof | true | true | true"
+    );
+}
+
+#[test]
+fn keeps_fields_and_private_members() {
+    let source = "
+        class Counter {
+            count = 0;
+            #secret = 'hidden';
+            static instances = 0;
+            constructor() { Counter.instances++; }
+            #bump() { return ++this.count; }
+            increment() { this.#bump(); return this; }
+            reveal() { return this.#secret; }
+            static has(o) { return #secret in o; }
+        }
+        var c = new Counter().increment().increment();
+        new Counter();
+        var result = [c.count, c.reveal(), Counter.instances, Counter.has(c), Counter.has({})].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "2,hidden,2,true,false");
+}
+
+#[test]
+fn enforces_class_rules() {
+    assert_eq!(
+        error("class A {} A();"),
+        "TypeError: Class constructor A cannot be invoked without 'new'"
+    );
+    assert_eq!(
+        error("class A {} class B extends A { constructor() { this.x = 1; } } new B();"),
+        "ReferenceError: Must call super constructor in derived class before accessing 'this' or returning from derived constructor"
+    );
+    assert_eq!(
+        error("class A { #x; static read(o) { return o.#x; } } A.read({});"),
+        "TypeError: Cannot read private member #x from an object whose class did not declare it"
+    );
+}
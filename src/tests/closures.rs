@@ -0,0 +1,61 @@
+use super::run;
+
+#[test]
+fn captures_variables_by_reference() {
+    let source = "
+        function counter() {
+            var count = 0;
+            return {
+                increment: function () { return ++count; },
+                read: function () { return count; },
+            };
+        }
+        var a = counter();
+        var b = counter();
+        a.increment();
+        a.increment();
+        b.increment();
+        var x = 1;
+        var readX = function () { return x; };
+        x = 2;
+        var result = [a.read(), b.read(), readX()].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "2,1,2");
+}
+
+#[test]
+fn binds_let_afresh_for_each_iteration() {
+    let source = "
+        var withLet = [];
+        for (let i = 0; i < 3; i++) withLet.push(function () { return i; });
+        var withVar = [];
+        for (var j = 0; j < 3; j++) withVar.push(function () { return j; });
+        var forOf = [];
+        for (const item of ['a', 'b']) forOf.push(function () { return item; });
+        var call = function (f) { return f(); };
+        var result = [withLet.map(call), withVar.map(call), forOf.map(call)].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "0,1,2 | 3,3,3 | a,b");
+}
+
+#[test]
+fn nests_scopes() {
+    let source = "
+        function outer(a) {
+            return function middle(b) {
+                return function inner(c) { return a + b + c; };
+            };
+        }
+        var shadow = 'global';
+        function shadowed() {
+            var shadow = 'local';
+            { let shadow = 'block'; }
+            return shadow;
+        }
+        var result = [outer(1)(2)(3), shadowed(), shadow].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "6,local,global");
+}
@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use super::{execute, run};
+use crate::apis::type_variants::js_function::FunctionKind;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::value_variant::JSValueVariant;
+
+/// Leaves enough scopes behind closures for the collector to run.
+const CHURN: &str = "
+    function churn() {
+        for (var i = 0; i < 500; i++) {
+            (function () { var o = {}; o.f = function () { return o; }; })();
+        }
+    }
+";
+
+fn with_churn(source: &str) -> String {
+    run(&format!("{}{}", CHURN, source)).unwrap()
+}
+
+#[test]
+fn keeps_escaping_closures_alive() {
+    let source = "
+        function counter() { var count = 0; return function () { return ++count; }; }
+        var next = counter();
+        next();
+        churn();
+        next();
+        var result = next();
+    ";
+
+    assert_eq!(with_churn(source), "3");
+}
+
+#[test]
+fn keeps_the_scope_of_getters_alive() {
+    let source = "
+        function box(value) { return { get value() { return value; }, set value(v) { value = v; } }; }
+        var b = box(1);
+        churn();
+        b.value = b.value + 1;
+        churn();
+        var result = b.value;
+    ";
+
+    assert_eq!(with_churn(source), "2");
+}
+
+#[test]
+fn keeps_private_fields_alive() {
+    let source = "
+        function make() {
+            var secret = 'hidden';
+            class Vault { #value = secret; reveal() { return this.#value; } }
+            return new Vault();
+        }
+        var vault = make();
+        churn();
+        var result = vault.reveal();
+    ";
+
+    assert_eq!(with_churn(source), "hidden");
+}
+
+#[test]
+fn keeps_bound_functions_alive() {
+    let source = "
+        function make() {
+            var scale = 10;
+            return function (a, b) { return (a + b) * scale + this.offset; }.bind({ offset: 1 }, 2);
+        }
+        var bound = make();
+        churn();
+        var result = bound(3);
+    ";
+
+    assert_eq!(with_churn(source), "51");
+}
+
+#[test]
+fn keeps_thrown_closures_alive() {
+    let source = "
+        function fail() { var reason = 'thrown'; throw function () { return reason; }; }
+        var caught;
+        try { fail(); } catch (error) { caught = error; }
+        churn();
+        var result = caught();
+    ";
+
+    assert_eq!(with_churn(source), "thrown");
+}
+
+#[test]
+fn frees_cycles_nothing_holds() {
+    let mut interpretter = Interpretter::new(None);
+    let source = "
+        var keep;
+        function make() { var o = {}; keep = function () { return o; }; o.f = keep; }
+        make();
+    ";
+    execute(&mut interpretter, source).unwrap();
+
+    let keep = interpretter
+        .scope()
+        .borrow()
+        .get_state("keep")
+        .map(|state| state.value().clone());
+    let scope = match keep {
+        Some(JSValueVariant::JSFunction(keep)) => match keep.kind() {
+            FunctionKind::Script { scope, .. } => Rc::downgrade(scope),
+            _ => panic!("expected a script function"),
+        },
+        _ => panic!("expected `keep` to hold a function"),
+    };
+
+    execute(
+        &mut interpretter,
+        &format!("{}keep = null; churn();", CHURN),
+    )
+    .unwrap();
+
+    assert!(scope.upgrade().is_none());
+}
@@ -0,0 +1,53 @@
+use super::{error, run};
+
+#[test]
+fn constructs_with_new() {
+    let source = "
+        function Person(name) { this.name = name; }
+        Person.prototype.greet = function () { return 'hi ' + this.name; };
+        function ReturnsObject() { this.ignored = true; return { replaced: true }; }
+        function ReturnsPrimitive() { this.kept = true; return 5; }
+        function Target() { return new.target === Target; }
+        var result = [
+            new Person('Ann').greet(),
+            new ReturnsObject().replaced,
+            new ReturnsPrimitive().kept,
+            new Target() instanceof Target,
+            Target(),
+            new Person instanceof Person,
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "hi Ann,true,true,true,false,true");
+}
+
+#[test]
+fn binds_this_by_how_functions_are_called() {
+    let source = "
+        function who() { return this === undefined ? 'undefined' : this.name; }
+        var a = { name: 'a', who };
+        var b = { name: 'b' };
+        var detached = a.who;
+        var bound = who.bind(b);
+        var result = [
+            a.who(),
+            detached(),
+            who.call(b),
+            who.apply({ name: 'c' }, []),
+            bound(),
+            bound.call(a),
+            a['who'](),
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "a,undefined,b,c,b,b,a");
+}
+
+#[test]
+fn rejects_what_cannot_be_constructed() {
+    assert_eq!(error("new 5;"), "TypeError: 5 is not a constructor");
+    assert_eq!(
+        error("var o = {}; new o.missing();"),
+        "TypeError: undefined is not a constructor"
+    );
+}
@@ -0,0 +1,77 @@
+use super::{error, run};
+
+#[test]
+fn branches() {
+    let source = "
+        function sign(n) {
+            if (n > 0) return 'positive';
+            else if (n < 0) return 'negative';
+            else return 'zero';
+        }
+        var result = [sign(3), sign(-1), sign(0)].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "positive,negative,zero");
+}
+
+#[test]
+fn loops() {
+    let source = "
+        var log = [];
+        var i = 0;
+        while (i < 3) log.push('w' + i++);
+        do { log.push('d' + i); } while (i < 0);
+        for (var j = 0, k = 10; j < 2; j++, k--) log.push(j + k);
+        for (;;) { if (++i > 5) break; }
+        var result = log.join() + ' ' + i;
+    ";
+
+    assert_eq!(run(source).unwrap(), "w0,w1,w2,d3,10,10 6");
+}
+
+#[test]
+fn breaks_and_continues() {
+    let source = "
+        var odd = [];
+        for (var i = 0; i < 10; i++) {
+            if (i % 2 === 0) continue;
+            if (i > 7) break;
+            odd.push(i);
+        }
+        var pairs = [];
+        outer: for (var a = 0; a < 3; a++) {
+            for (var b = 0; b < 3; b++) {
+                if (b === 2) continue outer;
+                if (a === 2) break outer;
+                pairs.push('' + a + b);
+            }
+        }
+        var result = odd.join() + ' | ' + pairs.join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,3,5,7 | 00,01,10,11");
+}
+
+#[test]
+fn iterates_keys_and_values() {
+    let source = "
+        var keys = [];
+        for (var key in { a: 1, b: 2 }) keys.push(key);
+        var values = [];
+        for (var value of [1, 2, 3]) values.push(value * 2);
+        var characters = [];
+        for (var character of 'hi') characters.push(character);
+        var result = [keys.join(), values.join(), characters.join()].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "a,b | 2,4,6 | h,i");
+}
+
+#[test]
+fn rejects_stray_jumps() {
+    assert_eq!(error("break;"), "SyntaxError: Illegal break statement");
+    assert_eq!(
+        error("function f() { continue; }"),
+        "SyntaxError: Illegal continue statement: no surrounding iteration statement"
+    );
+}
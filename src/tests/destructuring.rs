@@ -0,0 +1,49 @@
+use super::{error, run};
+
+#[test]
+fn destructures_declarations() {
+    let source = "
+        var [a, , b = 'default', ...rest] = [1, 2, undefined, 4, 5];
+        const { x, y: renamed, z = 'z', nested: { deep }, ...others } = { x: 1, y: 2, nested: { deep: 3 }, p: 4, q: 5 };
+        let [[inner], { length }] = [['in'], 'four'];
+        var result = [a, b, rest.join(), x, renamed, z, deep, Object.getOwnPropertyDescriptor(others, 'p').value, inner, length].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,default,4,5,1,2,z,3,4,in,4");
+}
+
+#[test]
+fn destructures_assignments() {
+    let source = "
+        var a = 1, b = 2, o = {};
+        [a, b] = [b, a];
+        ({ first: o.first, second: o['second'] = 'fallback' } = { first: 'f' });
+        var values = [];
+        [values[0], ...values[1]] = 'abc';
+        var result = [a, b, o.first, o.second, values[0], values[1].join('')].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "2,1,f,fallback,a,bc");
+}
+
+#[test]
+fn destructures_parameters() {
+    let source = "
+        function area({ width, height = width }) { return width * height; }
+        function head([first, ...others] = ['none']) { return first + others.length; }
+        var pairs = [[1, 'one'], [2, 'two']].map(([n, name]) => name + n);
+        var result = [area({ width: 3 }), area({ width: 2, height: 5 }), head(), head([7, 8, 9]), pairs.join()].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "9,10,none0,9,one1,two2");
+}
+
+#[test]
+fn rejects_what_cannot_be_destructured() {
+    assert_eq!(
+        error("var { a } = null;"),
+        "TypeError: Cannot destructure 'null' as it is null."
+    );
+    assert_eq!(error("var [b] = {};"), "TypeError: object is not iterable");
+    assert_eq!(error("var [c] = 5;"), "TypeError: number 5 is not iterable");
+}
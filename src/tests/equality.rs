@@ -0,0 +1,95 @@
+use super::{error, run};
+
+#[test]
+fn compares_loosely() {
+    let source = "
+        var object = {};
+        var valued = { valueOf: function () { return 1; } };
+        var result = [
+            null == undefined,
+            null == 0,
+            undefined == false,
+            '' == 0,
+            '1' == 1,
+            true == 1,
+            '0x10' == 16,
+            NaN == NaN,
+            object == object,
+            object == {},
+            valued == 1,
+            valued == '1',
+            [1, 2] == '1,2',
+            [] == false,
+        ].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "true,false,false,true,true,true,true,false,true,false,true,true,true,true"
+    );
+}
+
+#[test]
+fn compares_strictly_and_by_same_value() {
+    let source = "
+        var result = [
+            1 === 1.0,
+            '1' === 1,
+            NaN === NaN,
+            0 === -0,
+            null === undefined,
+            Object.is(NaN, NaN),
+            Object.is(0, -0),
+            Object.is('a', 'a'),
+            [NaN].includes(NaN),
+            [NaN].indexOf(NaN),
+        ].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "true,false,false,true,false,true,false,true,true,-1"
+    );
+}
+
+#[test]
+fn compares_relationally() {
+    let source = "
+        var result = [
+            1 < 2,
+            '10' < '9',
+            '10' < 9,
+            'a' < 'b',
+            'B' < 'a',
+            null < 1,
+            undefined < 1,
+            NaN <= NaN,
+            2 >= '2',
+            [2] > 1,
+            '\u{1F600}' > '\u{FFFF}',
+        ].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "true,true,false,true,true,true,false,false,true,true,false"
+    );
+}
+
+#[test]
+fn converts_operands_in_order() {
+    let source = "
+        var log = [];
+        var left = { valueOf: function () { log.push('left'); return 1; } };
+        var right = { valueOf: function () { log.push('right'); return 2; } };
+        var less = left < right;
+        var greater = left > right;
+        var result = [less, greater, log.join()].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "true | false | left,right,left,right");
+    assert_eq!(
+        error("({ valueOf: function () { throw new Error('no'); } }) == 1;"),
+        "Error: no"
+    );
+}
@@ -0,0 +1,78 @@
+use super::{error, run};
+
+#[test]
+fn catches_thrown_values() {
+    let source = "
+        var caught = [];
+        try { throw 'text'; } catch (e) { caught.push(e); }
+        try { throw { code: 7 }; } catch ({ code }) { caught.push(code); }
+        try { throw new TypeError('bad'); } catch (e) { caught.push(e.name + ': ' + e.message); }
+        try { null.x; } catch (e) { caught.push(e instanceof TypeError); }
+        try { throw 1; } catch { caught.push('no binding'); }
+        var result = caught.join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "text | 7 | TypeError: bad | true | no binding"
+    );
+}
+
+#[test]
+fn runs_finally_blocks() {
+    let source = "
+        var log = [];
+        function f() {
+            try { return 'try'; } finally { log.push('finally'); }
+        }
+        function g() {
+            try { throw 1; } catch (e) { return 'catch'; } finally { return 'overridden'; }
+        }
+        function h() {
+            for (var i = 0; i < 3; i++) {
+                try { if (i === 1) break; } finally { log.push('loop ' + i); }
+            }
+        }
+        h();
+        var result = [f(), g(), log.join()].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "try | overridden | loop 0,loop 1,finally"
+    );
+}
+
+#[test]
+fn builds_the_error_hierarchy() {
+    let source = "
+        var e = new RangeError('far');
+        var result = [
+            e instanceof RangeError,
+            e instanceof Error,
+            String(e),
+            Error('called').message,
+            new Error().message === '',
+            Object.getOwnPropertyDescriptor(e, 'message').enumerable,
+            typeof e.stack,
+        ].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "true,true,RangeError: far,called,true,false,string"
+    );
+}
+
+#[test]
+fn rethrows_from_catch_and_finally() {
+    assert_eq!(
+        error("try { throw new Error('first'); } catch (e) { throw new Error('second'); }"),
+        "Error: second"
+    );
+    assert_eq!(
+        error("try { throw 1; } finally { throw new SyntaxError('last'); }"),
+        "SyntaxError: last"
+    );
+    assert_eq!(error("throw 42;"), "42");
+}
@@ -0,0 +1,34 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use crate::errors::ErrorKind;
+use crate::read_script;
+
+#[test]
+fn tells_why_a_script_cannot_be_read() {
+    let directory = env::temp_dir().join(format!("endium-files-{}", process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let script = directory.join("script.js");
+    fs::write(&script, "var a = 1;").unwrap();
+    let invalid = directory.join("invalid.js");
+    fs::write(&invalid, b"var a = '\xff';").unwrap();
+    let missing = directory.join("missing.js");
+
+    let path = |path: &std::path::Path| path.to_str().unwrap().to_string();
+    let error = |path: String| read_script(&path).unwrap_err();
+
+    assert_eq!(read_script(&path(&script)).unwrap(), "var a = 1;");
+    assert_eq!(error(path(&missing)).kind(), ErrorKind::FileNotFound);
+    assert_eq!(
+        error(path(&invalid)).message(),
+        format!("File {} is not valid UTF-8", path(&invalid))
+    );
+    assert_eq!(
+        error(path(&directory)).message(),
+        format!("File {} is a directory", path(&directory))
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,65 @@
+use super::{error, run};
+
+#[test]
+fn calls_functions_with_arguments() {
+    let source = "
+        function add(a, b) { return a + b; }
+        function missing(a, b) { return b; }
+        function count() { return arguments.length; }
+        function nothing() {}
+        var result = [add(1, 2), missing(1), count(1, 2, 3), nothing(), add.length].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "3,,3,,2");
+}
+
+#[test]
+fn recurses() {
+    let source = "
+        function factorial(n) { return n <= 1 ? 1 : n * factorial(n - 1); }
+        function fibonacci(n) { return n < 2 ? n : fibonacci(n - 1) + fibonacci(n - 2); }
+        function isEven(n) { return n === 0 ? true : isOdd(n - 1); }
+        function isOdd(n) { return n === 0 ? false : isEven(n - 1); }
+        var result = [factorial(10), fibonacci(15), isEven(10), isOdd(7)].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "3628800,610,true,true");
+}
+
+#[test]
+fn returns_early_from_nested_statements() {
+    let source = "
+        function find(items, wanted) {
+            for (var i = 0; i < items.length; i++) {
+                while (true) {
+                    if (items[i] === wanted) return i;
+                    break;
+                }
+            }
+            return -1;
+        }
+        var result = [find([4, 5, 6], 6), find([], 1)].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "2,-1");
+}
+
+#[test]
+fn passes_functions_as_values() {
+    let source = "
+        var twice = function (f, x) { return f(f(x)); };
+        var named = function inner(n) { return n > 0 ? inner(n - 1) : 'done'; };
+        var result = [twice(function (x) { return x * 3; }, 2), named(3), typeof inner].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "18,done,undefined");
+}
+
+#[test]
+fn reports_bad_calls() {
+    assert_eq!(error("var x = 1; x();"), "TypeError: 1 is not a function");
+    assert_eq!(
+        error("function f() { f(); } f();"),
+        "RangeError: Maximum call stack size exceeded"
+    );
+}
@@ -0,0 +1,55 @@
+use super::{error, run};
+
+#[test]
+fn raises_catchable_errors() {
+    // Assigning to an undeclared name fails, as in strict mode.
+    let source = "
+        const fixed = 1;
+        var caught = [];
+        var attempts = [
+            function () { return missing; },
+            function () { missing = 1; },
+            function () { fixed = 2; },
+            function () { fixed++; },
+            function () { fixed += 1; },
+            function () { undefined.x; },
+            function () { new (function () {}.bind())(); return 'bound ok'; },
+        ];
+        for (var attempt of attempts) {
+            try { caught.push(attempt()); } catch (e) { caught.push(e.name + ': ' + e.message); }
+        }
+        var result = caught.join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "ReferenceError: missing is not defined | \
+         ReferenceError: missing is not defined | \
+         TypeError: Assignment to constant variable. | \
+         TypeError: Assignment to constant variable. | \
+         TypeError: Assignment to constant variable. | \
+         TypeError: Cannot read properties of undefined (reading 'x') | \
+         bound ok"
+    );
+}
+
+#[test]
+fn leaves_constants_unchanged() {
+    let source = "
+        const fixed = 1;
+        try { fixed = 2; } catch (e) {}
+        try { fixed++; } catch (e) {}
+        var result = fixed;
+    ";
+
+    assert_eq!(run(source).unwrap(), "1");
+}
+
+#[test]
+fn reports_uncaught_errors() {
+    assert_eq!(error("missing;"), "ReferenceError: missing is not defined");
+    assert_eq!(
+        error("const c = 1; c = 2;"),
+        "TypeError: Assignment to constant variable."
+    );
+}
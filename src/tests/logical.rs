@@ -0,0 +1,64 @@
+use super::{error, run};
+
+#[test]
+fn returns_operands_of_logical_operators() {
+    let source = "
+        var result = [
+            0 || 'fallback',
+            'first' || 'second',
+            1 && 'both',
+            '' && 'skipped',
+            null ?? 'default',
+            0 ?? 'default',
+            '' ?? 'default',
+            undefined ?? null ?? 'last',
+            !'' && !0,
+        ].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "fallback | first | both |  | default | 0 |  | last | true"
+    );
+}
+
+#[test]
+fn short_circuits() {
+    let source = "
+        var log = [];
+        function note(value) { log.push(value); return value; }
+        true || note('or');
+        false && note('and');
+        1 ?? note('nullish');
+        true ? note('then') : note('else');
+        var a = null;
+        a ||= note('or assigned');
+        a &&= note('and assigned');
+        a ??= note('never');
+        var result = log.join() + ' | ' + a;
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "then,or assigned,and assigned | and assigned"
+    );
+}
+
+#[test]
+fn nests_ternaries() {
+    let source = "
+        function grade(n) { return n >= 90 ? 'A' : n >= 80 ? 'B' : n >= 70 ? 'C' : 'F'; }
+        var result = [grade(95), grade(85), grade(75), grade(10), (1 ? 0 : 2) ? 'yes' : 'no'].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "A,B,C,F,no");
+}
+
+#[test]
+fn rejects_mixing_nullish_with_logical_operators() {
+    assert_eq!(
+        error("var x = null || undefined ?? 1;"),
+        "SyntaxError: Unexpected token '??'"
+    );
+    assert_eq!(run("var result = (null || undefined) ?? 1;").unwrap(), "1");
+}
@@ -0,0 +1,90 @@
+//! Behaviour tests that run scripts through the lexer, parser and
+//! interpretter, and check the `result` they leave or the error they raise.
+
+mod array_methods;
+mod arrays;
+mod arrows;
+mod classes;
+mod closures;
+mod collector;
+mod constructors;
+mod control_flow;
+mod destructuring;
+mod equality;
+mod exceptions;
+mod files;
+mod functions;
+mod internal_errors;
+mod logical;
+mod numbers;
+mod objects;
+mod operators;
+mod optional_chaining;
+mod parameters;
+mod positions;
+mod programs;
+mod properties;
+mod scoping;
+mod strings;
+mod switch;
+
+use std::thread;
+
+use crate::INTERPRETTER_STACK_SIZE;
+use crate::engine::interpretter::Interpretter;
+use crate::engine::lexer::Lexer;
+use crate::engine::parser::Parser;
+use crate::engine::value_variant::JSValueVariant;
+use crate::errors::{EndiumError, ErrorKind};
+
+/// Runs `source` and gives its global `result` as a string. Scripts get the
+/// stack the interpretter runs on outside of tests, deep enough to reach the
+/// call stack limit.
+pub fn run(source: &str) -> Result<String, String> {
+    let source = source.to_string();
+
+    thread::Builder::new()
+        .stack_size(INTERPRETTER_STACK_SIZE)
+        .spawn(move || {
+            let mut interpretter = Interpretter::new(None);
+            execute(&mut interpretter, &source).map_err(|error| describe(&error))?;
+
+            let result = interpretter
+                .scope()
+                .borrow()
+                .get_state("result")
+                .map(|state| state.value().clone())
+                .unwrap_or(JSValueVariant::Undefined);
+
+            interpretter
+                .string_of(&result)
+                .map_err(|error| describe(&error))
+        })
+        .expect("the script thread should start")
+        .join()
+        .expect("the script should not panic")
+}
+
+/// Runs `source`, which is expected to fail, and gives its error as
+/// `Name: message`.
+pub fn error(source: &str) -> String {
+    match run(source) {
+        Ok(result) => panic!("expected an error, got the result {:?}", result),
+        Err(error) => error,
+    }
+}
+
+fn execute(interpretter: &mut Interpretter, source: &str) -> Result<(), EndiumError> {
+    let tokens = Lexer::new(source).tokenize()?;
+    let program = Parser::new(tokens).parse()?;
+
+    interpretter.execute(&program)
+}
+
+/// A thrown value's message already names it, as uncaught errors do.
+fn describe(error: &EndiumError) -> String {
+    match error.kind() {
+        ErrorKind::Exception => error.message().to_string(),
+        kind => format!("{}: {}", kind.name(), error.message()),
+    }
+}
@@ -0,0 +1,67 @@
+use super::{error, run};
+
+#[test]
+fn reads_numeric_literals() {
+    let source = "
+        var result = [1_000, 0x1_F, 0o1_7, 0b1_0, 1_0.2_5, 1e1_0, 017, 019, 0.0_1].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "1000,31,15,2,10.25,10000000000,15,19,0.01"
+    );
+}
+
+#[test]
+fn rejects_misplaced_separators() {
+    for source in ["1__0;", "1_;", "0x_1;", "1._5;"] {
+        assert_eq!(
+            error(source),
+            "SyntaxError: Numeric separators are not allowed here",
+            "{}",
+            source
+        );
+    }
+
+    for source in ["0_1;", "00_1;", "01_7;", "08_1;"] {
+        assert_eq!(
+            error(source),
+            "SyntaxError: Numeric separator can not be used after leading 0",
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn writes_numbers_as_strings() {
+    let source = "
+        var result = [
+            String(0.1 + 0.2),
+            String(-0),
+            String(1 / 0),
+            String(0 / 0),
+            String(1e21),
+            String(123456789012345680000),
+            String(1e-7),
+            String(0.000001),
+            String(2 ** 53 + 2),
+            String(-1.5e-10),
+            '' + 5e-324,
+        ].join(' ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "0.30000000000000004 0 Infinity NaN 1e+21 123456789012345680000 1e-7 0.000001 9007199254740994 -1.5e-10 5e-324"
+    );
+}
+
+#[test]
+fn reads_numbers_from_strings() {
+    let source = "
+        var result = [+'  42  ', +'0x1f', +'0b11', +'1e3', +'.5', +'5.', +'1_000', +'Infinity', +'-0'].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "42,31,3,1000,0.5,5,NaN,Infinity,0");
+}
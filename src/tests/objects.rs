@@ -0,0 +1,104 @@
+use super::{error, run};
+
+#[test]
+fn converts_keys_with_to_string() {
+    let source = "
+        var calls = 0;
+        var key = { toString: function () { calls++; return 'k'; } };
+        var o = { [key]: 1 };
+        o[key] = o.k + 1;
+        var { [key]: found } = o;
+        var result = [o.k, key in o, found, calls].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "2,true,2,4");
+}
+
+#[test]
+fn prefers_to_string_over_value_of_for_keys() {
+    let source = "
+        var key = { toString: function () { return 'a'; }, valueOf: function () { return 'b'; } };
+        var o = { a: 'string', b: 'number' };
+        var result = o[key];
+    ";
+
+    assert_eq!(run(source).unwrap(), "string");
+}
+
+#[test]
+fn propagates_errors_from_key_conversion() {
+    let throwing = "var key = { toString: function () { throw new Error('no key'); } };";
+
+    assert_eq!(
+        error(&format!("{} var o = {{}}; o[key];", throwing)),
+        "Error: no key"
+    );
+    assert_eq!(
+        error(&format!("{} ({{ [key]: 1 }});", throwing)),
+        "Error: no key"
+    );
+    assert_eq!(
+        error(&format!("{} key in {{}};", throwing)),
+        "Error: no key"
+    );
+    assert_eq!(
+        error("'a' in 5;"),
+        "TypeError: Cannot use 'in' operator to search for 'a' in 5"
+    );
+}
+
+#[test]
+fn reads_and_writes_properties() {
+    let source = "
+        var name = 'n';
+        var o = { a: 1, 'b c': 2, 3: 'three', name, shorthand() { return this.a; } };
+        o.a = 10;
+        o['d'] = 4;
+        delete o['b c'];
+        var keys = [];
+        for (var key in o) keys.push(key);
+        var result = [keys.join(), o.shorthand(), o[3], o.missing, 'a' in o].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "3,a,name,shorthand,d | 10 | three |  | true"
+    );
+}
+
+#[test]
+fn looks_properties_up_the_prototype_chain() {
+    let source = "
+        function Animal(name) { this.name = name; }
+        Animal.prototype.speak = function () { return this.name + ' speaks'; };
+        var dog = new Animal('Rex');
+        dog.bark = function () { return 'woof'; };
+        var shadowing = new Animal('Tom');
+        shadowing.speak = function () { return 'meow'; };
+        var result = [
+            dog.speak(),
+            shadowing.speak(),
+            'speak' in dog,
+            Object.getOwnPropertyDescriptor(dog, 'speak') === undefined,
+            dog.constructor === Animal,
+            dog instanceof Animal,
+        ].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "Rex speaks | meow | true | true | true | true"
+    );
+}
+
+#[test]
+fn reports_members_of_null_and_undefined() {
+    assert_eq!(
+        error("var o; o.x;"),
+        "TypeError: Cannot read properties of undefined (reading 'x')"
+    );
+    assert_eq!(
+        error("var o = null; o.x = 1;"),
+        "TypeError: Cannot set properties of null (setting 'x')"
+    );
+}
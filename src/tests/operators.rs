@@ -0,0 +1,109 @@
+use super::{error, run};
+
+#[test]
+fn follows_precedence_and_associativity() {
+    let source = "
+        var result = [
+            1 + 2 * 3,
+            (1 + 2) * 3,
+            2 ** 3 ** 2,
+            (-2) ** 2,
+            10 - 4 - 3,
+            100 / 10 / 5,
+            7 % 4 * 2,
+            1 + 2 + '3',
+            '1' + 2 + 3,
+            1 < 2 === true,
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "7,9,512,4,3,2,6,33,123,true");
+}
+
+#[test]
+fn applies_bitwise_and_shift_operators() {
+    let source = "
+        var result = [
+            5 & 3,
+            5 | 3,
+            5 ^ 3,
+            ~5,
+            1 << 31,
+            -16 >> 2,
+            -16 >>> 28,
+            2 ** 32 + 5 | 0,
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,7,6,-6,-2147483648,-4,15,5");
+}
+
+#[test]
+fn applies_unary_operators() {
+    let source = "
+        var n = 5;
+        var result = [
+            -n,
+            +'3',
+            +'',
+            +'x',
+            !0,
+            !!'a',
+            typeof n,
+            typeof undeclared,
+            typeof null,
+            typeof function () {},
+            void 1,
+            -(-n),
+        ].join();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "-5,3,0,NaN,true,true,number,undefined,object,function,,5"
+    );
+}
+
+#[test]
+fn updates_and_assigns_in_place() {
+    let source = "
+        var a = 1;
+        var b = a++ + ++a;
+        var o = { n: 2 };
+        o.n **= 3;
+        o.n -= 1;
+        var s = 'x';
+        s += 1;
+        var c = 10;
+        c >>= 1;
+        c <<= 2;
+        var result = [a, b, o.n, s, c, a--, --a].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "3,4,7,x1,20,3,1");
+}
+
+#[test]
+fn converts_objects_for_arithmetic() {
+    let source = "
+        var money = { valueOf: function () { return 5; }, toString: function () { return 'five'; } };
+        var result = [money + 1, money * 2, `${money}`, money + '', [1, 2] + [3], {} + 1].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "6 | 10 | five | 5 | 1,23 | [object Object]1"
+    );
+}
+
+#[test]
+fn rejects_invalid_operands() {
+    assert_eq!(
+        error("var n = 1; n++ = 2;"),
+        "SyntaxError: Invalid left-hand side in assignment"
+    );
+    assert_eq!(
+        error("undeclared + 1;"),
+        "ReferenceError: undeclared is not defined"
+    );
+}
@@ -0,0 +1,66 @@
+use super::{error, run};
+
+#[test]
+fn stops_at_null_and_undefined() {
+    let source = "
+        var o = { a: { b: { c: 'deep' } }, list: [1, 2], empty: '' };
+        var nothing = null;
+        var result = [
+            o?.a?.b?.c,
+            o.missing?.b.c.d,
+            nothing?.x,
+            o?.['a'].b.c,
+            o.list?.[1],
+            nothing?.[0],
+            o.empty?.padStart(2, '-'),
+            o.empty?.length,
+            (o.missing?.b) === undefined,
+        ].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "deep |  |  | deep | 2 |  | -- | 0 | true"
+    );
+}
+
+#[test]
+fn calls_optionally() {
+    let source = "
+        var calls = 0;
+        var o = {
+            name: 'o',
+            method() { calls++; return this.name; },
+        };
+        var nothing;
+        var result = [
+            o.method?.(),
+            o.missing?.(),
+            nothing?.method(calls++),
+            o?.method(),
+            calls,
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "o,,,o,2");
+}
+
+#[test]
+fn rejects_what_optional_chaining_cannot_do() {
+    assert_eq!(
+        error("var o = { x: 1 }; o.x?.();"),
+        "TypeError: 1 is not a function"
+    );
+    assert_eq!(
+        error("var o = {}; (o?.a).b;"),
+        "TypeError: Cannot read properties of undefined (reading 'b')"
+    );
+    assert_eq!(
+        error("var o = {}; o?.a = 1;"),
+        "SyntaxError: Invalid left-hand side in assignment"
+    );
+    assert_eq!(
+        error("var f; new f?.();"),
+        "SyntaxError: Invalid optional chain from new expression"
+    );
+}
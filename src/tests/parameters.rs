@@ -0,0 +1,50 @@
+use super::{error, run};
+
+#[test]
+fn fills_in_default_parameters() {
+    let source = "
+        var calls = 0;
+        function count() { return ++calls; }
+        function f(a, b = a * 2, c = count()) { return [a, b, c].join(); }
+        function g(x = 1) { return x; }
+        var result = [f(1), f(1, 5, 9), f(2, undefined), g(null), g(undefined), f.length].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,2,1 | 1,5,9 | 2,4,2 |  | 1 | 1");
+}
+
+#[test]
+fn collects_rest_parameters() {
+    let source = "
+        function f(first, ...rest) { return first + ':' + rest.join('+') + ':' + Array.isArray(rest); }
+        var sum = (...numbers) => numbers.reduce((total, n) => total + n, 0);
+        var result = [f(1), f(1, 2, 3), sum(), sum(1, 2, 3), f.length].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "1::true | 1:2+3:true | 0 | 6 | 1");
+}
+
+#[test]
+fn spreads_into_calls_and_literals() {
+    let source = "
+        function three(a, b, c) { return [a, b, c].join('-'); }
+        var parts = [2, 3];
+        var copied = [1, ...parts, ...'ab'];
+        var merged = { a: 1, ...{ b: 2, a: 3 }, ...null };
+        var result = [three(1, ...parts), three(...'xyz'), copied.join(), merged.a + merged.b].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "1-2-3 | x-y-z | 1,2,3,a,b | 5");
+}
+
+#[test]
+fn rejects_bad_parameter_lists() {
+    assert_eq!(
+        error("function f(...rest, last) {}"),
+        "SyntaxError: Rest parameter must be last formal parameter"
+    );
+    assert_eq!(
+        error("f(...5); function f() {}"),
+        "TypeError: number 5 is not iterable"
+    );
+}
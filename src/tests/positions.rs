@@ -0,0 +1,44 @@
+use super::execute;
+use crate::engine::interpretter::Interpretter;
+use crate::reporter::{ReportFormat, Reporter};
+
+/// Runs `source`, which is expected to fail, and renders its error as the
+/// command line does for `script.js`.
+fn report(source: &str, format: ReportFormat) -> String {
+    let mut interpretter = Interpretter::new(None);
+    let error = execute(&mut interpretter, source).expect_err("the script should fail");
+
+    Reporter::new(format, "script.js", source).render(&error)
+}
+
+#[test]
+fn points_at_syntax_errors() {
+    assert_eq!(
+        report("var a = 1;\nvar b = ;", ReportFormat::Human),
+        "script.js:2:9\n2 | var b = ;\n  |         ^\n\nSyntaxError: Unexpected token ';'"
+    );
+    assert_eq!(
+        report("var s = 'unterminated", ReportFormat::Human),
+        "script.js:1:9\n1 | var s = 'unterminated\n  |         ^\n\nSyntaxError: Invalid or unexpected token"
+    );
+}
+
+#[test]
+fn points_at_runtime_errors() {
+    assert_eq!(
+        report("let x = 1;\n\tx();", ReportFormat::Human),
+        "script.js:2:2\n2 | \tx();\n  | \t^\n\nUncaught TypeError: 1 is not a function\n    at <global> (2:2)"
+    );
+}
+
+#[test]
+fn gives_positions_and_stacks_as_json() {
+    let source = "function fail() {\n  throw new Error('deep');\n}\nfail();";
+
+    assert_eq!(
+        report(source, ReportFormat::Json),
+        "{\"kind\":\"Exception\",\"message\":\"Error: deep\",\"file\":\"script.js\",\
+         \"line\":2,\"column\":3,\"offset\":20,\
+         \"stack\":\"Error: deep\\n    at fail (2:9)\\n    at <global> (4:1)\"}"
+    );
+}
@@ -0,0 +1,49 @@
+use super::{error, run};
+
+#[test]
+fn runs_statements_in_order() {
+    let source = "
+        var log = [];
+        log.push(1);
+        { log.push(2); }
+        log.push(3);
+        var result = log.join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,2,3");
+}
+
+#[test]
+fn leaves_result_undefined_when_unset() {
+    assert_eq!(run("1 + 1;").unwrap(), "undefined");
+}
+
+#[test]
+fn inserts_semicolons_at_line_breaks() {
+    let source = "
+        var a = 1
+        var b = a
+        ++b
+        var result = a + ' ' + b
+    ";
+
+    assert_eq!(run(source).unwrap(), "1 2");
+}
+
+#[test]
+fn reports_syntax_errors_before_running() {
+    assert_eq!(
+        error("var result = 1; result = ;"),
+        "SyntaxError: Unexpected token ';'"
+    );
+}
+
+#[test]
+fn reports_uncaught_errors() {
+    assert_eq!(
+        error("undefinedName;"),
+        "ReferenceError: undefinedName is not defined"
+    );
+    assert_eq!(error("throw new TypeError('bad');"), "TypeError: bad");
+    assert_eq!(error("throw 42;"), "42");
+}
@@ -0,0 +1,158 @@
+use super::{error, run};
+
+#[test]
+fn defines_elements_with_attributes() {
+    let source = "
+        var a = [];
+        Object.defineProperty(a, 0, { value: 5 });
+        var d = Object.getOwnPropertyDescriptor(a, 0);
+        var result = [a.length, a[0], d.writable, d.enumerable, d.configurable].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,5,false,false,false");
+    assert_eq!(
+        error("var a = [1]; Object.defineProperty(a, 0, { writable: false }); a[0] = 2;"),
+        "TypeError: Cannot assign to read only property '0' of object"
+    );
+}
+
+#[test]
+fn calls_accessors_on_elements() {
+    let source = "
+        var a = [1, 2, 3];
+        var assigned;
+        Object.defineProperty(a, 1, {
+            get: function () { return this.length * 10; },
+            set: function (value) { assigned = value; },
+            enumerable: true,
+            configurable: true,
+        });
+        a[1] = 42;
+        var keys = [];
+        for (var key in a) keys.push(key);
+        var result = [
+            a[1],
+            assigned,
+            a.join('-'),
+            a.map(function (n) { return n + 1; }).join(),
+            keys.join(),
+            delete a[1],
+            1 in a,
+        ].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "30 | 42 | 1-30-3 | 2,31,4 | 0,1,2 | true | false"
+    );
+}
+
+#[test]
+fn keeps_a_read_only_length() {
+    let source = "
+        var a = [1, 2, 3];
+        Object.defineProperty(a, 'length', { writable: false });
+        var result = a.length + ' ' + Object.getOwnPropertyDescriptor(a, 'length').writable;
+    ";
+
+    assert_eq!(run(source).unwrap(), "3 false");
+    assert_eq!(
+        error("var a = [1]; Object.defineProperty(a, 'length', { writable: false }); a[1] = 2;"),
+        "TypeError: Cannot add property 1, object is not extensible"
+    );
+    assert_eq!(
+        error(
+            "var a = [1]; Object.defineProperty(a, 'length', { writable: false }); a.length = 0;"
+        ),
+        "TypeError: Cannot assign to read only property 'length' of object"
+    );
+    assert_eq!(
+        error("var a = [1]; Object.defineProperty(a, 'length', { writable: false }); a.push(2);"),
+        "TypeError: Cannot add property 1, object is not extensible"
+    );
+}
+
+#[test]
+fn stops_truncating_at_fixed_elements() {
+    let source = "
+        var a = [1, 2, 3];
+        Object.defineProperty(a, 1, { value: 2, configurable: false });
+        try { a.length = 0; } catch (e) {}
+        var result = a.length;
+    ";
+
+    assert_eq!(run(source).unwrap(), "2");
+}
+
+#[test]
+fn defines_accessors_in_literals() {
+    let source = "
+        var temperature = {
+            celsius: 20,
+            get fahrenheit() { return this.celsius * 9 / 5 + 32; },
+            set fahrenheit(value) { this.celsius = (value - 32) * 5 / 9; },
+            get readOnly() { return 'fixed'; },
+        };
+        temperature.fahrenheit = 212;
+        var d = Object.getOwnPropertyDescriptor(temperature, 'fahrenheit');
+        var result = [
+            temperature.celsius,
+            temperature.fahrenheit,
+            typeof d.get,
+            typeof d.set,
+            d.enumerable,
+            d.configurable,
+        ].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "100,212,function,function,true,true");
+    assert_eq!(
+        error("var o = { get x() { return 1; } }; o.x = 2;"),
+        "TypeError: Cannot set property x of #<Object> which has only a getter"
+    );
+}
+
+#[test]
+fn inherits_accessors() {
+    let source = "
+        var base = {
+            get double() { return this.value * 2; },
+            set double(value) { this.value = value / 2; },
+        };
+        function Derived(value) { this.value = value; }
+        Derived.prototype = base;
+        var d = new Derived(4);
+        var before = d.double;
+        d.double = 20;
+        var result = [before, d.value, Object.getOwnPropertyDescriptor(d, 'double') === undefined].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "8,10,true");
+}
+
+#[test]
+fn defines_properties_with_descriptors() {
+    let source = "
+        var o = {};
+        Object.defineProperties(o, {
+            hidden: { value: 'h', enumerable: false },
+            shown: { value: 's', enumerable: true, writable: true },
+        });
+        Object.defineProperty(o, 'computed', { get() { return this.shown + '!'; }, enumerable: true });
+        var keys = [];
+        for (var key in o) keys.push(key);
+        var result = [keys.join(), o.hidden, o.computed].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "shown,computed | h | s!");
+    assert_eq!(
+        error(
+            "var o = {}; Object.defineProperty(o, 'x', { value: 1 }); Object.defineProperty(o, 'x', { value: 2 });"
+        ),
+        "TypeError: Cannot redefine property: x"
+    );
+    assert_eq!(
+        error("Object.defineProperty({}, 'x', { get() {}, value: 1 });"),
+        "TypeError: Invalid property descriptor. Cannot both specify accessors and a value or writable attribute"
+    );
+}
@@ -0,0 +1,79 @@
+use super::{error, run};
+
+#[test]
+fn hoists_var_and_function_declarations() {
+    let source = "
+        var before = typeof later + ' ' + early() + ' ' + hoisted;
+        var hoisted = 'set';
+        function early() { return 'called'; }
+        function later() {}
+        function scoped() {
+            if (false) { var inner = 1; }
+            return inner;
+        }
+        var result = before + ' | ' + scoped();
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "function called undefined | undefined"
+    );
+}
+
+#[test]
+fn scopes_let_and_const_to_blocks() {
+    let source = "
+        let outer = 'outer';
+        {
+            let outer = 'inner';
+            const fixed = 1;
+        }
+        var leaked = typeof fixed;
+        var closures = [];
+        for (let i = 0; i < 2; i++) { closures.push(() => i); }
+        var result = [outer, leaked, closures[0](), closures[1]()].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "outer,undefined,0,1");
+}
+
+#[test]
+fn guards_the_temporal_dead_zone() {
+    assert_eq!(
+        error("x; let x = 1;"),
+        "ReferenceError: Cannot access 'x' before initialization"
+    );
+    assert_eq!(
+        error("function f() { return c; } f(); const c = 1;"),
+        "ReferenceError: Cannot access 'c' before initialization"
+    );
+    assert_eq!(
+        error("new K(); class K {}"),
+        "ReferenceError: Cannot access 'K' before initialization"
+    );
+    assert_eq!(
+        error("let y = y;"),
+        "ReferenceError: Cannot access 'y' before initialization"
+    );
+    assert_eq!(
+        error("typeof z; let z;"),
+        "ReferenceError: Cannot access 'z' before initialization"
+    );
+}
+
+#[test]
+fn rejects_redeclarations() {
+    assert_eq!(
+        error("let a; var a;"),
+        "SyntaxError: Identifier 'a' has already been declared"
+    );
+    assert_eq!(
+        error("const b = 1; function b() {}"),
+        "SyntaxError: Identifier 'b' has already been declared"
+    );
+    assert_eq!(run("var c = 1; var c = 2; var result = c;").unwrap(), "2");
+    assert_eq!(
+        error("const d;"),
+        "SyntaxError: Missing initializer in const declaration"
+    );
+}
@@ -0,0 +1,52 @@
+use super::{error, run};
+
+#[test]
+fn converts_this_and_arguments_with_to_primitive() {
+    let source = "
+        var text = { toString: function () { return 'hello'; } };
+        var two = { valueOf: function () { return 2; } };
+        var slice = String.prototype.slice;
+        var result = [
+            slice.call(text, two),
+            'hello'.indexOf({ toString: function () { return 'l'; } }),
+            'hello'.charAt(two),
+            'a-b-c'.split({ toString: function () { return '-'; } }, two).join(),
+            'x'.padStart(3, { toString: function () { return '.'; } }),
+        ].join(' | ');
+    ";
+
+    assert_eq!(run(source).unwrap(), "llo | 2 | l | a,b | ..x");
+}
+
+#[test]
+fn propagates_errors_from_conversions() {
+    let throwing = "{ valueOf: function () { throw new Error('no number'); } }";
+
+    assert_eq!(
+        error(&format!("'abc'.charAt({});", throwing)),
+        "Error: no number"
+    );
+    assert_eq!(
+        error(&format!("'abc'.slice(0, {});", throwing)),
+        "Error: no number"
+    );
+    assert_eq!(
+        error("String.prototype.trim.call(null);"),
+        "TypeError: String.prototype.trim called on null or undefined"
+    );
+}
+
+#[test]
+fn reads_code_units() {
+    let source = "
+        var s = 'a\u{1F600}b';
+        var codes = [];
+        for (var i = 0; i < s.length; i++) codes.push(s.charCodeAt(i));
+        var result = [s.length, codes.join(), s.codePointAt(1), s[3], s.at(-1)].join(' | ');
+    ";
+
+    assert_eq!(
+        run(source).unwrap(),
+        "4 | 97,55357,56832,98 | 128512 | b | b"
+    );
+}
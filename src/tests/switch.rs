@@ -0,0 +1,78 @@
+use super::{error, run};
+
+#[test]
+fn matches_cases_strictly() {
+    let source = "
+        function name(value) {
+            switch (value) {
+                case 1: return 'number';
+                case '1': return 'string';
+                case NaN: return 'nan';
+                case null: return 'null';
+                default: return 'other';
+            }
+        }
+        var result = [name(1), name('1'), name(NaN), name(null), name(undefined), name(true)].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "number,string,other,null,other,other");
+}
+
+#[test]
+fn falls_through_until_break() {
+    let source = "
+        function visit(value) {
+            var log = [];
+            switch (value) {
+                case 'a': log.push('a');
+                case 'b': log.push('b'); break;
+                default: log.push('default');
+                case 'c': log.push('c');
+            }
+            return log.join('');
+        }
+        var result = [visit('a'), visit('b'), visit('c'), visit('z')].join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "ab,b,c,defaultc");
+}
+
+#[test]
+fn evaluates_cases_in_order_and_lazily() {
+    let source = "
+        var evaluated = [];
+        function test(n) { evaluated.push(n); return n; }
+        switch (2) {
+            case test(1):
+            case test(2):
+            case test(3):
+                evaluated.push('body');
+        }
+        var loops = [];
+        for (var i = 0; i < 3; i++) {
+            switch (i) {
+                case 1: continue;
+                default: loops.push(i);
+            }
+        }
+        var result = evaluated.join() + ' | ' + loops.join();
+    ";
+
+    assert_eq!(run(source).unwrap(), "1,2,body | 0,2");
+}
+
+#[test]
+fn scopes_case_blocks() {
+    assert_eq!(
+        error("switch (1) { case 0: let x = 1; break; case 1: x; }"),
+        "ReferenceError: Cannot access 'x' before initialization"
+    );
+    assert_eq!(
+        error("switch (1) { case 0: let y; case 1: let y; }"),
+        "SyntaxError: Identifier 'y' has already been declared"
+    );
+    assert_eq!(
+        error("switch (1) { default: break; default: }"),
+        "SyntaxError: More than one default clause in switch statement"
+    );
+}
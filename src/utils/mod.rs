@@ -1,6 +0,0 @@
-pub mod memory_addresses;
-pub use memory_addresses::*;
-pub mod propery_path;
-pub use propery_path::*;
-pub mod list_node;
-pub use list_node::*;